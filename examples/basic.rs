@@ -27,26 +27,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         buy: true,          // PumpAmm BuyEvent 
         sell: true,        // PumpAmm SellEvent
         create_pool: true, // PumpAmm CreatePoolEvent
+        ..EventFilter::none() // 其余事件类型
     };
 
     let handler = FilteredLoggingEventHandler::new(filter);
 
-    // 订阅多个程序
+    // 在同一个连接中订阅多个程序
     let program_ids = vec![
-        "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P",      // Pump
-        "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA",     // PumpAmm
+        "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P".to_string(),      // Pump
+        "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA".to_string(),     // PumpAmm
     ];
 
-    for program_id in program_ids {
-        let client = client.clone();
-        let handler = handler.clone();
-        tokio::spawn(async move {
-            if let Err(e) = client.subscribe(program_id.to_string(), handler).await {
-                log::error!("订阅程序 {} 失败: {:?}", program_id, e);
+    let subscription = client.subscribe_many(program_ids, handler);
+
+    tokio::select! {
+        result = subscription => {
+            if let Err(e) = result {
+                log::error!("订阅失败: {:?}", e);
             }
-        });
+        }
+        _ = tokio::signal::ctrl_c() => {}
     }
-
-    tokio::signal::ctrl_c().await?;
     Ok(())
 }
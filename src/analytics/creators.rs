@@ -0,0 +1,204 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{CompleteEvent, NewToken, TradeEvent},
+};
+
+/// 创建者卖出持仓达到该比例（万分比）即视为砸盘
+const DUMP_THRESHOLD_BPS: u128 = 9_000;
+
+/// 单个代币的跟踪记录
+#[derive(Clone, Copy, Debug)]
+struct TokenRecord {
+    creator: Pubkey,
+    created_at: i64,
+    last_trade_at: i64,
+    graduated_at: Option<i64>,
+    /// 创建者累计买入的代币数量（含 dev buy）
+    creator_bought: u64,
+    /// 创建者累计卖出的代币数量
+    creator_sold: u64,
+    /// 毕业前创建者是否已经砸盘
+    dumped: bool,
+}
+
+impl TokenRecord {
+    /// 代币的生命周期：毕业的代币到毕业为止，其余到最后一笔交易为止
+    fn lifetime(&self) -> i64 {
+        self.graduated_at
+            .unwrap_or(self.last_trade_at)
+            .saturating_sub(self.created_at)
+            .max(0)
+    }
+}
+
+/// 创建者的历史发币统计
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CreatorStats {
+    /// 观察到的发币数量
+    pub launched: u64,
+    /// 其中已毕业的数量
+    pub graduated: u64,
+    /// 毕业率（万分比）
+    pub graduation_rate_bps: u64,
+    /// 生命周期中位数：毕业的代币到毕业为止，其余到最后一笔交易为止
+    pub median_lifetime: Duration,
+    /// 毕业前创建者卖出了至少 90% 持仓的代币数量
+    pub dumps: u64,
+    /// 砸盘率（万分比）
+    pub dump_rate_bps: u64,
+    /// 最近一次发币的链上时间
+    pub last_launch_at: i64,
+}
+
+#[derive(Default)]
+struct State {
+    tokens: HashMap<Pubkey, TokenRecord>,
+    creators: HashMap<Pubkey, Vec<Pubkey>>,
+}
+
+/// 创建者信誉登记
+///
+/// 按创建者统计 SDK 观察到的发币记录：发币数量、毕业率、生命周期中位数，以及创建者
+/// 在毕业前卖出大部分持仓（砸盘）的次数，狙击规则可以据此判断创建者信誉，
+/// 而不必维护固定的黑白名单。发币记录来自 `on_new_token`，因此包含创建者的 dev buy。
+///
+/// 只统计 SDK 运行期间观察到的代币，可以定期调用 [`CreatorRegistry::forget_before`]
+/// 清理过旧的记录。克隆后的实例共享同一份数据。
+#[derive(Clone, Default)]
+pub struct CreatorRegistry {
+    state: Arc<Mutex<State>>,
+}
+
+impl CreatorRegistry {
+    /// 创建空的登记表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建者的统计，没有观察到该创建者发币时返回 None
+    pub fn stats(&self, creator: &Pubkey) -> Option<CreatorStats> {
+        let state = self.lock();
+        let mints = state.creators.get(creator)?;
+        let records: Vec<&TokenRecord> = mints
+            .iter()
+            .filter_map(|mint| state.tokens.get(mint))
+            .collect();
+        if records.is_empty() {
+            return None;
+        }
+
+        let launched = records.len() as u64;
+        let graduated = records.iter().filter(|r| r.graduated_at.is_some()).count() as u64;
+        let dumps = records.iter().filter(|r| r.dumped).count() as u64;
+        let mut lifetimes: Vec<i64> = records.iter().map(|r| r.lifetime()).collect();
+        lifetimes.sort_unstable();
+        Some(CreatorStats {
+            launched,
+            graduated,
+            graduation_rate_bps: graduated * 10_000 / launched,
+            median_lifetime: Duration::from_secs(lifetimes[lifetimes.len() / 2] as u64),
+            dumps,
+            dump_rate_bps: dumps * 10_000 / launched,
+            last_launch_at: records
+                .iter()
+                .map(|r| r.created_at)
+                .max()
+                .unwrap_or_default(),
+        })
+    }
+
+    /// 代币的创建者
+    pub fn creator_of(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.lock().tokens.get(mint).map(|record| record.creator)
+    }
+
+    /// 清理创建时间早于 `timestamp`（链上时间）的代币记录
+    pub fn forget_before(&self, timestamp: i64) {
+        let mut state = self.lock();
+        state
+            .tokens
+            .retain(|_, record| record.created_at >= timestamp);
+        let State { tokens, creators } = &mut *state;
+        creators.retain(|_, mints| {
+            mints.retain(|mint| tokens.contains_key(mint));
+            !mints.is_empty()
+        });
+    }
+
+    /// 记录的代币数量
+    pub fn len(&self) -> usize {
+        self.lock().tokens.len()
+    }
+
+    /// 是否没有任何记录
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl EventHandler for CreatorRegistry {
+    fn on_new_token(&self, token: &NewToken, _ctx: &EventContext) {
+        let mut state = self.lock();
+        if state.tokens.contains_key(&token.mint) {
+            return;
+        }
+        state.tokens.insert(
+            token.mint,
+            TokenRecord {
+                creator: token.creator,
+                created_at: token.timestamp,
+                last_trade_at: token.timestamp,
+                graduated_at: None,
+                creator_bought: token.dev_buy.map_or(0, |buy| buy.token_amount),
+                creator_sold: 0,
+                dumped: false,
+            },
+        );
+        state
+            .creators
+            .entry(token.creator)
+            .or_default()
+            .push(token.mint);
+    }
+
+    // 创建交易中的 dev buy 在 on_new_token 之前分发，此时还没有记录，不会重复计入
+    fn on_trade_event(&self, event: &TradeEvent, _ctx: &EventContext) {
+        let mut state = self.lock();
+        let Some(record) = state.tokens.get_mut(&event.mint) else {
+            return;
+        };
+        record.last_trade_at = record.last_trade_at.max(event.timestamp);
+        if event.user != record.creator || record.graduated_at.is_some() {
+            return;
+        }
+        if event.is_buy {
+            record.creator_bought += event.token_amount;
+        } else {
+            record.creator_sold += event.token_amount;
+            if record.creator_bought > 0
+                && record.creator_sold as u128 * 10_000
+                    >= record.creator_bought as u128 * DUMP_THRESHOLD_BPS
+            {
+                record.dumped = true;
+            }
+        }
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, _ctx: &EventContext) {
+        if let Some(record) = self.lock().tokens.get_mut(&event.mint) {
+            record.graduated_at = Some(event.timestamp);
+            record.last_trade_at = record.last_trade_at.max(event.timestamp);
+        }
+    }
+}
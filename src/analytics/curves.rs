@@ -0,0 +1,280 @@
+use lru::LruCache;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    client::{AccountContext, EventContext, EventHandler},
+    models::{
+        BondingCurveAccount, CompleteEvent, CreateEvent, CreateV2Event, CurveState, TradeEvent,
+    },
+};
+
+/// 默认缓存储备的 mint 数量
+const DEFAULT_CAPACITY: usize = 100_000;
+
+/// Pump 全局配置中新币的初始真实代币储备，用于计算未观察到创建事件的代币的进度
+pub const DEFAULT_INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+/// 代币 bonding curve 的最新储备
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveReserves {
+    pub mint: Pubkey,
+    pub curve: CurveState,
+    /// 创建时的真实代币储备，未观察到创建事件时为 Pump 的默认值
+    pub initial_real_token_reserves: u64,
+    /// bonding curve 是否已完成（代币已迁移或等待迁移）
+    pub complete: bool,
+    /// 最近一次更新所在的槽位
+    pub slot: u64,
+}
+
+impl CurveReserves {
+    /// 用 `sol_in`（lamports，含手续费）买入可以得到的代币数量，`fee_bps` 为协议与创建者手续费率合计
+    ///
+    /// 不超过 bonding curve 中剩余的真实代币储备，曲线已完成时为 0。
+    pub fn buy_tokens_out(&self, sol_in: u64, fee_bps: u64) -> u64 {
+        if self.complete {
+            return 0;
+        }
+        let sol_in = sol_in as u128 * 10_000 / (10_000 + fee_bps as u128);
+        let out = (self.curve.virtual_token_reserves as u128 * sol_in)
+            .checked_div(self.curve.virtual_sol_reserves as u128 + sol_in)
+            .unwrap_or_default() as u64;
+        out.min(self.curve.real_token_reserves)
+    }
+
+    /// 卖出 `tokens_in` 个代币实际到手的 SOL（lamports，已扣除手续费），曲线已完成时为 0
+    pub fn sell_sol_out(&self, tokens_in: u64, fee_bps: u64) -> u64 {
+        if self.complete {
+            return 0;
+        }
+        let out = (self.curve.virtual_sol_reserves as u128 * tokens_in as u128)
+            .checked_div(self.curve.virtual_token_reserves as u128 + tokens_in as u128)
+            .unwrap_or_default();
+        let out = out.min(self.curve.real_sol_reserves as u128);
+        out.saturating_sub(out * fee_bps as u128 / 10_000) as u64
+    }
+
+    /// bonding curve 的完成进度（0.0 ~ 1.0），按已售出的真实代币储备计算
+    pub fn progress(&self) -> f64 {
+        if self.complete || self.initial_real_token_reserves == 0 {
+            return 1.0;
+        }
+        let sold = self
+            .initial_real_token_reserves
+            .saturating_sub(self.curve.real_token_reserves);
+        (sold as f64 / self.initial_real_token_reserves as f64).min(1.0)
+    }
+}
+
+/// bonding curve 储备跟踪
+///
+/// 由事件流维护每个代币的最新储备：CreateEvent / CreateV2Event 建立初始储备，
+/// 每笔 TradeEvent 更新为交易后的储备，CompleteEvent 标记曲线完成；观察列表的
+/// bonding curve 账户更新和 [`seed`](Self::seed)（例如用 `enrich::RpcFetcher::bonding_curve`
+/// 查询的结果）可以为订阅前就已存在的代币补充储备。活跃代币的报价和进度计算因此不需要任何 RPC 调用。
+///
+/// 乱序到达的旧交易不覆盖新储备。克隆后的实例共享同一份数据。
+#[derive(Clone)]
+pub struct CurveTracker {
+    curves: Arc<Mutex<LruCache<Pubkey, CurveReserves>>>,
+}
+
+impl Default for CurveTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CurveTracker {
+    /// 使用默认容量创建
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// 指定最多缓存储备的 mint 数量，超过后淘汰最久未更新的 mint，容量为 0 时按 1 处理
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            curves: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// 代币的最新储备
+    pub fn reserves(&self, mint: &Pubkey) -> Option<CurveReserves> {
+        self.lock().peek(mint).copied()
+    }
+
+    /// 用 bonding curve 账户数据补充储备，`slot` 为读取账户时的槽位，比已有储备旧时忽略
+    pub fn seed(&self, mint: Pubkey, curve: &BondingCurveAccount, slot: u64) {
+        update(
+            &mut self.lock(),
+            CurveReserves {
+                mint,
+                curve: CurveState {
+                    virtual_sol_reserves: curve.virtual_sol_reserves,
+                    virtual_token_reserves: curve.virtual_token_reserves,
+                    real_sol_reserves: curve.real_sol_reserves,
+                    real_token_reserves: curve.real_token_reserves,
+                },
+                initial_real_token_reserves: DEFAULT_INITIAL_REAL_TOKEN_RESERVES,
+                complete: curve.complete,
+                slot,
+            },
+        );
+    }
+
+    /// 停止跟踪代币
+    pub fn remove(&self, mint: &Pubkey) -> Option<CurveReserves> {
+        self.lock().pop(mint)
+    }
+
+    /// 跟踪中的代币数量
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 是否没有跟踪中的代币
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn created(
+        &self,
+        mint: Pubkey,
+        virtual_sol: u64,
+        virtual_token: u64,
+        real_token: u64,
+        slot: u64,
+    ) {
+        update(
+            &mut self.lock(),
+            CurveReserves {
+                mint,
+                curve: CurveState {
+                    virtual_sol_reserves: virtual_sol,
+                    virtual_token_reserves: virtual_token,
+                    real_sol_reserves: 0,
+                    real_token_reserves: real_token,
+                },
+                initial_real_token_reserves: real_token,
+                complete: false,
+                slot,
+            },
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<Pubkey, CurveReserves>> {
+        self.curves.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 写入储备，比已有储备旧时忽略；已知的初始储备和完成状态不会被覆盖
+fn update(curves: &mut LruCache<Pubkey, CurveReserves>, mut reserves: CurveReserves) {
+    if let Some(previous) = curves.peek(&reserves.mint) {
+        if previous.slot > reserves.slot {
+            return;
+        }
+        reserves.complete |= previous.complete;
+        if reserves.initial_real_token_reserves == DEFAULT_INITIAL_REAL_TOKEN_RESERVES {
+            reserves.initial_real_token_reserves = previous.initial_real_token_reserves;
+        }
+    }
+    curves.put(reserves.mint, reserves);
+}
+
+impl EventHandler for CurveTracker {
+    fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
+        self.created(
+            event.mint,
+            event.virtual_sol_reserves,
+            event.virtual_token_reserves,
+            event.real_token_reserves,
+            ctx.slot,
+        );
+    }
+
+    fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
+        self.created(
+            event.mint,
+            event.virtual_sol_reserves,
+            event.virtual_token_reserves,
+            event.real_token_reserves,
+            ctx.slot,
+        );
+    }
+
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        update(
+            &mut self.lock(),
+            CurveReserves {
+                mint: event.mint,
+                curve: CurveState {
+                    virtual_sol_reserves: event.virtual_sol_reserves,
+                    virtual_token_reserves: event.virtual_token_reserves,
+                    real_sol_reserves: event.real_sol_reserves,
+                    real_token_reserves: event.real_token_reserves,
+                },
+                initial_real_token_reserves: DEFAULT_INITIAL_REAL_TOKEN_RESERVES,
+                complete: false,
+                slot: ctx.slot,
+            },
+        );
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, _ctx: &EventContext) {
+        if let Some(reserves) = self.lock().get_mut(&event.mint) {
+            reserves.complete = true;
+        }
+    }
+
+    fn on_bonding_curve_update(
+        &self,
+        mint: &Pubkey,
+        curve: &BondingCurveAccount,
+        ctx: &AccountContext,
+    ) {
+        self.seed(*mint, curve, ctx.slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(complete: bool) -> CurveReserves {
+        CurveReserves {
+            mint: Pubkey::new_unique(),
+            curve: CurveState {
+                virtual_sol_reserves: 1_000_000,
+                virtual_token_reserves: 1_000_000,
+                real_sol_reserves: 500_000,
+                real_token_reserves: 500_000,
+            },
+            initial_real_token_reserves: 1_000_000,
+            complete,
+            slot: 1,
+        }
+    }
+
+    #[test]
+    fn sell_quote_deducts_fee_and_saturates() {
+        let reserves = curve(false);
+        assert_eq!(reserves.sell_sol_out(1_000, 0), 999);
+        assert_eq!(reserves.sell_sol_out(1_000, 100), 990);
+        assert_eq!(reserves.sell_sol_out(1_000, 20_000), 0);
+        assert_eq!(reserves.sell_sol_out(1_000, u64::MAX), 0);
+        assert_eq!(curve(true).sell_sol_out(1_000, 0), 0);
+    }
+
+    #[test]
+    fn buy_quote_is_capped_by_real_reserves() {
+        let reserves = curve(false);
+        assert_eq!(reserves.buy_tokens_out(1_000, 0), 999);
+        assert_eq!(reserves.buy_tokens_out(u64::MAX / 2, 0), 500_000);
+        assert_eq!(reserves.progress(), 0.5);
+    }
+}
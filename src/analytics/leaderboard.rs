@@ -0,0 +1,231 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::TradeEvent,
+};
+
+/// 排行依据
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RankBy {
+    /// 成交额（lamports）
+    Volume,
+    /// 盈亏近似值：窗口内的 SOL 净流入（卖出所得减买入花费，含手续费）
+    Pnl,
+    /// 交易笔数
+    TradeCount,
+}
+
+/// 交易者在窗口内的统计
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TraderStats {
+    /// 成交额（lamports，不含手续费）
+    pub volume: u128,
+    /// SOL 净流入（lamports），未平仓的持仓不计价，因此只是盈亏的近似值
+    pub pnl: i128,
+    /// 交易笔数
+    pub trades: u64,
+    /// 买入笔数
+    pub buys: u64,
+    /// 卖出笔数
+    pub sells: u64,
+}
+
+impl TraderStats {
+    fn add(&mut self, trade: &WindowTrade) {
+        self.volume += trade.volume as u128;
+        self.pnl += trade.pnl;
+        self.trades += 1;
+        if trade.is_buy {
+            self.buys += 1;
+        } else {
+            self.sells += 1;
+        }
+    }
+
+    fn remove(&mut self, trade: &WindowTrade) {
+        self.volume -= trade.volume as u128;
+        self.pnl -= trade.pnl;
+        self.trades -= 1;
+        if trade.is_buy {
+            self.buys -= 1;
+        } else {
+            self.sells -= 1;
+        }
+    }
+
+    fn rank_key(&self, by: RankBy) -> i128 {
+        match by {
+            RankBy::Volume => self.volume as i128,
+            RankBy::Pnl => self.pnl,
+            RankBy::TradeCount => self.trades as i128,
+        }
+    }
+}
+
+/// 排行榜条目
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    /// 交易者钱包
+    pub trader: Pubkey,
+    /// 窗口内统计
+    pub stats: TraderStats,
+}
+
+/// 窗口内的一笔交易
+struct WindowTrade {
+    timestamp: i64,
+    mint: Pubkey,
+    trader: Pubkey,
+    volume: u64,
+    pnl: i128,
+    is_buy: bool,
+}
+
+#[derive(Default)]
+struct State {
+    trades: VecDeque<WindowTrade>,
+    global: HashMap<Pubkey, TraderStats>,
+    per_mint: HashMap<Pubkey, HashMap<Pubkey, TraderStats>>,
+    /// 已观察到的最新链上时间，作为滑动窗口的时钟
+    now: i64,
+}
+
+impl State {
+    /// 把交易计入（`added == true`）或移出聚合结果
+    fn apply(&mut self, trade: &WindowTrade, added: bool) {
+        fn update(map: &mut HashMap<Pubkey, TraderStats>, trade: &WindowTrade, added: bool) {
+            let stats = map.entry(trade.trader).or_default();
+            if added {
+                stats.add(trade);
+            } else {
+                stats.remove(trade);
+            }
+            if stats.trades == 0 {
+                map.remove(&trade.trader);
+            }
+        }
+
+        update(&mut self.global, trade, added);
+        let mint_map = self.per_mint.entry(trade.mint).or_default();
+        update(mint_map, trade, added);
+        if mint_map.is_empty() {
+            self.per_mint.remove(&trade.mint);
+        }
+    }
+
+    fn evict(&mut self, window: i64) {
+        let cutoff = self.now - window;
+        while self.trades.front().is_some_and(|t| t.timestamp <= cutoff) {
+            if let Some(trade) = self.trades.pop_front() {
+                self.apply(&trade, false);
+            }
+        }
+    }
+}
+
+/// 交易者排行榜
+///
+/// 在滑动时间窗口内按交易者聚合 bonding curve 交易（`TradeEvent`），同时维护全局和按 mint 的排行，
+/// 可以按成交额、盈亏近似值或交易笔数查询。窗口以事件中的链上时间为时钟，重放历史数据时结果一致。
+/// PumpAmm 事件不包含 mint，且 quote 不一定是 SOL，因此不计入。
+///
+/// 可以直接作为 `EventHandler` 订阅，也可以在自定义处理器中调用 [`Leaderboard::record`]。
+/// 克隆后的实例共享同一份数据。需要多个窗口时创建多个实例。
+#[derive(Clone)]
+pub struct Leaderboard {
+    window: Duration,
+    state: Arc<Mutex<State>>,
+}
+
+impl Leaderboard {
+    /// 创建指定窗口长度的排行榜
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 窗口长度
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// 记录一笔交易
+    pub fn record(&self, event: &TradeEvent) {
+        let trade = WindowTrade {
+            timestamp: event.timestamp,
+            mint: event.mint,
+            trader: event.user,
+            volume: event.sol_amount,
+            pnl: event.sol_delta(),
+            is_buy: event.is_buy,
+        };
+        let window = self.window.as_secs() as i64;
+        let mut state = self.lock();
+        // 已经滑出窗口的迟到交易直接忽略
+        if trade.timestamp <= state.now - window {
+            return;
+        }
+        state.now = state.now.max(trade.timestamp);
+        state.apply(&trade, true);
+        state.trades.push_back(trade);
+        state.evict(window);
+    }
+
+    /// 全局排行前 `limit` 名
+    pub fn top(&self, by: RankBy, limit: usize) -> Vec<LeaderboardEntry> {
+        let state = self.lock();
+        rank(&state.global, by, limit)
+    }
+
+    /// 指定 mint 的排行前 `limit` 名
+    pub fn top_for_mint(&self, mint: &Pubkey, by: RankBy, limit: usize) -> Vec<LeaderboardEntry> {
+        let state = self.lock();
+        state
+            .per_mint
+            .get(mint)
+            .map(|traders| rank(traders, by, limit))
+            .unwrap_or_default()
+    }
+
+    /// 交易者在窗口内的全局统计
+    pub fn trader_stats(&self, trader: &Pubkey) -> Option<TraderStats> {
+        self.lock().global.get(trader).copied()
+    }
+
+    /// 清空所有数据
+    pub fn clear(&self) {
+        *self.lock() = State::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn rank(traders: &HashMap<Pubkey, TraderStats>, by: RankBy, limit: usize) -> Vec<LeaderboardEntry> {
+    let mut entries: Vec<LeaderboardEntry> = traders
+        .iter()
+        .map(|(trader, stats)| LeaderboardEntry {
+            trader: *trader,
+            stats: *stats,
+        })
+        .collect();
+    entries.sort_unstable_by_key(|entry| Reverse(entry.stats.rank_key(by)));
+    entries.truncate(limit);
+    entries
+}
+
+impl EventHandler for Leaderboard {
+    fn on_trade_event(&self, event: &TradeEvent, _ctx: &EventContext) {
+        self.record(event);
+    }
+}
@@ -0,0 +1,403 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{BuyEvent, CompleteEvent, CreatePoolEvent, NewToken, SellEvent, TradeEvent},
+    pda,
+};
+
+/// 默认的不活跃判定时间
+const DEFAULT_INACTIVE_AFTER: Duration = Duration::from_secs(3600);
+
+/// 两次检查不活跃代币之间的链上时间间隔（秒）
+const SWEEP_INTERVAL: i64 = 60;
+
+/// 代币所处的生命周期阶段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenPhase {
+    /// 已创建，创建交易之后还没有交易
+    Created,
+    /// 在 bonding curve 上交易
+    Trading,
+    /// bonding curve 已完成，等待迁移
+    CurveComplete,
+    /// PumpAmm 池已创建
+    PoolLive,
+    /// 超过不活跃判定时间没有任何交易，再次出现交易时回到对应的活跃阶段
+    Inactive,
+}
+
+/// 单个代币的生命周期记录
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenLifecycle {
+    pub mint: Pubkey,
+    pub phase: TokenPhase,
+    /// 创建的链上时间，SDK 启动前创建的代币为 None
+    pub created_at: Option<i64>,
+    /// bonding curve 完成的链上时间
+    pub completed_at: Option<i64>,
+    /// 迁移生成的 PumpAmm 池
+    pub pool: Option<Pubkey>,
+    /// 池创建的链上时间
+    pub pool_created_at: Option<i64>,
+    /// 最近一次创建、交易、完成或池事件的链上时间
+    pub last_activity_at: i64,
+}
+
+impl TokenLifecycle {
+    fn new(mint: Pubkey, phase: TokenPhase, timestamp: i64) -> Self {
+        Self {
+            mint,
+            phase,
+            created_at: None,
+            completed_at: None,
+            pool: None,
+            pool_created_at: None,
+            last_activity_at: timestamp,
+        }
+    }
+
+    /// 出现交易后应处的活跃阶段
+    fn trading_phase(&self) -> TokenPhase {
+        if self.pool.is_some() {
+            TokenPhase::PoolLive
+        } else if self.completed_at.is_some() {
+            TokenPhase::CurveComplete
+        } else {
+            TokenPhase::Trading
+        }
+    }
+}
+
+/// 阶段变化
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseTransition {
+    pub mint: Pubkey,
+    /// 变化前的阶段，首次观察到代币时为 None
+    pub from: Option<TokenPhase>,
+    pub to: TokenPhase,
+    /// 引起变化的事件的链上时间，不活跃判定时为判定时的链上时钟
+    pub timestamp: i64,
+}
+
+#[derive(Default)]
+struct State {
+    tokens: HashMap<Pubkey, TokenLifecycle>,
+    /// 池地址 -> mint
+    pools: HashMap<Pubkey, Pubkey>,
+    /// 首次出现在交易事件中、尚未确认是否为创建交易的代币
+    pending: Vec<(Pubkey, Signature)>,
+    /// 已观察到的最新链上时间
+    now: i64,
+    last_sweep: i64,
+}
+
+impl State {
+    /// 更新代币阶段，阶段变化时记录转换
+    fn set_phase(
+        &mut self,
+        mint: Pubkey,
+        phase: TokenPhase,
+        timestamp: i64,
+        transitions: &mut Vec<PhaseTransition>,
+    ) {
+        let Some(record) = self.tokens.get_mut(&mint) else {
+            return;
+        };
+        record.last_activity_at = record.last_activity_at.max(timestamp);
+        if record.phase != phase {
+            transitions.push(PhaseTransition {
+                mint,
+                from: Some(record.phase),
+                to: phase,
+                timestamp,
+            });
+            record.phase = phase;
+        }
+    }
+
+    /// 代币的其他事件先于确认到达时，立即以当前阶段登记
+    fn confirm(&mut self, mint: &Pubkey, transitions: &mut Vec<PhaseTransition>) {
+        let Some(index) = self.pending.iter().position(|(pending, _)| pending == mint) else {
+            return;
+        };
+        self.pending.swap_remove(index);
+        if let Some(record) = self.tokens.get(mint) {
+            transitions.push(PhaseTransition {
+                mint: *mint,
+                from: None,
+                to: record.phase,
+                timestamp: record.last_activity_at,
+            });
+        }
+    }
+
+    /// 确认其他交易中首次出现的代币：不是创建交易，以交易阶段登记
+    fn settle(&mut self, signature: &Signature, transitions: &mut Vec<PhaseTransition>) {
+        let tokens = &self.tokens;
+        self.pending.retain(|(mint, pending)| {
+            if pending == signature {
+                return true;
+            }
+            if let Some(record) = tokens.get(mint) {
+                transitions.push(PhaseTransition {
+                    mint: *mint,
+                    from: None,
+                    to: record.phase,
+                    timestamp: record.last_activity_at,
+                });
+            }
+            false
+        });
+    }
+}
+
+/// 代币生命周期跟踪
+///
+/// 按 mint 维护 Created → Trading → CurveComplete → PoolLive 的状态机，由新币、交易、
+/// bonding curve 完成和池创建事件驱动；超过 `inactive_after` 没有交易的代币进入 Inactive，
+/// 再次出现交易时回到对应的活跃阶段。阶段变化时调用回调，也可以随时查询。
+///
+/// SDK 启动前创建的代币在首次出现交易时以当前阶段登记（转换的 `from` 为 None）。
+/// PumpAmm 池只跟踪已登记代币的池和 pump 迁移生成的标准池。时间以事件中的链上时间为时钟。
+///
+/// 可以直接作为 `EventHandler` 订阅，克隆后的实例共享同一份状态。
+pub struct LifecycleTracker<F> {
+    inactive_after: Duration,
+    callback: Arc<F>,
+    state: Arc<Mutex<State>>,
+}
+
+impl<F> Clone for LifecycleTracker<F> {
+    fn clone(&self) -> Self {
+        Self {
+            inactive_after: self.inactive_after,
+            callback: Arc::clone(&self.callback),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<F> LifecycleTracker<F>
+where
+    F: Fn(&PhaseTransition) + Send + Sync,
+{
+    /// 创建跟踪器，`callback` 在代币阶段变化时调用
+    pub fn new(callback: F) -> Self {
+        Self {
+            inactive_after: DEFAULT_INACTIVE_AFTER,
+            callback: Arc::new(callback),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 设置不活跃判定时间
+    pub fn with_inactive_after(mut self, duration: Duration) -> Self {
+        self.inactive_after = duration;
+        self
+    }
+
+    /// 代币的生命周期记录
+    pub fn get(&self, mint: &Pubkey) -> Option<TokenLifecycle> {
+        self.lock().tokens.get(mint).copied()
+    }
+
+    /// 代币当前所处的阶段
+    pub fn phase(&self, mint: &Pubkey) -> Option<TokenPhase> {
+        self.lock().tokens.get(mint).map(|record| record.phase)
+    }
+
+    /// 处于指定阶段的所有代币
+    pub fn in_phase(&self, phase: TokenPhase) -> Vec<TokenLifecycle> {
+        self.lock()
+            .tokens
+            .values()
+            .filter(|record| record.phase == phase)
+            .copied()
+            .collect()
+    }
+
+    /// 池对应的 mint
+    pub fn mint_of_pool(&self, pool: &Pubkey) -> Option<Pubkey> {
+        self.lock().pools.get(pool).copied()
+    }
+
+    /// 清理最近活动早于 `timestamp`（链上时间）的代币
+    pub fn forget_before(&self, timestamp: i64) {
+        let mut state = self.lock();
+        state
+            .tokens
+            .retain(|_, record| record.last_activity_at >= timestamp);
+        let State { tokens, pools, .. } = &mut *state;
+        pools.retain(|_, mint| tokens.contains_key(mint));
+    }
+
+    /// 登记的代币数量
+    pub fn len(&self) -> usize {
+        self.lock().tokens.len()
+    }
+
+    /// 是否没有任何记录
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 在锁内更新状态，之后在释放锁之后依次回调阶段变化
+    fn update(
+        &self,
+        timestamp: i64,
+        ctx: &EventContext,
+        apply: impl FnOnce(&mut State, &mut Vec<PhaseTransition>),
+    ) {
+        let mut transitions = Vec::new();
+        {
+            let mut state = self.lock();
+            state.settle(&ctx.signature, &mut transitions);
+            state.now = state.now.max(timestamp);
+            apply(&mut state, &mut transitions);
+            self.sweep(&mut state, &mut transitions);
+        }
+        for transition in &transitions {
+            (self.callback)(transition);
+        }
+    }
+
+    /// 按链上时钟定期把长时间没有活动的代币标记为不活跃
+    fn sweep(&self, state: &mut State, transitions: &mut Vec<PhaseTransition>) {
+        let now = state.now;
+        if now - state.last_sweep < SWEEP_INTERVAL {
+            return;
+        }
+        state.last_sweep = now;
+        let cutoff = now - self.inactive_after.as_secs() as i64;
+        for record in state.tokens.values_mut() {
+            if record.phase != TokenPhase::Inactive && record.last_activity_at < cutoff {
+                transitions.push(PhaseTransition {
+                    mint: record.mint,
+                    from: Some(record.phase),
+                    to: TokenPhase::Inactive,
+                    timestamp: now,
+                });
+                record.phase = TokenPhase::Inactive;
+            }
+        }
+    }
+
+    /// 池中的交易
+    fn on_pool_trade(&self, pool: Pubkey, timestamp: i64, ctx: &EventContext) {
+        self.update(timestamp, ctx, |state, transitions| {
+            if let Some(mint) = state.pools.get(&pool).copied() {
+                state.set_phase(mint, TokenPhase::PoolLive, timestamp, transitions);
+            }
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<F> EventHandler for LifecycleTracker<F>
+where
+    F: Fn(&PhaseTransition) + Send + Sync,
+{
+    // 创建交易中的 dev buy 在 on_new_token 之前分发，先暂存为待确认，由这里改为 Created
+    fn on_new_token(&self, token: &NewToken, ctx: &EventContext) {
+        self.update(token.timestamp, ctx, |state, transitions| {
+            state.pending.retain(|(mint, _)| *mint != token.mint);
+            let record = state.tokens.entry(token.mint).or_insert_with(|| {
+                TokenLifecycle::new(token.mint, TokenPhase::Created, token.timestamp)
+            });
+            record.phase = TokenPhase::Created;
+            record.created_at = Some(token.timestamp);
+            transitions.push(PhaseTransition {
+                mint: token.mint,
+                from: None,
+                to: TokenPhase::Created,
+                timestamp: token.timestamp,
+            });
+        });
+    }
+
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        self.update(event.timestamp, ctx, |state, transitions| {
+            match state.tokens.get(&event.mint) {
+                Some(record) => {
+                    let phase = record.trading_phase();
+                    state.set_phase(event.mint, phase, event.timestamp, transitions);
+                }
+                None => {
+                    state.tokens.insert(
+                        event.mint,
+                        TokenLifecycle::new(event.mint, TokenPhase::Trading, event.timestamp),
+                    );
+                    state.pending.push((event.mint, ctx.signature));
+                }
+            }
+        });
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
+        self.update(event.timestamp, ctx, |state, transitions| {
+            state.confirm(&event.mint, transitions);
+            let record = state.tokens.entry(event.mint).or_insert_with(|| {
+                transitions.push(PhaseTransition {
+                    mint: event.mint,
+                    from: None,
+                    to: TokenPhase::CurveComplete,
+                    timestamp: event.timestamp,
+                });
+                TokenLifecycle::new(event.mint, TokenPhase::CurveComplete, event.timestamp)
+            });
+            record.completed_at = Some(event.timestamp);
+            let phase = record.trading_phase();
+            state.set_phase(event.mint, phase, event.timestamp, transitions);
+        });
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, ctx: &EventContext) {
+        let mint = event.base_mint;
+        let canonical = event.pool == pda::canonical_pump_pool(&mint);
+        self.update(event.timestamp, ctx, |state, transitions| {
+            state.confirm(&mint, transitions);
+            let record = match state.tokens.entry(mint) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(_) if !canonical => return,
+                Entry::Vacant(entry) => {
+                    transitions.push(PhaseTransition {
+                        mint,
+                        from: None,
+                        to: TokenPhase::PoolLive,
+                        timestamp: event.timestamp,
+                    });
+                    entry.insert(TokenLifecycle::new(
+                        mint,
+                        TokenPhase::PoolLive,
+                        event.timestamp,
+                    ))
+                }
+            };
+            // 已有池时只接受标准池替换
+            if record.pool.is_some() && !canonical {
+                return;
+            }
+            record.pool = Some(event.pool);
+            record.pool_created_at = Some(event.timestamp);
+            state.pools.insert(event.pool, mint);
+            state.set_phase(mint, TokenPhase::PoolLive, event.timestamp, transitions);
+        });
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        self.on_pool_trade(event.pool, event.timestamp, ctx);
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        self.on_pool_trade(event.pool, event.timestamp, ctx);
+    }
+}
@@ -0,0 +1,21 @@
+pub mod creators;
+pub mod curves;
+pub mod leaderboard;
+pub mod lifecycle;
+pub mod portfolio;
+pub mod price;
+pub mod sandwich;
+pub mod trending;
+pub mod wash;
+pub mod whale;
+
+pub use creators::{CreatorRegistry, CreatorStats};
+pub use curves::{CurveReserves, CurveTracker};
+pub use leaderboard::{Leaderboard, LeaderboardEntry, RankBy, TraderStats};
+pub use lifecycle::{LifecycleTracker, PhaseTransition, TokenLifecycle, TokenPhase};
+pub use portfolio::{HoldingValue, Portfolio, PortfolioValue, Quote};
+pub use price::{Price, PriceFeed, PriceSource};
+pub use sandwich::{SandwichDetector, SandwichLeg, SandwichReport};
+pub use trending::{TrendingDetector, TrendingSignal};
+pub use wash::{WashScore, WashTradeDetector};
+pub use whale::{WhaleAlert, WhaleRule, WhaleWatcher};
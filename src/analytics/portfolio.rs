@@ -0,0 +1,277 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{BuyEvent, CreatePoolEvent, SellEvent, TradeEvent},
+    pda::{canonical_pump_pool, WSOL_MINT},
+};
+
+/// 最新价格，以交易后的储备表示
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    /// SOL 储备（lamports），bonding curve 为虚拟储备
+    pub sol_reserves: u64,
+    /// 代币储备，bonding curve 为虚拟储备
+    pub token_reserves: u64,
+    /// 产生该价格的交易的链上时间
+    pub timestamp: i64,
+}
+
+impl Quote {
+    /// 按当前价格估算 `amount` 个代币的价值（lamports），不考虑滑点
+    pub fn value_of(&self, amount: u64) -> Option<u128> {
+        (amount as u128 * self.sol_reserves as u128).checked_div(self.token_reserves as u128)
+    }
+}
+
+/// 单个代币的持仓估值
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HoldingValue {
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// 估值（lamports），尚未观察到该代币的价格时为 None
+    pub value: Option<u128>,
+    /// 估值使用的价格
+    pub quote: Option<Quote>,
+}
+
+/// 钱包的持仓估值
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PortfolioValue {
+    /// 已定价持仓的总价值（lamports）
+    pub total: u128,
+    /// 各代币的持仓估值，按价值从高到低排列，未定价的排在最后
+    pub holdings: Vec<HoldingValue>,
+}
+
+#[derive(Default)]
+struct State {
+    /// 钱包 -> (mint -> 持仓)
+    wallets: HashMap<Pubkey, HashMap<Pubkey, u64>>,
+    /// 被任一钱包持有的 mint 的最新价格
+    quotes: HashMap<Pubkey, Quote>,
+    /// PumpAmm 池 -> mint，只记录被持有的 mint
+    pools: HashMap<Pubkey, Pubkey>,
+}
+
+impl State {
+    fn is_held(&self, mint: &Pubkey) -> bool {
+        self.wallets
+            .values()
+            .any(|holdings| holdings.contains_key(mint))
+    }
+
+    fn set_holding(&mut self, wallet: &Pubkey, mint: Pubkey, amount: u64) {
+        let Some(holdings) = self.wallets.get_mut(wallet) else {
+            return;
+        };
+        if amount == 0 {
+            holdings.remove(&mint);
+            self.forget_if_unheld(&mint);
+        } else if holdings.insert(mint, amount).is_none() {
+            self.pools.insert(canonical_pump_pool(&mint), mint);
+        }
+    }
+
+    fn apply_fill(&mut self, wallet: &Pubkey, mint: Pubkey, delta: i128) {
+        let Some(holdings) = self.wallets.get(wallet) else {
+            return;
+        };
+        let current = holdings.get(&mint).copied().unwrap_or_default() as i128;
+        let amount = (current + delta).clamp(0, u64::MAX as i128) as u64;
+        self.set_holding(wallet, mint, amount);
+    }
+
+    fn forget_if_unheld(&mut self, mint: &Pubkey) {
+        if !self.is_held(mint) {
+            self.quotes.remove(mint);
+            self.pools.retain(|_, pool_mint| pool_mint != mint);
+        }
+    }
+
+    fn update_quote(&mut self, mint: Pubkey, quote: Quote) {
+        if !self.is_held(&mint) {
+            return;
+        }
+        let entry = self.quotes.entry(mint).or_insert(quote);
+        if quote.timestamp >= entry.timestamp {
+            *entry = quote;
+        }
+    }
+
+    fn value(&self, holdings: &HashMap<Pubkey, u64>) -> PortfolioValue {
+        let mut values: Vec<HoldingValue> = holdings
+            .iter()
+            .map(|(mint, amount)| {
+                let quote = self.quotes.get(mint).copied();
+                HoldingValue {
+                    mint: *mint,
+                    amount: *amount,
+                    value: quote.and_then(|quote| quote.value_of(*amount)),
+                    quote,
+                }
+            })
+            .collect();
+        values.sort_unstable_by_key(|holding| Reverse(holding.value));
+        PortfolioValue {
+            total: values.iter().filter_map(|holding| holding.value).sum(),
+            holdings: values,
+        }
+    }
+}
+
+/// 钱包持仓估值
+///
+/// 跟踪一组钱包的 pump 代币持仓：初始持仓由调用方通过 [`Portfolio::apply_snapshot`]
+/// 写入（例如 RPC `getTokenAccountsByOwner` 的结果），之后按事件流中这些钱包的成交增减；
+/// 价格取自持仓代币最新一笔交易后的储备，bonding curve 交易使用虚拟储备，
+/// 毕业后的代币使用标准 PumpAmm 池（mint/WSOL）的储备。
+///
+/// 转账等非交易的余额变化不会被观察到，需要定期重新写入快照。克隆后的实例共享同一份数据。
+#[derive(Clone, Default)]
+pub struct Portfolio {
+    state: Arc<Mutex<State>>,
+}
+
+impl Portfolio {
+    /// 创建跟踪指定钱包的估值服务
+    pub fn new(wallets: impl IntoIterator<Item = Pubkey>) -> Self {
+        let portfolio = Self::default();
+        for wallet in wallets {
+            portfolio.add_wallet(wallet);
+        }
+        portfolio
+    }
+
+    /// 开始跟踪钱包，已跟踪时不做任何事
+    pub fn add_wallet(&self, wallet: Pubkey) {
+        self.lock().wallets.entry(wallet).or_default();
+    }
+
+    /// 停止跟踪钱包并丢弃其持仓
+    pub fn remove_wallet(&self, wallet: &Pubkey) {
+        let mut state = self.lock();
+        if let Some(holdings) = state.wallets.remove(wallet) {
+            for mint in holdings.keys() {
+                state.forget_if_unheld(mint);
+            }
+        }
+    }
+
+    /// 用快照替换钱包的全部持仓，钱包未被跟踪时忽略
+    pub fn apply_snapshot(
+        &self,
+        wallet: &Pubkey,
+        holdings: impl IntoIterator<Item = (Pubkey, u64)>,
+    ) {
+        let mut state = self.lock();
+        let Some(previous) = state.wallets.get_mut(wallet).map(std::mem::take) else {
+            return;
+        };
+        for (mint, amount) in holdings {
+            state.set_holding(wallet, mint, amount);
+        }
+        for mint in previous.keys() {
+            state.forget_if_unheld(mint);
+        }
+    }
+
+    /// 设置钱包单个代币的持仓，`amount` 为 0 时移除
+    pub fn set_holding(&self, wallet: &Pubkey, mint: Pubkey, amount: u64) {
+        self.lock().set_holding(wallet, mint, amount);
+    }
+
+    /// 钱包的持仓估值，钱包未被跟踪时返回 None
+    pub fn value(&self, wallet: &Pubkey) -> Option<PortfolioValue> {
+        let state = self.lock();
+        let holdings = state.wallets.get(wallet)?;
+        Some(state.value(holdings))
+    }
+
+    /// 所有跟踪钱包的已定价持仓总价值（lamports）
+    pub fn total_value(&self) -> u128 {
+        let state = self.lock();
+        state
+            .wallets
+            .values()
+            .map(|holdings| state.value(holdings).total)
+            .sum()
+    }
+
+    /// 代币的最新价格
+    pub fn quote(&self, mint: &Pubkey) -> Option<Quote> {
+        self.lock().quotes.get(mint).copied()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl EventHandler for Portfolio {
+    fn on_trade_event(&self, event: &TradeEvent, _ctx: &EventContext) {
+        let mut state = self.lock();
+        state.apply_fill(&event.user, event.mint, event.token_delta());
+        state.update_quote(
+            event.mint,
+            Quote {
+                sol_reserves: event.virtual_sol_reserves,
+                token_reserves: event.virtual_token_reserves,
+                timestamp: event.timestamp,
+            },
+        );
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, _ctx: &EventContext) {
+        let mut state = self.lock();
+        let Some(mint) = state.pools.get(&event.pool).copied() else {
+            return;
+        };
+        state.apply_fill(&event.user, mint, event.base_delta());
+        // 事件中的池储备为交易前的值
+        state.update_quote(
+            mint,
+            Quote {
+                sol_reserves: event
+                    .pool_quote_token_reserves
+                    .saturating_add(event.quote_amount_in),
+                token_reserves: event
+                    .pool_base_token_reserves
+                    .saturating_sub(event.base_amount_out),
+                timestamp: event.timestamp,
+            },
+        );
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, _ctx: &EventContext) {
+        let mut state = self.lock();
+        let Some(mint) = state.pools.get(&event.pool).copied() else {
+            return;
+        };
+        state.apply_fill(&event.user, mint, event.base_delta());
+        state.update_quote(
+            mint,
+            Quote {
+                sol_reserves: event
+                    .pool_quote_token_reserves
+                    .saturating_sub(event.quote_amount_out),
+                token_reserves: event
+                    .pool_base_token_reserves
+                    .saturating_add(event.base_amount_in),
+                timestamp: event.timestamp,
+            },
+        );
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, _ctx: &EventContext) {
+        let mut state = self.lock();
+        if event.quote_mint == WSOL_MINT && state.is_held(&event.base_mint) {
+            state.pools.insert(event.pool, event.base_mint);
+        }
+    }
+}
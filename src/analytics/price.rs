@@ -0,0 +1,225 @@
+use lru::LruCache;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{BuyEvent, CreatePoolEvent, SellEvent, TradeEvent},
+    pda::{canonical_pump_pool, WSOL_MINT},
+};
+
+/// 默认缓存价格的 mint 数量
+const DEFAULT_CAPACITY: usize = 100_000;
+
+/// pump 代币的小数位数
+const TOKEN_DECIMALS: i32 = 6;
+
+/// SOL 的小数位数
+const SOL_DECIMALS: i32 = 9;
+
+/// 价格来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceSource {
+    /// bonding curve 交易，储备为虚拟储备
+    BondingCurve,
+    /// PumpAmm 池交易
+    PumpAmm { pool: Pubkey },
+}
+
+/// 代币的最新价格
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Price {
+    pub mint: Pubkey,
+    /// 交易后的 SOL 储备（lamports）
+    pub sol_reserves: u64,
+    /// 交易后的代币储备
+    pub token_reserves: u64,
+    pub source: PriceSource,
+    /// 产生该价格的交易所在槽位
+    pub slot: u64,
+    /// 产生该价格的交易的链上时间
+    pub timestamp: i64,
+    /// SDK 收到该交易的本地时间，用于判断价格是否过期
+    pub received_at: Instant,
+}
+
+impl Price {
+    /// 每个完整代币的 SOL 价格
+    pub fn sol_per_token(&self) -> f64 {
+        if self.token_reserves == 0 {
+            return 0.0;
+        }
+        self.sol_reserves as f64 / self.token_reserves as f64
+            * 10f64.powi(TOKEN_DECIMALS - SOL_DECIMALS)
+    }
+
+    /// 按当前价格估算 `amount` 个代币（最小单位）的价值（lamports），不考虑滑点
+    pub fn value_of(&self, amount: u64) -> Option<u128> {
+        (amount as u128 * self.sol_reserves as u128).checked_div(self.token_reserves as u128)
+    }
+
+    /// 距离收到该价格经过的时间
+    pub fn age(&self) -> Duration {
+        self.received_at.elapsed()
+    }
+
+    /// 价格是否已经超过 `max_age` 没有更新
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+}
+
+struct State {
+    latest: LruCache<Pubkey, Price>,
+    /// 有订阅者的 mint
+    subscribers: HashMap<Pubkey, watch::Sender<Option<Price>>>,
+    /// PumpAmm 池 -> mint
+    pools: LruCache<Pubkey, Pubkey>,
+}
+
+impl State {
+    fn update(&mut self, price: Price) {
+        if let Some(previous) = self.latest.get(&price.mint) {
+            // 乱序到达的旧交易不覆盖新价格
+            if (previous.slot, previous.timestamp) > (price.slot, price.timestamp) {
+                return;
+            }
+        }
+        self.latest.put(price.mint, price);
+        if let Some(sender) = self.subscribers.get(&price.mint) {
+            if sender.send(Some(price)).is_err() {
+                // 所有订阅者都已经 drop
+                self.subscribers.remove(&price.mint);
+            }
+        }
+    }
+}
+
+/// 价格预言机
+///
+/// 由事件流中的交易维护每个代币的最新价格：bonding curve 交易使用交易后的虚拟储备，
+/// PumpAmm 交易使用交易后的池储备（只处理 quote 为 WSOL 的池，池与 mint 的对应关系
+/// 来自 `CreatePoolEvent` 或订阅时推导的标准池地址）。每个价格都带有槽位、链上时间和
+/// 本地接收时间，调用方可以据此判断是否过期。
+///
+/// 作为 `EventHandler` 订阅后，策略代码可以通过 [`PriceFeed::latest`] 查询，
+/// 或通过 [`PriceFeed::subscribe`] 等待价格变化。克隆后的实例共享同一份数据。
+#[derive(Clone)]
+pub struct PriceFeed {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for PriceFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PriceFeed {
+    /// 使用默认容量创建
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// 指定最多缓存价格的 mint 数量，超过后淘汰最久未更新的 mint，容量为 0 时按 1 处理
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            state: Arc::new(Mutex::new(State {
+                latest: LruCache::new(capacity),
+                subscribers: HashMap::new(),
+                pools: LruCache::new(capacity),
+            })),
+        }
+    }
+
+    /// 代币的最新价格
+    pub fn latest(&self, mint: &Pubkey) -> Option<Price> {
+        self.lock().latest.peek(mint).copied()
+    }
+
+    /// 订阅代币的价格变化
+    ///
+    /// 接收端的初始值为当前最新价格，尚未观察到该代币的交易时为 None。
+    pub fn subscribe(&self, mint: Pubkey) -> watch::Receiver<Option<Price>> {
+        let mut state = self.lock();
+        let latest = state.latest.peek(&mint).copied();
+        state.pools.put(canonical_pump_pool(&mint), mint);
+        state
+            .subscribers
+            .entry(mint)
+            .or_insert_with(|| watch::channel(latest).0)
+            .subscribe()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl EventHandler for PriceFeed {
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        self.lock().update(Price {
+            mint: event.mint,
+            sol_reserves: event.virtual_sol_reserves,
+            token_reserves: event.virtual_token_reserves,
+            source: PriceSource::BondingCurve,
+            slot: ctx.slot,
+            timestamp: event.timestamp,
+            received_at: ctx.timestamp,
+        });
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        let mut state = self.lock();
+        let Some(mint) = state.pools.get(&event.pool).copied() else {
+            return;
+        };
+        // 事件中的池储备为交易前的值
+        state.update(Price {
+            mint,
+            sol_reserves: event
+                .pool_quote_token_reserves
+                .saturating_add(event.quote_amount_in),
+            token_reserves: event
+                .pool_base_token_reserves
+                .saturating_sub(event.base_amount_out),
+            source: PriceSource::PumpAmm { pool: event.pool },
+            slot: ctx.slot,
+            timestamp: event.timestamp,
+            received_at: ctx.timestamp,
+        });
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        let mut state = self.lock();
+        let Some(mint) = state.pools.get(&event.pool).copied() else {
+            return;
+        };
+        state.update(Price {
+            mint,
+            sol_reserves: event
+                .pool_quote_token_reserves
+                .saturating_sub(event.quote_amount_out),
+            token_reserves: event
+                .pool_base_token_reserves
+                .saturating_add(event.base_amount_in),
+            source: PriceSource::PumpAmm { pool: event.pool },
+            slot: ctx.slot,
+            timestamp: event.timestamp,
+            received_at: ctx.timestamp,
+        });
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, _ctx: &EventContext) {
+        if event.quote_mint == WSOL_MINT {
+            self.lock().pools.put(event.pool, event.base_mint);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{BuyEvent, SellEvent, Side, TradeEvent},
+};
+
+/// 默认等待的槽位数：槽位落后最新交易超过该值后才分析，容忍乱序到达的交易
+const DEFAULT_SETTLE_SLOTS: u64 = 2;
+
+/// 夹子中的一笔交易
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SandwichLeg {
+    pub signature: Signature,
+    pub tx_index: u64,
+    pub side: Side,
+    /// 成交额（lamports，PumpAmm 交易为 quote 数量）
+    pub lamports: u64,
+    pub token_amount: u64,
+}
+
+/// 疑似夹子交易报告
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SandwichReport {
+    pub slot: u64,
+    /// bonding curve 交易为 mint，PumpAmm 交易为池地址
+    pub market: Pubkey,
+    pub attacker: Pubkey,
+    pub victim: Pubkey,
+    /// 攻击者在受害者之前、与受害者同方向的交易
+    pub front_run: SandwichLeg,
+    pub victim_trade: SandwichLeg,
+    /// 攻击者在受害者之后、反方向的交易
+    pub back_run: SandwichLeg,
+    /// 攻击者两笔交易的 SOL 净流入（lamports，不含手续费与小费）
+    pub attacker_gross: i128,
+}
+
+/// 槽位内的一笔交易
+#[derive(Clone, Copy)]
+struct SlotTrade {
+    market: Pubkey,
+    trader: Pubkey,
+    leg: SandwichLeg,
+}
+
+#[derive(Default)]
+struct State {
+    /// 槽位 -> 尚未分析的交易
+    slots: BTreeMap<u64, Vec<SlotTrade>>,
+    /// 已观察到的最高槽位
+    tip: u64,
+}
+
+/// 夹子（三明治）交易检测
+///
+/// 按槽位缓存交易，槽位稳定后按 `tx_index` 排序，在同一市场中查找
+/// “A 同向交易 → 受害者交易 → A 反向交易” 的模式，命中时调用回调。
+/// 这只是启发式判断：同一槽位内恰好先买后卖的普通交易者也会被标记。
+///
+/// 槽位在落后最新交易 `settle_slots` 个槽位后分析；流结束时可以调用 [`SandwichDetector::flush`]
+/// 分析剩余槽位。克隆后的实例共享同一份状态。
+pub struct SandwichDetector<F> {
+    settle_slots: u64,
+    wallets: Option<Arc<HashSet<Pubkey>>>,
+    callback: Arc<F>,
+    state: Arc<Mutex<State>>,
+}
+
+impl<F> Clone for SandwichDetector<F> {
+    fn clone(&self) -> Self {
+        Self {
+            settle_slots: self.settle_slots,
+            wallets: self.wallets.clone(),
+            callback: Arc::clone(&self.callback),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<F> SandwichDetector<F>
+where
+    F: Fn(&SandwichReport) + Send + Sync,
+{
+    /// 创建检测器，`callback` 对每个疑似被夹的交易调用一次
+    pub fn new(callback: F) -> Self {
+        Self {
+            settle_slots: DEFAULT_SETTLE_SLOTS,
+            wallets: None,
+            callback: Arc::new(callback),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 设置槽位稳定前等待的槽位数
+    pub fn with_settle_slots(mut self, slots: u64) -> Self {
+        self.settle_slots = slots;
+        self
+    }
+
+    /// 只报告这些钱包作为受害者的夹子，用于监控自己的交易
+    pub fn with_wallets(mut self, wallets: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.wallets = Some(Arc::new(wallets.into_iter().collect()));
+        self
+    }
+
+    /// 记录一笔交易，并分析已经稳定的槽位
+    fn record(&self, trade: SlotTrade, slot: u64) {
+        let settled = {
+            let mut state = self.lock();
+            // 已经分析过的槽位不再接收交易
+            if slot.saturating_add(self.settle_slots) < state.tip {
+                return;
+            }
+            state.slots.entry(slot).or_default().push(trade);
+            state.tip = state.tip.max(slot);
+            let cutoff = state.tip.saturating_sub(self.settle_slots);
+            let keep = state.slots.split_off(&cutoff);
+            std::mem::replace(&mut state.slots, keep)
+        };
+        self.analyze(settled);
+    }
+
+    /// 立即分析所有缓存的槽位
+    pub fn flush(&self) {
+        let slots = std::mem::take(&mut self.lock().slots);
+        self.analyze(slots);
+    }
+
+    fn analyze(&self, slots: BTreeMap<u64, Vec<SlotTrade>>) {
+        for (slot, trades) in slots {
+            for report in detect(slot, trades) {
+                if self
+                    .wallets
+                    .as_ref()
+                    .is_none_or(|wallets| wallets.contains(&report.victim))
+                {
+                    (self.callback)(&report);
+                }
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 在一个槽位的交易中查找夹子模式，每个受害者交易取距离最近的前后两笔攻击交易
+fn detect(slot: u64, trades: Vec<SlotTrade>) -> Vec<SandwichReport> {
+    let mut markets: HashMap<Pubkey, Vec<SlotTrade>> = HashMap::new();
+    for trade in trades {
+        markets.entry(trade.market).or_default().push(trade);
+    }
+
+    let mut reports = Vec::new();
+    for (market, mut trades) in markets {
+        if trades.len() < 3 {
+            continue;
+        }
+        trades.sort_by_key(|trade| trade.leg.tx_index);
+        for (j, victim) in trades.iter().enumerate() {
+            let front = trades[..j]
+                .iter()
+                .rev()
+                .find(|front| front.trader != victim.trader && front.leg.side == victim.leg.side);
+            let Some(front) = front else {
+                continue;
+            };
+            let back = trades[j + 1..]
+                .iter()
+                .find(|back| back.trader == front.trader && back.leg.side != front.leg.side);
+            let Some(back) = back else {
+                continue;
+            };
+            reports.push(SandwichReport {
+                slot,
+                market,
+                attacker: front.trader,
+                victim: victim.trader,
+                front_run: front.leg,
+                victim_trade: victim.leg,
+                back_run: back.leg,
+                attacker_gross: signed(&front.leg) + signed(&back.leg),
+            });
+        }
+    }
+    reports.sort_by_key(|report| report.victim_trade.tx_index);
+    reports
+}
+
+/// 交易者视角的 SOL 变化：卖出为正，买入为负
+fn signed(leg: &SandwichLeg) -> i128 {
+    match leg.side {
+        Side::Buy => -(leg.lamports as i128),
+        Side::Sell => leg.lamports as i128,
+    }
+}
+
+impl<F> EventHandler for SandwichDetector<F>
+where
+    F: Fn(&SandwichReport) + Send + Sync,
+{
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        let trade = SlotTrade {
+            market: event.mint,
+            trader: event.user,
+            leg: SandwichLeg {
+                signature: ctx.signature,
+                tx_index: ctx.tx_index,
+                side: event.side(),
+                lamports: event.sol_amount,
+                token_amount: event.token_amount,
+            },
+        };
+        self.record(trade, ctx.slot);
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        let trade = SlotTrade {
+            market: event.pool,
+            trader: event.user,
+            leg: SandwichLeg {
+                signature: ctx.signature,
+                tx_index: ctx.tx_index,
+                side: Side::Buy,
+                lamports: event.quote_amount_in,
+                token_amount: event.base_amount_out,
+            },
+        };
+        self.record(trade, ctx.slot);
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        let trade = SlotTrade {
+            market: event.pool,
+            trader: event.user,
+            leg: SandwichLeg {
+                signature: ctx.signature,
+                tx_index: ctx.tx_index,
+                side: Side::Sell,
+                lamports: event.quote_amount_out,
+                token_amount: event.base_amount_in,
+            },
+        };
+        self.record(trade, ctx.slot);
+    }
+}
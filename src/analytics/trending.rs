@@ -0,0 +1,342 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{BuyEvent, SellEvent, TradeEvent},
+};
+
+/// 默认用作基线的历史桶数量
+const DEFAULT_BASELINE_BUCKETS: usize = 10;
+
+/// 默认触发阈值（z-score）
+const DEFAULT_THRESHOLD: f64 = 3.0;
+
+/// 默认触发所需的当前桶最少交易笔数
+const DEFAULT_MIN_TRADES: u64 = 10;
+
+/// 同一市场两次触发之间的默认间隔
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// 成交额标准差的下限（相对基线均值），避免基线平稳时微小波动得到极大的 z-score
+const MIN_RELATIVE_STD: f64 = 0.1;
+
+/// 市场数量超过该值时清理基线窗口内已没有交易的市场
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// 热度信号及其依据的指标
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrendingSignal {
+    /// bonding curve 交易为 mint，PumpAmm 交易为池地址
+    pub market: Pubkey,
+    /// 当前桶的起始链上时间（Unix 秒）
+    pub bucket_start: i64,
+    /// 当前桶的成交额（lamports，PumpAmm 交易为 quote 数量）
+    pub volume: u128,
+    /// 当前桶的交易笔数
+    pub trades: u64,
+    /// 基线各桶成交额的均值
+    pub baseline_volume: f64,
+    /// 基线各桶交易笔数的均值
+    pub baseline_trades: f64,
+    /// 成交额相对基线的 z-score
+    pub volume_z: f64,
+    /// 交易笔数相对基线的 z-score
+    pub trades_z: f64,
+}
+
+impl TrendingSignal {
+    /// 综合评分：两项 z-score 中较小者，成交额和交易笔数需要同时加速
+    pub fn score(&self) -> f64 {
+        self.volume_z.min(self.trades_z)
+    }
+}
+
+/// 一个时间桶内的成交统计
+#[derive(Clone, Copy)]
+struct Bucket {
+    index: i64,
+    volume: u128,
+    trades: u64,
+}
+
+struct Market {
+    /// 按时间顺序排列的非空桶，只保留当前桶和基线窗口
+    buckets: VecDeque<Bucket>,
+    /// 首次观察到交易的桶
+    first_bucket: i64,
+    /// 上次触发的链上时间
+    last_fired: Option<i64>,
+}
+
+impl Market {
+    fn new(index: i64) -> Self {
+        Self {
+            buckets: VecDeque::new(),
+            first_bucket: index,
+            last_fired: None,
+        }
+    }
+
+    fn add(&mut self, index: i64, volume: u64) {
+        let position = self.buckets.iter().rposition(|b| b.index <= index);
+        match position {
+            Some(i) if self.buckets[i].index == index => {
+                let bucket = &mut self.buckets[i];
+                bucket.volume += volume as u128;
+                bucket.trades += 1;
+            }
+            _ => {
+                let at = position.map_or(0, |i| i + 1);
+                self.buckets.insert(
+                    at,
+                    Bucket {
+                        index,
+                        volume: volume as u128,
+                        trades: 1,
+                    },
+                );
+            }
+        }
+        self.first_bucket = self.first_bucket.min(index);
+    }
+
+    fn evict(&mut self, oldest: i64) {
+        while self.buckets.front().is_some_and(|b| b.index < oldest) {
+            self.buckets.pop_front();
+        }
+    }
+
+    fn latest(&self) -> Option<i64> {
+        self.buckets.back().map(|b| b.index)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    markets: HashMap<Pubkey, Market>,
+    /// 已观察到的最新桶，作为滑动窗口的时钟
+    now: i64,
+}
+
+/// 热门代币检测
+///
+/// 按固定长度的时间桶统计每个市场的成交额和交易笔数，用当前桶相对之前 `baseline_buckets`
+/// 个桶的 z-score 衡量加速程度。成交额和交易笔数的 z-score 都不低于阈值、
+/// 当前桶交易笔数达到下限时调用回调，同一市场在冷却期内只触发一次。
+///
+/// 只有在基线窗口开始之前就观察到交易的市场才会触发，刚上线的代币不会因为基线为空被误报。
+/// 时间以事件中的链上时间为时钟。
+///
+/// 可以直接作为 `EventHandler` 订阅，也可以在自定义处理器中调用 `on_*_event`。
+/// 克隆后的实例共享同一份状态。
+pub struct TrendingDetector<F> {
+    bucket_secs: i64,
+    baseline_buckets: usize,
+    threshold: f64,
+    min_trades: u64,
+    cooldown: Duration,
+    callback: Arc<F>,
+    state: Arc<Mutex<State>>,
+}
+
+impl<F> Clone for TrendingDetector<F> {
+    fn clone(&self) -> Self {
+        Self {
+            bucket_secs: self.bucket_secs,
+            baseline_buckets: self.baseline_buckets,
+            threshold: self.threshold,
+            min_trades: self.min_trades,
+            cooldown: self.cooldown,
+            callback: Arc::clone(&self.callback),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<F> TrendingDetector<F>
+where
+    F: Fn(&TrendingSignal, &EventContext) + Send + Sync,
+{
+    /// 创建检测器，`bucket` 为时间桶长度（按秒取整，至少 1 秒），`callback` 在市场变热时调用
+    pub fn new(bucket: Duration, callback: F) -> Self {
+        Self {
+            bucket_secs: (bucket.as_secs() as i64).max(1),
+            baseline_buckets: DEFAULT_BASELINE_BUCKETS,
+            threshold: DEFAULT_THRESHOLD,
+            min_trades: DEFAULT_MIN_TRADES,
+            cooldown: DEFAULT_COOLDOWN,
+            callback: Arc::new(callback),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 设置用作基线的历史桶数量，至少为 2
+    pub fn with_baseline_buckets(mut self, buckets: usize) -> Self {
+        self.baseline_buckets = buckets.max(2);
+        self
+    }
+
+    /// 设置触发阈值（z-score）
+    pub fn with_threshold(mut self, z: f64) -> Self {
+        self.threshold = z;
+        self
+    }
+
+    /// 设置触发所需的当前桶最少交易笔数
+    pub fn with_min_trades(mut self, trades: u64) -> Self {
+        self.min_trades = trades;
+        self
+    }
+
+    /// 设置同一市场两次触发之间的最短间隔
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// 市场当前桶的指标，基线窗口内没有交易时返回 None
+    pub fn signal(&self, market: &Pubkey) -> Option<TrendingSignal> {
+        let state = self.lock();
+        self.evaluate(market, state.markets.get(market)?, state.now)
+    }
+
+    /// 评分不低于 `min_score` 的所有市场，按评分从高到低排列
+    pub fn trending(&self, min_score: f64) -> Vec<TrendingSignal> {
+        let state = self.lock();
+        let mut signals: Vec<TrendingSignal> = state
+            .markets
+            .iter()
+            .filter_map(|(market, stats)| self.evaluate(market, stats, state.now))
+            .filter(|signal| signal.score() >= min_score)
+            .collect();
+        signals
+            .sort_unstable_by(|a, b| b.score().partial_cmp(&a.score()).unwrap_or(Ordering::Equal));
+        signals
+    }
+
+    /// 清空所有数据
+    pub fn clear(&self) {
+        *self.lock() = State::default();
+    }
+
+    /// 记录一笔交易，达到触发条件时在释放锁之后回调
+    fn record(&self, market: Pubkey, volume: u64, timestamp: i64, ctx: &EventContext) {
+        let index = timestamp.div_euclid(self.bucket_secs);
+        let signal = {
+            let mut state = self.lock();
+            // 已经滑出基线窗口的迟到交易直接忽略
+            if index < state.now - self.baseline_buckets as i64 {
+                return;
+            }
+            state.now = state.now.max(index);
+            let now = state.now;
+            let stats = state
+                .markets
+                .entry(market)
+                .or_insert_with(|| Market::new(index));
+            stats.add(index, volume);
+            stats.evict(now - self.baseline_buckets as i64);
+
+            let signal = self
+                .evaluate(&market, stats, now)
+                .filter(|signal| signal.trades >= self.min_trades)
+                .filter(|signal| signal.score() >= self.threshold)
+                .filter(|_| stats.first_bucket <= now - self.baseline_buckets as i64)
+                .filter(|_| {
+                    stats
+                        .last_fired
+                        .is_none_or(|last| timestamp - last >= self.cooldown.as_secs() as i64)
+                });
+            if signal.is_some() {
+                stats.last_fired = Some(timestamp);
+            }
+            if state.markets.len() >= PRUNE_THRESHOLD {
+                let cutoff = now - self.baseline_buckets as i64;
+                state
+                    .markets
+                    .retain(|_, stats| stats.latest().is_some_and(|latest| latest >= cutoff));
+            }
+            signal
+        };
+        if let Some(signal) = signal {
+            (self.callback)(&signal, ctx);
+        }
+    }
+
+    /// 计算市场在 `now` 所在桶相对基线的指标
+    fn evaluate(&self, market: &Pubkey, stats: &Market, now: i64) -> Option<TrendingSignal> {
+        let baseline_start = now - self.baseline_buckets as i64;
+        if stats.latest().is_none_or(|latest| latest < baseline_start) {
+            return None;
+        }
+        let current = stats
+            .buckets
+            .back()
+            .filter(|b| b.index == now)
+            .copied()
+            .unwrap_or(Bucket {
+                index: now,
+                volume: 0,
+                trades: 0,
+            });
+        let mut volumes = vec![0.0; self.baseline_buckets];
+        let mut trades = vec![0.0; self.baseline_buckets];
+        for bucket in &stats.buckets {
+            if (baseline_start..now).contains(&bucket.index) {
+                let i = (bucket.index - baseline_start) as usize;
+                volumes[i] = bucket.volume as f64;
+                trades[i] = bucket.trades as f64;
+            }
+        }
+        let (baseline_volume, volume_std) = mean_std(&volumes);
+        let (baseline_trades, trades_std) = mean_std(&trades);
+        // 交易笔数近似泊松分布，标准差不低于均值的平方根
+        let volume_std = volume_std.max(baseline_volume * MIN_RELATIVE_STD).max(1.0);
+        let trades_std = trades_std.max(baseline_trades.sqrt()).max(1.0);
+        Some(TrendingSignal {
+            market: *market,
+            bucket_start: now * self.bucket_secs,
+            volume: current.volume,
+            trades: current.trades,
+            baseline_volume,
+            baseline_trades,
+            volume_z: (current.volume as f64 - baseline_volume) / volume_std,
+            trades_z: (current.trades as f64 - baseline_trades) / trades_std,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 均值与总体标准差
+fn mean_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+impl<F> EventHandler for TrendingDetector<F>
+where
+    F: Fn(&TrendingSignal, &EventContext) + Send + Sync,
+{
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        self.record(event.mint, event.sol_amount, event.timestamp, ctx);
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        self.record(event.pool, event.quote_amount_in, event.timestamp, ctx);
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        self.record(event.pool, event.quote_amount_out, event.timestamp, ctx);
+    }
+}
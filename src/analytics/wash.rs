@@ -0,0 +1,301 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{Side, TradeEvent},
+};
+
+/// 计算评分所需的最少交易笔数
+const DEFAULT_MIN_TRADES: u64 = 10;
+
+/// 集中度统计的钱包数量
+const TOP_WALLETS: usize = 3;
+
+/// mint 数量超过该值时清理窗口内已没有交易的 mint
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// 单个 mint 的刷量评分
+///
+/// 所有比例均为万分比。`score_bps` 由三项指标加权得出：
+/// 往返成交占比 50%，前 3 个钱包的成交额占比 25%，买卖方向切换率 25%。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WashScore {
+    pub mint: Pubkey,
+    /// 综合评分（0 - 10000），越高越可疑
+    pub score_bps: u64,
+    /// 窗口内成交额（lamports）
+    pub volume: u128,
+    /// 窗口内交易笔数
+    pub trades: u64,
+    /// 参与交易的钱包数量
+    pub wallets: u64,
+    /// 被同一钱包反向成交抵消的成交额占比（买入额与卖出额中较小者的两倍）
+    pub round_trip_bps: u64,
+    /// 成交额最高的 3 个钱包的占比
+    pub top_wallets_bps: u64,
+    /// 同一钱包相邻两笔交易方向相反的比例
+    pub flip_rate_bps: u64,
+}
+
+/// 窗口内的一笔交易
+struct WindowTrade {
+    timestamp: i64,
+    trader: Pubkey,
+    side: Side,
+    lamports: u64,
+}
+
+/// 单个钱包在某个 mint 上的统计
+#[derive(Default)]
+struct WalletStats {
+    bought: u128,
+    sold: u128,
+    trades: u64,
+    flips: u64,
+    last_side: Option<Side>,
+}
+
+#[derive(Default)]
+struct State {
+    mints: HashMap<Pubkey, VecDeque<WindowTrade>>,
+    /// 已观察到的最新链上时间，作为滑动窗口的时钟
+    now: i64,
+}
+
+impl State {
+    fn evict(&mut self, mint: &Pubkey, window: i64) {
+        let cutoff = self.now - window;
+        if let Some(trades) = self.mints.get_mut(mint) {
+            while trades.front().is_some_and(|t| t.timestamp <= cutoff) {
+                trades.pop_front();
+            }
+            if trades.is_empty() {
+                self.mints.remove(mint);
+            }
+        }
+    }
+
+    fn prune(&mut self, window: i64) {
+        let cutoff = self.now - window;
+        self.mints
+            .retain(|_, trades| trades.back().is_some_and(|t| t.timestamp > cutoff));
+    }
+}
+
+/// 刷量交易检测
+///
+/// 在滑动时间窗口内按 mint 统计 bonding curve 交易（`TradeEvent`），用启发式指标衡量成交额
+/// 是否主要来自少数钱包的来回买卖，得出每个 mint 的刷量评分。事件中不包含资金来源，
+/// 因此无法识别由同一资金方控制的多个钱包之间的循环转账，只能从交易行为判断。
+///
+/// 窗口以事件中的链上时间为时钟。可以直接作为 `EventHandler` 订阅，
+/// 也可以调用 [`WashTradeDetector::record`]。克隆后的实例共享同一份数据。
+#[derive(Clone)]
+pub struct WashTradeDetector {
+    window: Duration,
+    min_trades: u64,
+    state: Arc<Mutex<State>>,
+}
+
+impl WashTradeDetector {
+    /// 创建指定窗口长度的检测器
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            min_trades: DEFAULT_MIN_TRADES,
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 设置计算评分所需的最少交易笔数，交易不足的 mint 不评分
+    pub fn with_min_trades(mut self, trades: u64) -> Self {
+        self.min_trades = trades;
+        self
+    }
+
+    /// 记录一笔交易
+    pub fn record(&self, event: &TradeEvent) {
+        let window = self.window.as_secs() as i64;
+        let mut state = self.lock();
+        // 已经滑出窗口的迟到交易直接忽略
+        if event.timestamp <= state.now - window {
+            return;
+        }
+        state.now = state.now.max(event.timestamp);
+        state
+            .mints
+            .entry(event.mint)
+            .or_default()
+            .push_back(WindowTrade {
+                timestamp: event.timestamp,
+                trader: event.user,
+                side: event.side(),
+                lamports: event.sol_amount,
+            });
+        state.evict(&event.mint, window);
+        if state.mints.len() >= PRUNE_THRESHOLD {
+            state.prune(window);
+        }
+    }
+
+    /// 指定 mint 在窗口内的评分，交易笔数不足时返回 None
+    pub fn score(&self, mint: &Pubkey) -> Option<WashScore> {
+        let window = self.window.as_secs() as i64;
+        let mut state = self.lock();
+        state.evict(mint, window);
+        score(mint, state.mints.get(mint)?, self.min_trades)
+    }
+
+    /// 评分不低于 `min_score_bps` 的所有 mint，按评分从高到低排列
+    pub fn suspicious(&self, min_score_bps: u64) -> Vec<WashScore> {
+        let window = self.window.as_secs() as i64;
+        let mut state = self.lock();
+        state.prune(window);
+        let mut scores: Vec<WashScore> = state
+            .mints
+            .iter()
+            .filter_map(|(mint, trades)| score(mint, trades, self.min_trades))
+            .filter(|score| score.score_bps >= min_score_bps)
+            .collect();
+        scores.sort_unstable_by_key(|score| Reverse(score.score_bps));
+        scores
+    }
+
+    /// 清空所有数据
+    pub fn clear(&self) {
+        *self.lock() = State::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn score(mint: &Pubkey, trades: &VecDeque<WindowTrade>, min_trades: u64) -> Option<WashScore> {
+    let count = trades.len() as u64;
+    if count < min_trades.max(1) {
+        return None;
+    }
+
+    let mut wallets: HashMap<Pubkey, WalletStats> = HashMap::new();
+    for trade in trades {
+        let stats = wallets.entry(trade.trader).or_default();
+        match trade.side {
+            Side::Buy => stats.bought += trade.lamports as u128,
+            Side::Sell => stats.sold += trade.lamports as u128,
+        }
+        if stats.last_side.is_some_and(|side| side != trade.side) {
+            stats.flips += 1;
+        }
+        stats.last_side = Some(trade.side);
+        stats.trades += 1;
+    }
+
+    let volume: u128 = wallets.values().map(|s| s.bought + s.sold).sum();
+    let round_trip: u128 = wallets.values().map(|s| s.bought.min(s.sold) * 2).sum();
+    let mut per_wallet: Vec<u128> = wallets.values().map(|s| s.bought + s.sold).collect();
+    per_wallet.sort_unstable_by_key(|volume| Reverse(*volume));
+    let top: u128 = per_wallet.iter().take(TOP_WALLETS).sum();
+    // 每个钱包的第一笔交易不可能是方向切换
+    let flip_chances = count - wallets.len() as u64;
+    let flips: u64 = wallets.values().map(|s| s.flips).sum();
+
+    let round_trip_bps = ratio_bps(round_trip, volume);
+    let top_wallets_bps = ratio_bps(top, volume);
+    let flip_rate_bps = ratio_bps(flips as u128, flip_chances as u128);
+    Some(WashScore {
+        mint: *mint,
+        score_bps: (round_trip_bps * 2 + top_wallets_bps + flip_rate_bps) / 4,
+        volume,
+        trades: count,
+        wallets: wallets.len() as u64,
+        round_trip_bps,
+        top_wallets_bps,
+        flip_rate_bps,
+    })
+}
+
+fn ratio_bps(part: u128, total: u128) -> u64 {
+    (part * 10_000).checked_div(total).unwrap_or_default() as u64
+}
+
+impl EventHandler for WashTradeDetector {
+    fn on_trade_event(&self, event: &TradeEvent, _ctx: &EventContext) {
+        self.record(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(mint: Pubkey, user: Pubkey, is_buy: bool, timestamp: i64) -> TradeEvent {
+        TradeEvent {
+            mint,
+            user,
+            is_buy,
+            sol_amount: 100,
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn single_wallet_round_trips_score_maximum() {
+        let detector = WashTradeDetector::new(Duration::from_secs(60));
+        let (mint, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+        for i in 0..10 {
+            detector.record(&trade(mint, wallet, i % 2 == 0, i));
+        }
+        let score = detector.score(&mint).unwrap();
+        assert_eq!((score.trades, score.wallets, score.volume), (10, 1, 1_000));
+        assert_eq!(score.round_trip_bps, 10_000);
+        assert_eq!(score.top_wallets_bps, 10_000);
+        assert_eq!(score.flip_rate_bps, 10_000);
+        assert_eq!(score.score_bps, 10_000);
+        assert_eq!(detector.suspicious(9_000), vec![score]);
+    }
+
+    #[test]
+    fn distinct_buyers_score_low() {
+        let detector = WashTradeDetector::new(Duration::from_secs(60));
+        let mint = Pubkey::new_unique();
+        for i in 0..10 {
+            detector.record(&trade(mint, Pubkey::new_unique(), true, i));
+        }
+        let score = detector.score(&mint).unwrap();
+        assert_eq!(score.round_trip_bps, 0);
+        assert_eq!(score.top_wallets_bps, 3_000);
+        assert_eq!(score.flip_rate_bps, 0);
+        assert_eq!(score.score_bps, 750);
+        assert!(detector.suspicious(1_000).is_empty());
+    }
+
+    #[test]
+    fn requires_min_trades_within_window() {
+        let detector = WashTradeDetector::new(Duration::from_secs(60)).with_min_trades(3);
+        let (mint, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+        detector.record(&trade(mint, wallet, true, 0));
+        detector.record(&trade(mint, wallet, false, 10));
+        assert_eq!(detector.score(&mint), None);
+
+        // 第一笔交易滑出窗口，剩余两笔加新交易仍为 3 笔
+        detector.record(&trade(mint, wallet, true, 65));
+        assert_eq!(detector.score(&mint).map(|s| s.trades), None);
+        detector.record(&trade(mint, wallet, false, 66));
+        assert_eq!(detector.score(&mint).map(|s| s.trades), Some(3));
+
+        // 迟到且已滑出窗口的交易被忽略
+        detector.record(&trade(mint, wallet, true, 1));
+        assert_eq!(detector.score(&mint).map(|s| s.trades), Some(3));
+
+        detector.clear();
+        assert_eq!(detector.score(&mint), None);
+    }
+}
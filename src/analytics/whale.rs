@@ -0,0 +1,296 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    models::{BuyEvent, SellEvent, Side, TradeEvent},
+};
+
+/// Pump 代币的总供应量（最小单位，10 亿枚 × 10^6）
+pub const PUMP_TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+/// 冷却记录超过该数量时清理已过期的记录
+const COOLDOWN_PRUNE_THRESHOLD: usize = 10_000;
+
+/// 告警规则
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WhaleRule {
+    /// 单笔成交额超过 `min_lamports`
+    ///
+    /// PumpAmm 交易以 quote 数量计算，pump 迁移生成的标准池 quote 为 WSOL。
+    LargeTrade { min_lamports: u64 },
+    /// 单个钱包持有某代币超过总供应量的 `min_share_bps`（万分比）
+    ///
+    /// 持仓由 SDK 启动后观察到的 bonding curve 买卖累计得出，不包含启动前的持仓和转账。
+    Accumulation { min_share_bps: u64 },
+    /// 代币创建者卖出
+    CreatorSell,
+}
+
+/// 告警内容
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WhaleAlert {
+    /// 大额交易
+    LargeTrade {
+        /// bonding curve 交易为 mint，PumpAmm 交易为池地址
+        market: Pubkey,
+        trader: Pubkey,
+        side: Side,
+        lamports: u64,
+        signature: Signature,
+    },
+    /// 钱包持仓占比超过阈值
+    Accumulation {
+        mint: Pubkey,
+        wallet: Pubkey,
+        /// 累计持仓（代币最小单位）
+        amount: u64,
+        /// 持仓占总供应量的万分比
+        share_bps: u64,
+    },
+    /// 创建者卖出
+    CreatorSell {
+        /// bonding curve 交易为 mint，PumpAmm 交易为池地址
+        market: Pubkey,
+        creator: Pubkey,
+        token_amount: u64,
+        lamports: u64,
+        signature: Signature,
+    },
+}
+
+/// 冷却键：(规则序号, 市场, 钱包)
+type CooldownKey = (usize, Pubkey, Pubkey);
+
+#[derive(Default)]
+struct State {
+    /// (mint, 钱包) -> 累计持仓
+    holdings: HashMap<(Pubkey, Pubkey), u64>,
+    /// 冷却键 -> 上次触发的链上时间
+    last_fired: HashMap<CooldownKey, i64>,
+}
+
+/// 巨鲸告警
+///
+/// 按配置的规则检查交易事件，命中时调用回调。每条规则可以设置冷却时间，
+/// 同一规则对同一 (市场, 钱包) 在冷却期内只触发一次，冷却以事件中的链上时间计算。
+///
+/// 可以直接作为 `EventHandler` 订阅，也可以在自定义处理器中调用 `on_*_event`。
+/// 克隆后的实例共享同一份状态。
+pub struct WhaleWatcher<F> {
+    rules: Vec<(WhaleRule, Duration)>,
+    callback: Arc<F>,
+    state: Arc<Mutex<State>>,
+}
+
+impl<F> Clone for WhaleWatcher<F> {
+    fn clone(&self) -> Self {
+        Self {
+            rules: self.rules.clone(),
+            callback: Arc::clone(&self.callback),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<F> WhaleWatcher<F>
+where
+    F: Fn(&WhaleAlert, &EventContext) + Send + Sync,
+{
+    /// 创建告警器，`callback` 在规则命中时调用
+    pub fn new(callback: F) -> Self {
+        Self {
+            rules: Vec::new(),
+            callback: Arc::new(callback),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 添加规则及其冷却时间（`Duration::ZERO` 表示不冷却）
+    pub fn with_rule(mut self, rule: WhaleRule, cooldown: Duration) -> Self {
+        self.rules.push((rule, cooldown));
+        self
+    }
+
+    /// 按规则检查一笔交易，命中的告警在释放锁之后再回调
+    fn check(&self, trade: Trade, ctx: &EventContext) {
+        let mut alerts = Vec::new();
+        {
+            let mut state = self.lock();
+            let holding = trade.track_holding.then(|| {
+                state.update_holding(trade.market, trade.trader, trade.side, trade.token_amount)
+            });
+            for (index, (rule, cooldown)) in self.rules.iter().enumerate() {
+                let Some(alert) = trade.evaluate(*rule, holding, ctx) else {
+                    continue;
+                };
+                let key = (index, trade.market, trade.trader);
+                if state.cooldown_elapsed(key, *cooldown, self.max_cooldown(), trade.timestamp) {
+                    alerts.push(alert);
+                }
+            }
+        }
+        for alert in &alerts {
+            (self.callback)(alert, ctx);
+        }
+    }
+
+    /// 所有规则中最长的冷却时间，超过它的冷却记录可以安全清理
+    fn max_cooldown(&self) -> Duration {
+        self.rules
+            .iter()
+            .map(|(_, cooldown)| *cooldown)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 规则检查需要的交易信息
+struct Trade {
+    /// bonding curve 交易为 mint，PumpAmm 交易为池地址
+    market: Pubkey,
+    trader: Pubkey,
+    creator: Pubkey,
+    side: Side,
+    lamports: u64,
+    token_amount: u64,
+    timestamp: i64,
+    /// 是否累计持仓（只对以 mint 为市场的 bonding curve 交易有意义）
+    track_holding: bool,
+}
+
+impl Trade {
+    fn evaluate(
+        &self,
+        rule: WhaleRule,
+        holding: Option<u64>,
+        ctx: &EventContext,
+    ) -> Option<WhaleAlert> {
+        match rule {
+            WhaleRule::LargeTrade { min_lamports } if self.lamports >= min_lamports => {
+                Some(WhaleAlert::LargeTrade {
+                    market: self.market,
+                    trader: self.trader,
+                    side: self.side,
+                    lamports: self.lamports,
+                    signature: ctx.signature,
+                })
+            }
+            WhaleRule::Accumulation { min_share_bps } => {
+                let amount = holding?;
+                let share_bps = (amount as u128 * 10_000 / PUMP_TOKEN_TOTAL_SUPPLY as u128) as u64;
+                (share_bps >= min_share_bps).then_some(WhaleAlert::Accumulation {
+                    mint: self.market,
+                    wallet: self.trader,
+                    amount,
+                    share_bps,
+                })
+            }
+            WhaleRule::CreatorSell if self.side == Side::Sell && self.trader == self.creator => {
+                Some(WhaleAlert::CreatorSell {
+                    market: self.market,
+                    creator: self.creator,
+                    token_amount: self.token_amount,
+                    lamports: self.lamports,
+                    signature: ctx.signature,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl State {
+    /// 冷却期已过时记录本次触发并返回 `true`
+    fn cooldown_elapsed(
+        &mut self,
+        key: CooldownKey,
+        cooldown: Duration,
+        max_cooldown: Duration,
+        timestamp: i64,
+    ) -> bool {
+        let cooldown = cooldown.as_secs() as i64;
+        if let Some(last) = self.last_fired.get(&key) {
+            if timestamp - last < cooldown {
+                return false;
+            }
+        }
+        if self.last_fired.len() >= COOLDOWN_PRUNE_THRESHOLD {
+            let retention = max_cooldown.as_secs() as i64;
+            self.last_fired
+                .retain(|_, last| timestamp - *last < retention);
+        }
+        self.last_fired.insert(key, timestamp);
+        true
+    }
+
+    /// 更新钱包持仓并返回最新持仓
+    fn update_holding(&mut self, mint: Pubkey, wallet: Pubkey, side: Side, amount: u64) -> u64 {
+        let key = (mint, wallet);
+        let holding = self.holdings.entry(key).or_default();
+        match side {
+            Side::Buy => *holding = holding.saturating_add(amount),
+            Side::Sell => *holding = holding.saturating_sub(amount),
+        }
+        let holding = *holding;
+        if holding == 0 {
+            self.holdings.remove(&key);
+        }
+        holding
+    }
+}
+
+impl<F> EventHandler for WhaleWatcher<F>
+where
+    F: Fn(&WhaleAlert, &EventContext) + Send + Sync,
+{
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        let trade = Trade {
+            market: event.mint,
+            trader: event.user,
+            creator: event.creator,
+            side: event.side(),
+            lamports: event.sol_amount,
+            token_amount: event.token_amount,
+            timestamp: event.timestamp,
+            track_holding: true,
+        };
+        self.check(trade, ctx);
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        let trade = Trade {
+            market: event.pool,
+            trader: event.user,
+            creator: event.coin_creator,
+            side: Side::Buy,
+            lamports: event.quote_amount_in,
+            token_amount: event.base_amount_out,
+            timestamp: event.timestamp,
+            track_holding: false,
+        };
+        self.check(trade, ctx);
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        let trade = Trade {
+            market: event.pool,
+            trader: event.user,
+            creator: event.coin_creator,
+            side: Side::Sell,
+            lamports: event.quote_amount_out,
+            token_amount: event.base_amount_in,
+            timestamp: event.timestamp,
+            track_holding: false,
+        };
+        self.check(trade, ctx);
+    }
+}
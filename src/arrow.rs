@@ -0,0 +1,272 @@
+//! Arrow RecordBatch 转换（需要启用 `arrow` feature）
+//!
+//! 把同一类型的一批事件转换为 Apache Arrow `RecordBatch`，可以直接交给 DataFusion / Polars
+//! 或写入 Parquet，无需经过 JSON。每个批次的前三列为交易上下文（`slot`、`tx_index`、
+//! `signature`），其后按模型字段顺序排列；Pubkey 与签名以 base58 字符串存储。
+
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, UInt16Array, UInt64Array,
+    UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+use crate::{
+    client::EventContext,
+    error::Result,
+    models::{
+        BuyEvent, CompleteEvent, CreateEvent, CreatePoolEvent, CreateV2Event, SellEvent, TradeEvent,
+    },
+};
+
+/// 可以批量转换为 Arrow `RecordBatch` 的事件类型
+pub trait ArrowEvent: Sized {
+    /// 批次的 schema（上下文列 + 事件字段列）
+    fn arrow_schema() -> SchemaRef;
+
+    /// 把一批事件及其上下文转换为 `RecordBatch`
+    fn to_record_batch(rows: &[(Self, EventContext)]) -> Result<RecordBatch>;
+}
+
+/// 交易上下文列
+fn context_fields() -> Vec<Field> {
+    vec![
+        Field::new("slot", DataType::UInt64, false),
+        Field::new("tx_index", DataType::UInt64, false),
+        Field::new("signature", DataType::Utf8, false),
+    ]
+}
+
+fn context_columns<'a>(contexts: impl Iterator<Item = &'a EventContext> + Clone) -> Vec<ArrayRef> {
+    vec![
+        Arc::new(UInt64Array::from_iter_values(
+            contexts.clone().map(|ctx| ctx.slot),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            contexts.clone().map(|ctx| ctx.tx_index),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            contexts.map(|ctx| ctx.signature.to_string()),
+        )),
+    ]
+}
+
+macro_rules! arrow_column {
+    (@type pubkey) => {
+        DataType::Utf8
+    };
+    (@type string) => {
+        DataType::Utf8
+    };
+    (@type u64) => {
+        DataType::UInt64
+    };
+    (@type i64) => {
+        DataType::Int64
+    };
+    (@type u16) => {
+        DataType::UInt16
+    };
+    (@type u8) => {
+        DataType::UInt8
+    };
+    (@type bool) => {
+        DataType::Boolean
+    };
+
+    (@array pubkey, $values:expr) => {
+        Arc::new(StringArray::from_iter_values(
+            $values.map(|v| v.to_string()),
+        )) as ArrayRef
+    };
+    (@array string, $values:expr) => {
+        Arc::new(StringArray::from_iter_values($values)) as ArrayRef
+    };
+    (@array u64, $values:expr) => {
+        Arc::new(UInt64Array::from_iter_values($values.copied())) as ArrayRef
+    };
+    (@array i64, $values:expr) => {
+        Arc::new(Int64Array::from_iter_values($values.copied())) as ArrayRef
+    };
+    (@array u16, $values:expr) => {
+        Arc::new(UInt16Array::from_iter_values($values.copied())) as ArrayRef
+    };
+    (@array u8, $values:expr) => {
+        Arc::new(UInt8Array::from_iter_values($values.copied())) as ArrayRef
+    };
+    (@array bool, $values:expr) => {
+        Arc::new($values.map(|v| Some(*v)).collect::<BooleanArray>()) as ArrayRef
+    };
+}
+
+/// 为事件声明列布局，字段列表必须与结构体定义一致（解构模式保证字段名完整）
+macro_rules! arrow_event {
+    ($ty:ident { $($field:ident: $kind:ident),* $(,)? }) => {
+        impl ArrowEvent for $ty {
+            fn arrow_schema() -> SchemaRef {
+                let mut fields = context_fields();
+                $(fields.push(Field::new(stringify!($field), arrow_column!(@type $kind), false));)*
+                Arc::new(Schema::new(fields))
+            }
+
+            fn to_record_batch(rows: &[(Self, EventContext)]) -> Result<RecordBatch> {
+                let _check = |value: &$ty| {
+                    let $ty { $($field: _),* } = value;
+                };
+                let mut columns = context_columns(rows.iter().map(|(_, ctx)| ctx));
+                $(columns.push(arrow_column!(@array $kind, rows.iter().map(|(e, _)| &e.$field)));)*
+                Ok(RecordBatch::try_new(Self::arrow_schema(), columns)?)
+            }
+        }
+    };
+}
+
+arrow_event!(CreateEvent {
+    name: string,
+    symbol: string,
+    uri: string,
+    mint: pubkey,
+    bonding_curve: pubkey,
+    user: pubkey,
+    creator: pubkey,
+    timestamp: i64,
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    token_total_supply: u64,
+    token_program: pubkey,
+    is_mayhem_mode: bool,
+});
+
+arrow_event!(CreateV2Event {
+    name: string,
+    symbol: string,
+    uri: string,
+    mint: pubkey,
+    bonding_curve: pubkey,
+    user: pubkey,
+    creator: pubkey,
+    timestamp: i64,
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    token_total_supply: u64,
+    token_program: pubkey,
+    is_mayhem_mode: bool,
+});
+
+arrow_event!(CompleteEvent {
+    user: pubkey,
+    mint: pubkey,
+    bonding_curve: pubkey,
+    timestamp: i64,
+});
+
+arrow_event!(TradeEvent {
+    mint: pubkey,
+    sol_amount: u64,
+    token_amount: u64,
+    is_buy: bool,
+    user: pubkey,
+    timestamp: i64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    real_sol_reserves: u64,
+    real_token_reserves: u64,
+    fee_recipient: pubkey,
+    fee_basis_points: u64,
+    fee: u64,
+    creator: pubkey,
+    creator_fee_basis_points: u64,
+    creator_fee: u64,
+    track_volume: bool,
+    total_unclaimed_tokens: u64,
+    total_claimed_tokens: u64,
+    current_sol_volume: u64,
+    last_update_timestamp: i64,
+    ix_name: string,
+});
+
+arrow_event!(BuyEvent {
+    timestamp: i64,
+    base_amount_out: u64,
+    max_quote_amount_in: u64,
+    user_base_token_reserves: u64,
+    user_quote_token_reserves: u64,
+    pool_base_token_reserves: u64,
+    pool_quote_token_reserves: u64,
+    quote_amount_in: u64,
+    lp_fee_basis_points: u64,
+    lp_fee: u64,
+    protocol_fee_basis_points: u64,
+    protocol_fee: u64,
+    quote_amount_in_with_lp_fee: u64,
+    user_quote_amount_in: u64,
+    pool: pubkey,
+    user: pubkey,
+    user_base_token_account: pubkey,
+    user_quote_token_account: pubkey,
+    protocol_fee_recipient: pubkey,
+    protocol_fee_recipient_token_account: pubkey,
+    coin_creator: pubkey,
+    coin_creator_fee_basis_points: u64,
+    coin_creator_fee: u64,
+    track_volume: bool,
+    total_unclaimed_tokens: u64,
+    total_claimed_tokens: u64,
+    current_sol_volume: u64,
+    last_update_timestamp: i64,
+    min_base_amount_out: u64,
+    ix_name: string,
+});
+
+arrow_event!(SellEvent {
+    timestamp: i64,
+    base_amount_in: u64,
+    min_quote_amount_out: u64,
+    user_base_token_reserves: u64,
+    user_quote_token_reserves: u64,
+    pool_base_token_reserves: u64,
+    pool_quote_token_reserves: u64,
+    quote_amount_out: u64,
+    lp_fee_basis_points: u64,
+    lp_fee: u64,
+    protocol_fee_basis_points: u64,
+    protocol_fee: u64,
+    quote_amount_out_without_lp_fee: u64,
+    user_quote_amount_out: u64,
+    pool: pubkey,
+    user: pubkey,
+    user_base_token_account: pubkey,
+    user_quote_token_account: pubkey,
+    protocol_fee_recipient: pubkey,
+    protocol_fee_recipient_token_account: pubkey,
+    coin_creator: pubkey,
+    coin_creator_fee_basis_points: u64,
+    coin_creator_fee: u64,
+});
+
+arrow_event!(CreatePoolEvent {
+    timestamp: i64,
+    index: u16,
+    creator: pubkey,
+    base_mint: pubkey,
+    quote_mint: pubkey,
+    base_mint_decimals: u8,
+    quote_mint_decimals: u8,
+    base_amount_in: u64,
+    quote_amount_in: u64,
+    pool_base_amount: u64,
+    pool_quote_amount: u64,
+    minimum_liquidity: u64,
+    initial_liquidity: u64,
+    lp_token_amount_out: u64,
+    pool_bump: u8,
+    pool: pubkey,
+    lp_mint: pubkey,
+    user_base_token_account: pubkey,
+    user_quote_token_account: pubkey,
+    coin_creator: pubkey,
+    is_mayhem_mode: bool,
+});
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use crate::models::PumpEvent;
+
+use super::handler::{EventContext, EventHandler};
+
+/// 没有收到区块元数据时，槽位落后最新交易超过该值后强制视为完成
+const MAX_PENDING_SLOTS: u64 = 32;
+
+/// 按槽位缓存已分发的事件，槽位完成后通过 `on_slot_complete` 整批交付
+///
+/// 槽位完成以该槽位的区块元数据到达为准（Yellowstone 在区块的全部交易之后推送）；
+/// 并行解析时还要等该槽位在途的交易分发完毕。
+pub(crate) struct SlotBatcher {
+    slots: BTreeMap<u64, Vec<(PumpEvent, EventContext)>>,
+    /// 已收到区块元数据的最高槽位
+    completed: Option<u64>,
+}
+
+impl SlotBatcher {
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: BTreeMap::new(),
+            completed: None,
+        }
+    }
+
+    /// 缓存一个已分发的事件
+    pub(crate) fn push(&mut self, event: PumpEvent, ctx: EventContext) {
+        self.slots.entry(ctx.slot).or_default().push((event, ctx));
+    }
+
+    /// 记录槽位的区块元数据已到达
+    pub(crate) fn block_completed(&mut self, slot: u64) {
+        self.completed = self.completed.max(Some(slot));
+    }
+
+    /// 交付已完成的槽位
+    ///
+    /// `dispatched_through` 为已确定全部分发完毕的最高槽位（内联解析或没有在途交易时为 None，
+    /// 表示没有限制）；`tip` 为最新交易所在槽位，用于在缺少区块元数据时强制交付过旧的槽位。
+    pub(crate) fn flush<H: EventHandler>(
+        &mut self,
+        dispatched_through: Option<u64>,
+        tip: u64,
+        handler: &H,
+    ) {
+        let completed = match (self.completed, dispatched_through) {
+            (Some(completed), Some(limit)) => Some(completed.min(limit)),
+            (completed, None) => completed,
+            (None, Some(_)) => None,
+        };
+        let forced = tip.checked_sub(MAX_PENDING_SLOTS);
+        let Some(through) = completed.max(forced) else {
+            return;
+        };
+        let pending = self.slots.split_off(&(through + 1));
+        let ready = std::mem::replace(&mut self.slots, pending);
+        Self::deliver(ready, handler);
+    }
+
+    /// 交付所有缓存的槽位，流结束时调用
+    pub(crate) fn flush_all<H: EventHandler>(&mut self, handler: &H) {
+        Self::deliver(std::mem::take(&mut self.slots), handler);
+    }
+
+    fn deliver<H: EventHandler>(
+        slots: BTreeMap<u64, Vec<(PumpEvent, EventContext)>>,
+        handler: &H,
+    ) {
+        for (slot, mut events) in slots {
+            // 并行无序分发时事件可能乱序，按交易在区块中的位置重新排好
+            events.sort_by_key(|(_, ctx)| ctx.tx_index);
+            handler.on_slot_complete(slot, &events);
+        }
+    }
+}
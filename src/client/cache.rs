@@ -0,0 +1,314 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use tokio::sync::watch;
+use yellowstone_grpc_proto::geyser::{SubscribeRequest, SubscribeRequestFilterAccounts};
+
+#[cfg(feature = "rpc")]
+use crate::{
+    enrich::RpcFetcher,
+    error::{Error, Result},
+};
+use crate::{
+    models::{
+        BondingCurveAccount, GlobalAccount, GlobalConfigAccount, PoolAccount, ProgramAccount,
+    },
+    pda,
+};
+
+/// 账户缓存在订阅请求中使用的账户过滤器名称
+pub const ACCOUNT_CACHE_FILTER: &str = "account_cache";
+
+struct CachedAccount {
+    account: ProgramAccount,
+    slot: u64,
+}
+
+#[derive(Default)]
+struct State {
+    accounts: HashMap<Pubkey, CachedAccount>,
+    /// 账户变化的订阅者
+    senders: HashMap<Pubkey, watch::Sender<Option<ProgramAccount>>>,
+}
+
+/// 解码后的程序账户缓存
+///
+/// 缓存 Pump / PumpAmm 的 Global、GlobalConfig、池和 bonding curve 账户。
+/// 通过 `GrpcClient::with_account_cache` 挂到客户端上后，缓存中的账户会自动添加账户订阅
+/// （运行中的订阅也会即时更新），链上变化后由订阅流更新缓存，读取时无需 RPC 调用。
+///
+/// 启用 `rpc` feature 并设置 [`with_rpc`](Self::with_rpc) 后，`resolve_*` 方法在未命中时
+/// 通过 RPC 获取账户、写入缓存并开始订阅，之后的读取都由订阅流保持最新。
+///
+/// 克隆后的实例共享同一份数据。
+#[derive(Clone)]
+pub struct AccountCache {
+    state: Arc<Mutex<State>>,
+    watched: Arc<watch::Sender<HashSet<Pubkey>>>,
+    #[cfg(feature = "rpc")]
+    rpc: Option<RpcFetcher>,
+}
+
+impl Default for AccountCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountCache {
+    /// 创建空的账户缓存
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+            watched: Arc::new(watch::Sender::new(HashSet::new())),
+            #[cfg(feature = "rpc")]
+            rpc: None,
+        }
+    }
+
+    /// 设置未命中时使用的 RPC 客户端（需要启用 `rpc` feature）
+    #[cfg(feature = "rpc")]
+    pub fn with_rpc(mut self, rpc: RpcFetcher) -> Self {
+        self.rpc = Some(rpc);
+        self
+    }
+
+    /// 订阅账户的变化，新加入时返回 `true`
+    pub fn watch(&self, address: Pubkey) -> bool {
+        self.watched
+            .send_if_modified(|watched| watched.insert(address))
+    }
+
+    /// 停止订阅账户并移出缓存，存在时返回 `true`
+    pub fn unwatch(&self, address: &Pubkey) -> bool {
+        {
+            let mut state = self.lock();
+            state.accounts.remove(address);
+            if let Some(sender) = state.senders.remove(address) {
+                sender.send_replace(None);
+            }
+        }
+        self.watched
+            .send_if_modified(|watched| watched.remove(address))
+    }
+
+    /// 订阅中的所有账户
+    pub fn watched(&self) -> Vec<Pubkey> {
+        self.watched.borrow().iter().copied().collect()
+    }
+
+    /// 写入账户并开始订阅其变化，旧槽位的数据不会覆盖新数据
+    pub fn insert(&self, address: Pubkey, account: ProgramAccount, slot: u64) {
+        self.store(address, account, slot);
+        self.watch(address);
+    }
+
+    /// 写入账户但不逐个订阅，旧槽位的数据不会覆盖新数据
+    ///
+    /// 用于批量预热（如 `MarketBootstrap`）：账户数量很大时逐个添加账户订阅会使订阅请求过大。
+    /// 预热的账户在订阅流中出现时（例如通过按程序 owner 的账户订阅）同样会被更新。
+    pub fn seed(&self, address: Pubkey, account: ProgramAccount, slot: u64) {
+        self.store(address, account, slot);
+    }
+
+    /// 缓存中的账户
+    pub fn get(&self, address: &Pubkey) -> Option<ProgramAccount> {
+        self.lock()
+            .accounts
+            .get(address)
+            .map(|cached| cached.account.clone())
+    }
+
+    /// 缓存中账户最近一次更新所在的槽位
+    pub fn slot(&self, address: &Pubkey) -> Option<u64> {
+        self.lock().accounts.get(address).map(|cached| cached.slot)
+    }
+
+    /// 订阅账户内容的变化，同时开始订阅该账户
+    ///
+    /// 接收端的初始值为当前缓存的内容，之后每次解码后的内容变化时更新；
+    /// 账户被 [`unwatch`](Self::unwatch) 时变为 None。
+    pub fn subscribe(&self, address: Pubkey) -> watch::Receiver<Option<ProgramAccount>> {
+        let receiver = {
+            let mut state = self.lock();
+            let current = state
+                .accounts
+                .get(&address)
+                .map(|cached| cached.account.clone());
+            state
+                .senders
+                .entry(address)
+                .or_insert_with(|| watch::Sender::new(current))
+                .subscribe()
+        };
+        self.watch(address);
+        receiver
+    }
+
+    /// 缓存中的 Pump 全局配置
+    pub fn global(&self) -> Option<GlobalAccount> {
+        match self.get(&pda::global())? {
+            ProgramAccount::Global(global) => Some(global),
+            _ => None,
+        }
+    }
+
+    /// 缓存中的 PumpAmm 全局配置
+    pub fn global_config(&self) -> Option<GlobalConfigAccount> {
+        match self.get(&pda::global_config())? {
+            ProgramAccount::GlobalConfig(config) => Some(config),
+            _ => None,
+        }
+    }
+
+    /// 缓存中的 PumpAmm 池
+    pub fn pool(&self, pool: &Pubkey) -> Option<PoolAccount> {
+        match self.get(pool)? {
+            ProgramAccount::Pool(pool) => Some(pool),
+            _ => None,
+        }
+    }
+
+    /// 缓存中 mint 的 bonding curve
+    pub fn bonding_curve(&self, mint: &Pubkey) -> Option<BondingCurveAccount> {
+        match self.get(&pda::bonding_curve(mint))? {
+            ProgramAccount::BondingCurve(curve) => Some(curve),
+            _ => None,
+        }
+    }
+
+    /// 读取账户，未命中时通过 RPC 获取并开始订阅，账户不存在时返回 None
+    ///
+    /// 未设置 RPC 客户端且未命中时返回错误。
+    #[cfg(feature = "rpc")]
+    pub async fn resolve(&self, address: &Pubkey) -> Result<Option<ProgramAccount>> {
+        if let Some(account) = self.get(address) {
+            return Ok(Some(account));
+        }
+        let Some(rpc) = &self.rpc else {
+            return Err(Error::Unknown(format!(
+                "账户 {address} 不在缓存中，且未设置 RPC 客户端"
+            )));
+        };
+        // 先订阅再获取，避免获取与订阅生效之间的变化被遗漏
+        self.watch(*address);
+        let (slot, mut accounts) = rpc.multiple_account_data(&[*address]).await?;
+        let Some(data) = accounts.pop().flatten() else {
+            return Ok(None);
+        };
+        let account = ProgramAccount::from_account_data(&data)?;
+        self.store(*address, account, slot);
+        Ok(self.get(address))
+    }
+
+    /// 读取 Pump 全局配置，未命中时通过 RPC 获取
+    #[cfg(feature = "rpc")]
+    pub async fn resolve_global(&self) -> Result<Option<GlobalAccount>> {
+        match self.resolve(&pda::global()).await? {
+            Some(ProgramAccount::Global(global)) => Ok(Some(global)),
+            Some(_) => Err(mismatch("Global")),
+            None => Ok(None),
+        }
+    }
+
+    /// 读取 PumpAmm 全局配置，未命中时通过 RPC 获取
+    #[cfg(feature = "rpc")]
+    pub async fn resolve_global_config(&self) -> Result<Option<GlobalConfigAccount>> {
+        match self.resolve(&pda::global_config()).await? {
+            Some(ProgramAccount::GlobalConfig(config)) => Ok(Some(config)),
+            Some(_) => Err(mismatch("GlobalConfig")),
+            None => Ok(None),
+        }
+    }
+
+    /// 读取 PumpAmm 池，未命中时通过 RPC 获取
+    #[cfg(feature = "rpc")]
+    pub async fn resolve_pool(&self, pool: &Pubkey) -> Result<Option<PoolAccount>> {
+        match self.resolve(pool).await? {
+            Some(ProgramAccount::Pool(pool)) => Ok(Some(pool)),
+            Some(_) => Err(mismatch("Pool")),
+            None => Ok(None),
+        }
+    }
+
+    /// 读取 mint 的 bonding curve，未命中时通过 RPC 获取
+    #[cfg(feature = "rpc")]
+    pub async fn resolve_bonding_curve(
+        &self,
+        mint: &Pubkey,
+    ) -> Result<Option<BondingCurveAccount>> {
+        match self.resolve(&pda::bonding_curve(mint)).await? {
+            Some(ProgramAccount::BondingCurve(curve)) => Ok(Some(curve)),
+            Some(_) => Err(mismatch("BondingCurve")),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn subscribe_watched(&self) -> watch::Receiver<HashSet<Pubkey>> {
+        self.watched.subscribe()
+    }
+
+    /// 用订阅流中的账户数据更新缓存，账户未被订阅或预热、或无法解码时返回 `false`
+    pub(crate) fn apply_account(&self, address: &Pubkey, data: &[u8], slot: u64) -> bool {
+        let known =
+            self.watched.borrow().contains(address) || self.lock().accounts.contains_key(address);
+        if !known {
+            return false;
+        }
+        match ProgramAccount::from_account_data(data) {
+            Ok(account) => {
+                self.store(*address, account, slot);
+                true
+            }
+            Err(e) => {
+                log::debug!("缓存账户 {} 解码失败: {}", address, e);
+                false
+            }
+        }
+    }
+
+    fn store(&self, address: Pubkey, account: ProgramAccount, slot: u64) {
+        let mut state = self.lock();
+        let previous = state.accounts.get(&address);
+        if previous.is_some_and(|cached| cached.slot > slot) {
+            return;
+        }
+        let changed = previous.is_none_or(|cached| cached.account != account);
+        if changed {
+            if let Some(sender) = state.senders.get(&address) {
+                sender.send_replace(Some(account.clone()));
+            }
+        }
+        state
+            .accounts
+            .insert(address, CachedAccount { account, slot });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "rpc")]
+fn mismatch(expected: &str) -> Error {
+    Error::ParseError(format!("账户类型不是 {expected}"))
+}
+
+/// 将缓存订阅的账户写入订阅请求的账户过滤器，没有订阅的账户时移除该过滤器
+pub(crate) fn apply_account_cache(watched: &HashSet<Pubkey>, request: &mut SubscribeRequest) {
+    if watched.is_empty() {
+        request.accounts.remove(ACCOUNT_CACHE_FILTER);
+        return;
+    }
+    request.accounts.insert(
+        ACCOUNT_CACHE_FILTER.to_string(),
+        SubscribeRequestFilterAccounts {
+            account: watched.iter().map(|address| address.to_string()).collect(),
+            owner: vec![],
+            filters: vec![],
+            nonempty_txn_signature: None,
+        },
+    );
+}
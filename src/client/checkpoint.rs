@@ -0,0 +1,222 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::error::{Error, Result};
+
+/// 槽位检查点存储
+///
+/// 记录已完整处理的最高槽位。配置到 `GrpcClient` 后，订阅会在启动时读取检查点并以
+/// `from_slot` 从下一个槽位开始回放，实现跨进程重启的至少一次（at-least-once）处理。
+/// 回放可能重复投递检查点之后的部分交易，可配合 `Config::with_dedup` 去重。
+pub trait CheckpointStore: Send + Sync {
+    /// 读取已保存的槽位，没有记录时返回 None
+    fn load(&self) -> Result<Option<u64>>;
+
+    /// 保存已完整处理的最高槽位
+    fn save(&self, slot: u64) -> Result<()>;
+}
+
+/// 内存检查点存储，只在进程内有效，适合测试或进程内多次重连
+#[derive(Default)]
+pub struct MemoryCheckpointStore {
+    slot: AtomicU64,
+}
+
+impl MemoryCheckpointStore {
+    /// 创建空的内存检查点存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    fn load(&self) -> Result<Option<u64>> {
+        let slot = self.slot.load(Ordering::Relaxed);
+        Ok((slot > 0).then_some(slot))
+    }
+
+    fn save(&self, slot: u64) -> Result<()> {
+        self.slot.fetch_max(slot, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// 文件检查点存储
+///
+/// 以文本形式把槽位写入文件，先写临时文件再重命名，避免进程崩溃时留下半写的检查点。
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// 使用指定路径创建文件检查点存储，文件不存在时视为没有记录
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&self) -> Result<Option<u64>> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => content
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|e| Error::Unknown(format!("检查点文件格式错误: {e}"))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::Unknown(format!("读取检查点失败: {e}"))),
+        }
+    }
+
+    fn save(&self, slot: u64) -> Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, slot.to_string())
+            .and_then(|_| fs::rename(&tmp, &self.path))
+            .map_err(|e| Error::Unknown(format!("写入检查点失败: {e}")))
+    }
+}
+
+/// SQLite 检查点存储（需要启用 `sqlite` feature）
+///
+/// 检查点保存在 `checkpoints(name, slot)` 表中，多个订阅可以用不同的 `name` 共用一个数据库。
+#[cfg(feature = "sqlite")]
+pub struct SqliteCheckpointStore {
+    conn: std::sync::Mutex<rusqlite::Connection>,
+    name: String,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteCheckpointStore {
+    /// 打开（或创建）数据库，并以 `name` 作为检查点名称
+    pub fn open(path: impl AsRef<std::path::Path>, name: impl Into<String>) -> Result<Self> {
+        let conn = rusqlite::Connection::open(path).map_err(sqlite_error)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS checkpoints (name TEXT PRIMARY KEY, slot INTEGER NOT NULL)",
+            [],
+        )
+        .map_err(sqlite_error)?;
+        Ok(Self {
+            conn: std::sync::Mutex::new(conn),
+            name: name.into(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl CheckpointStore for SqliteCheckpointStore {
+    fn load(&self) -> Result<Option<u64>> {
+        use rusqlite::OptionalExtension;
+
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.query_row(
+            "SELECT slot FROM checkpoints WHERE name = ?1",
+            [&self.name],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map(|slot| slot.map(|slot| slot as u64))
+        .map_err(sqlite_error)
+    }
+
+    fn save(&self, slot: u64) -> Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT INTO checkpoints (name, slot) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET slot = MAX(slot, excluded.slot)",
+            rusqlite::params![self.name, slot as i64],
+        )
+        .map(|_| ())
+        .map_err(sqlite_error)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite_error(e: rusqlite::Error) -> Error {
+    Error::Unknown(format!("SQLite 检查点错误: {e}"))
+}
+
+/// 订阅内的槽位水位线
+///
+/// 槽位完成以其区块元数据到达为准（Yellowstone 在区块的全部交易之后推送）；
+/// 并行解析时还要等该槽位在途的交易分发完毕，之后才写入检查点。
+/// Processed / Confirmed 承诺级别下被分叉丢弃的槽位同样会推送区块元数据，检查点只在 Finalized 下与链上一致。
+pub(crate) struct SlotWatermark {
+    store: Arc<dyn CheckpointStore>,
+    /// 已收到区块元数据的最高槽位
+    completed: Option<u64>,
+    saved: u64,
+}
+
+impl SlotWatermark {
+    pub(crate) fn new(store: Arc<dyn CheckpointStore>) -> Self {
+        Self {
+            store,
+            completed: None,
+            saved: 0,
+        }
+    }
+
+    /// 记录槽位的区块元数据已到达
+    pub(crate) fn block_completed(&mut self, slot: u64) {
+        self.completed = self.completed.max(Some(slot));
+    }
+
+    /// 保存已完成且已分发完毕的最高槽位（保存失败只记录日志，不中断订阅）
+    ///
+    /// `dispatched_through` 为已确定全部分发完毕的最高槽位，内联解析或没有在途交易时为 None，表示没有限制。
+    pub(crate) fn commit(&mut self, dispatched_through: Option<u64>) {
+        let Some(completed) = self.completed else {
+            return;
+        };
+        let slot = dispatched_through.map_or(completed, |limit| completed.min(limit));
+        if slot > self.saved {
+            match self.store.save(slot) {
+                Ok(()) => self.saved = slot,
+                Err(e) => log::warn!("保存检查点失败: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watermark() -> (Arc<MemoryCheckpointStore>, SlotWatermark) {
+        let store = Arc::new(MemoryCheckpointStore::new());
+        let watermark = SlotWatermark::new(Arc::clone(&store) as Arc<dyn CheckpointStore>);
+        (store, watermark)
+    }
+
+    #[test]
+    fn saves_only_completed_slots() {
+        let (store, mut watermark) = watermark();
+        watermark.commit(None);
+        assert_eq!(store.load().unwrap(), None);
+
+        watermark.block_completed(10);
+        watermark.commit(None);
+        assert_eq!(store.load().unwrap(), Some(10));
+
+        // 乱序到达的旧区块元数据不回退
+        watermark.block_completed(9);
+        watermark.commit(None);
+        assert_eq!(store.load().unwrap(), Some(10));
+    }
+
+    #[test]
+    fn waits_for_in_flight_transactions() {
+        let (store, mut watermark) = watermark();
+        watermark.block_completed(12);
+        watermark.commit(Some(10));
+        assert_eq!(store.load().unwrap(), Some(10));
+        watermark.commit(None);
+        assert_eq!(store.load().unwrap(), Some(12));
+    }
+}
@@ -1,5 +1,22 @@
 use std::time::Duration;
 
+use super::handler::SharedEventFilter;
+use crate::parser::events::{DecodeMode, EventSource};
+
+/// 事件分发顺序
+///
+/// 只在启用并行解析（`with_parse_workers`）时有区别；内联解析总是按到达顺序分发。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DispatchOrder {
+    /// 严格按交易到达顺序（槽位 / 交易索引）分发，先到的交易解析完成前后续交易会被暂存。
+    /// 适合索引器等需要确定顺序的场景。
+    #[default]
+    Ordered,
+    /// 交易解析完成后立即分发，不保证顺序，延迟最低。
+    /// 适合狙击等只关心速度的场景，处理器需自行处理乱序。
+    Unordered,
+}
+
 /// gRPC客户端配置
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -11,8 +28,38 @@ pub struct Config {
     pub timeout: Duration,
     /// 保持连接活跃
     pub keep_alive_while_idle: bool,
+    /// HTTP/2 keep-alive ping 间隔（None 表示使用底层默认值，不主动发送）
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// 等待 keep-alive ping 响应的超时时间（None 表示使用底层默认值）
+    pub keep_alive_timeout: Option<Duration>,
+    /// 是否启用 HTTP/2 自适应流控窗口（None 表示使用底层默认值）
+    pub http2_adaptive_window: Option<bool>,
     /// 承诺级别
     pub commitment: yellowstone_grpc_proto::geyser::CommitmentLevel,
+    /// 事件去重缓存容量（None 表示不去重）
+    pub dedup_capacity: Option<usize>,
+    /// 并行解析线程数（None 表示在接收循环中内联解析）
+    pub parse_workers: Option<usize>,
+    /// 并行解析时的事件分发顺序
+    pub dispatch_order: DispatchOrder,
+    /// 延迟告警阈值（槽位数，None 表示不告警）
+    pub lag_threshold: Option<u64>,
+    /// 过期事件阈值（槽位数，None 表示不跳过过期事件）
+    pub stale_slot_limit: Option<u64>,
+    /// 事件解码模式
+    pub decode_mode: DecodeMode,
+    /// 是否按槽位整批交付事件（`EventHandler::on_slot_complete`）
+    pub slot_batching: bool,
+    /// 是否在解析与分发路径上复用缓冲区
+    pub buffer_reuse: bool,
+    /// 订阅流统计的交付周期（None 表示不统计）
+    pub stats_interval: Option<Duration>,
+    /// 是否交付交易的完整元数据（`EventHandler::on_transaction`）
+    pub transaction_meta: bool,
+    /// 分发级别的事件过滤器（None 表示分发所有事件）
+    pub event_filter: Option<SharedEventFilter>,
+    /// 事件来源：程序日志、`emit_cpi!` 内部指令或两者
+    pub event_source: EventSource,
 }
 
 impl Config {
@@ -23,7 +70,22 @@ impl Config {
             connect_timeout: Duration::from_secs(10),
             timeout: Duration::from_secs(60),
             keep_alive_while_idle: true,
+            http2_keep_alive_interval: None,
+            keep_alive_timeout: None,
+            http2_adaptive_window: None,
             commitment: yellowstone_grpc_proto::geyser::CommitmentLevel::Processed,
+            dedup_capacity: None,
+            parse_workers: None,
+            dispatch_order: DispatchOrder::Ordered,
+            lag_threshold: None,
+            stale_slot_limit: None,
+            decode_mode: DecodeMode::Strict,
+            slot_batching: false,
+            buffer_reuse: false,
+            stats_interval: None,
+            transaction_meta: false,
+            event_filter: None,
+            event_source: EventSource::Logs,
         }
     }
 
@@ -45,6 +107,26 @@ impl Config {
         self
     }
 
+    /// 设置 HTTP/2 keep-alive ping 间隔
+    ///
+    /// 位于会回收空闲连接的 NAT / 负载均衡之后时，定期 ping 可以避免长连接被静默断开。
+    pub fn with_http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// 设置等待 keep-alive ping 响应的超时时间，超时后连接被视为断开
+    pub fn with_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// 设置是否启用 HTTP/2 自适应流控窗口
+    pub fn with_http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = Some(enabled);
+        self
+    }
+
     /// 设置承诺级别
     pub fn with_commitment(
         mut self,
@@ -53,6 +135,121 @@ impl Config {
         self.commitment = commitment;
         self
     }
+
+    /// 启用按 (签名, 事件类型) 的事件去重
+    ///
+    /// 重连回放时同一笔交易会被重复推送，启用后处理器对每个事件只会收到一次。
+    /// `capacity` 为 LRU 缓存可记录的最大事件数量。
+    pub fn with_dedup(mut self, capacity: usize) -> Self {
+        self.dedup_capacity = Some(capacity);
+        self
+    }
+
+    /// 启用并行解析流水线
+    ///
+    /// 交易日志的 base64 解码和 Borsh 解析交给 `workers` 个工作线程完成，
+    /// 默认结果按交易到达顺序重新排序后再分发，处理器看到的事件顺序与内联模式一致，
+    /// 可通过 `with_dispatch_order` 改为解析完成即分发。
+    /// 适用于大量新币发射时单线程接收循环跟不上的场景。
+    pub fn with_parse_workers(mut self, workers: usize) -> Self {
+        self.parse_workers = Some(workers);
+        self
+    }
+
+    /// 设置并行解析时的事件分发顺序，见 [`DispatchOrder`]
+    pub fn with_dispatch_order(mut self, order: DispatchOrder) -> Self {
+        self.dispatch_order = order;
+        self
+    }
+
+    /// 设置延迟告警阈值（槽位数）
+    ///
+    /// 分发事件时若落后已观察到的最高槽位超过 `slots` 个槽位，调用 `EventHandler::on_lag`。
+    /// 启用后若订阅请求未配置 `slots` 过滤器，会自动添加槽位订阅以跟踪链上最新槽位。
+    pub fn with_lag_threshold(mut self, slots: u64) -> Self {
+        self.lag_threshold = Some(slots);
+        self
+    }
+
+    /// 跳过过期事件
+    ///
+    /// 事件所在槽位落后已观察到的最高槽位超过 `slots` 个槽位时不再分发，
+    /// 常见于重连后的回放。对实时交易策略而言，基于过期成交做决策比错过它更糟。
+    /// 启用后若订阅请求未配置 `slots` 过滤器，会自动添加槽位订阅以跟踪链上最新槽位。
+    pub fn with_stale_slot_limit(mut self, slots: u64) -> Self {
+        self.stale_slot_limit = Some(slots);
+        self
+    }
+
+    /// 设置事件解码模式，见 [`DecodeMode`]
+    ///
+    /// `Tolerant` 模式下程序升级在事件末尾追加的字段会被忽略，
+    /// 未知的末尾字节通过 `EventContext::unknown_tail` 提供。
+    pub fn with_decode_mode(mut self, mode: DecodeMode) -> Self {
+        self.decode_mode = mode;
+        self
+    }
+
+    /// 启用按槽位整批交付
+    ///
+    /// 事件照常逐个分发，同时按槽位缓存；槽位的全部交易处理完后，
+    /// 通过 `EventHandler::on_slot_complete` 把该槽位的事件按交易顺序整批交付，
+    /// 适用于 K 线构建和按槽位的统计。槽位完成以区块元数据到达为准，
+    /// 启用后若订阅请求未配置 `blocks_meta` 过滤器，会自动添加区块元数据订阅。
+    pub fn with_slot_batching(mut self, enabled: bool) -> Self {
+        self.slot_batching = enabled;
+        self
+    }
+
+    /// 启用缓冲区复用，降低新币集中上线等高峰期的延迟抖动
+    ///
+    /// 并行解析模式下每笔交易的事件列表和交付批次从缓冲池中借用，分发后归还；
+    /// 更新所匹配的过滤器名称与上一条相同时共享同一份，不再逐条分配。
+    /// 日志的 base64 解码始终使用每个线程独立的缓冲区，不受此选项影响。
+    pub fn with_buffer_reuse(mut self, enabled: bool) -> Self {
+        self.buffer_reuse = enabled;
+        self
+    }
+
+    /// 启用订阅流统计
+    ///
+    /// 每个订阅按周期统计收到的消息数、字节数、解码失败的事件数和 ping 往返时间，
+    /// 通过 `EventHandler::on_stats` 交付，可按端点绘制连接质量曲线。
+    /// 周期在收到消息时检查，每次交付后主动发送一个 ping 测量下一周期的往返时间。
+    pub fn with_stats_interval(mut self, interval: Duration) -> Self {
+        self.stats_interval = Some(interval);
+        self
+    }
+
+    /// 启用交易元数据交付
+    ///
+    /// 每笔交易的事件分发完成后，通过 `EventHandler::on_transaction` 交付交易前后的
+    /// SOL / 代币余额、账户列表、手续费和执行结果，可用于计算自己交易的实际成交情况。
+    /// 未启用时不解析这些字段，不增加开销。
+    pub fn with_transaction_meta(mut self, enabled: bool) -> Self {
+        self.transaction_meta = enabled;
+        self
+    }
+
+    /// 设置分发级别的事件过滤器
+    ///
+    /// 未启用的事件类型不再调用处理器的逐事件回调（`on_new_token` 跟随对应的创建事件），
+    /// 但照常参与去重、`on_slot_complete`、`on_transaction` 和成交跟踪。
+    /// 保留一份克隆即可在运行中静音或恢复某类事件，所有订阅立即生效。
+    pub fn with_event_filter(mut self, filter: SharedEventFilter) -> Self {
+        self.event_filter = Some(filter);
+        self
+    }
+
+    /// 设置事件来源，见 [`EventSource`]
+    ///
+    /// 日志超过长度上限被截断时，`Program data:` 中的事件会丢失；`Cpi` 改为从程序通过
+    /// `emit_cpi!` 自调用的内部指令数据解码，`Both` 以日志为主、用内部指令补齐日志中缺失的事件类型。
+    /// 每种事件类型在一笔交易中仍只分发一条。自定义事件始终从日志解码。
+    pub fn with_event_source(mut self, source: EventSource) -> Self {
+        self.event_source = source;
+        self
+    }
 }
 
 impl Default for Config {
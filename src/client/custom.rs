@@ -0,0 +1,129 @@
+use borsh::BorshDeserialize;
+use std::{
+    collections::HashMap,
+    ops::ControlFlow,
+    sync::{Arc, RwLock},
+};
+
+use super::handler::EventContext;
+use crate::parser::events::{is_known_event, visit_program_logs};
+
+/// 已解码的自定义事件，分发时以事件上下文调用注册的回调
+pub(crate) type DecodedCall = Box<dyn FnOnce(&EventContext) + Send>;
+
+/// 类型擦除后的解码器，解码成功时返回绑定了事件的回调
+type Decoder = dyn Fn(&[u8]) -> Option<DecodedCall> + Send + Sync;
+
+/// 交易中解码出的一个自定义事件
+pub(crate) struct CustomEvent {
+    pub discriminator: [u8; 8],
+    pub call: DecodedCall,
+}
+
+/// 自定义事件类型注册表
+///
+/// 用于接入 SDK 未内置的事件类型，例如 Pump 分叉程序或其他程序通过 `Program data:` 日志发出的事件。
+/// 每个事件类型由 8 字节 discriminator、解码函数和回调组成，通过 `GrpcClient::with_custom_events`
+/// 挂到客户端上后，与内置事件在同一个解析和分发循环中处理：
+///
+/// - 解码在内置事件所在的线程中完成（启用并行解析时在解析线程中）
+/// - 回调在交易的内置事件之后、`on_new_token` 之前按日志顺序调用，同一交易中的多个同类事件都会分发
+/// - 与内置事件一样经过过期过滤和去重，上下文中的 `block_time` 为 None
+///
+/// 订阅请求需要包含发出事件的程序。运行中可以随时注册和移除事件类型，克隆后的实例共享同一份注册表。
+#[derive(Clone, Default)]
+pub struct CustomEvents {
+    decoders: Arc<RwLock<HashMap<[u8; 8], Arc<Decoder>>>>,
+}
+
+impl CustomEvents {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册事件类型，`decode` 收到去掉 discriminator 后的事件数据，返回 None 表示解码失败
+    ///
+    /// discriminator 与内置的 Pump / PumpAmm 事件相同时不注册并返回 `false`。
+    /// 同一 discriminator 重复注册时替换之前的解码函数和回调。
+    pub fn register<T, D, C>(&self, discriminator: [u8; 8], decode: D, callback: C) -> bool
+    where
+        T: Send + 'static,
+        D: Fn(&[u8]) -> Option<T> + Send + Sync + 'static,
+        C: Fn(&T, &EventContext) + Send + Sync + 'static,
+    {
+        if is_known_event(&discriminator) {
+            return false;
+        }
+        let callback = Arc::new(callback);
+        let decoder = move |data: &[u8]| -> Option<DecodedCall> {
+            let event = decode(data)?;
+            let callback = Arc::clone(&callback);
+            Some(Box::new(move |ctx: &EventContext| callback(&event, ctx)))
+        };
+        self.write().insert(discriminator, Arc::new(decoder));
+        true
+    }
+
+    /// 注册 Borsh 编码的事件类型（例如 Anchor 程序的 `emit!` 事件），事件数据必须被完整消费
+    pub fn register_borsh<T, C>(&self, discriminator: [u8; 8], callback: C) -> bool
+    where
+        T: BorshDeserialize + Send + 'static,
+        C: Fn(&T, &EventContext) + Send + Sync + 'static,
+    {
+        self.register(discriminator, |data| T::try_from_slice(data).ok(), callback)
+    }
+
+    /// 移除事件类型，存在时返回 `true`
+    pub fn unregister(&self, discriminator: &[u8; 8]) -> bool {
+        self.write().remove(discriminator).is_some()
+    }
+
+    /// 是否注册了指定 discriminator
+    pub fn contains(&self, discriminator: &[u8; 8]) -> bool {
+        self.read().contains_key(discriminator)
+    }
+
+    /// 已注册的事件类型数量
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// 是否没有注册任何事件类型
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// 按日志顺序解码交易中所有已注册类型的事件，解码失败的事件被跳过
+    pub(crate) fn decode(&self, logs: &[String]) -> Vec<CustomEvent> {
+        let decoders = self.read();
+        if decoders.is_empty() {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        visit_program_logs(logs, |discriminator, data| {
+            let Some((key, decoder)) = decoders.get_key_value(discriminator) else {
+                return ControlFlow::Continue(());
+            };
+            match decoder(data) {
+                Some(call) => events.push(CustomEvent {
+                    discriminator: *key,
+                    call,
+                }),
+                None => log::warn!("自定义事件 {:?} 解码失败", key),
+            }
+            ControlFlow::Continue(())
+        });
+        // 日志按倒序遍历
+        events.reverse();
+        events
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<[u8; 8], Arc<Decoder>>> {
+        self.decoders.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<[u8; 8], Arc<Decoder>>> {
+        self.decoders.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
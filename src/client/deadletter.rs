@@ -0,0 +1,330 @@
+use base64::{engine::general_purpose, Engine};
+use solana_sdk::signature::Signature;
+use std::{
+    any::Any,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+
+use super::handler::EventContext;
+use crate::{
+    error::{Error, Result},
+    models::PumpEvent,
+    parser::events::{decode_event_with, encode_program_data, try_decode_event, DecodeMode},
+};
+
+/// 事件进入死信队列的原因
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    /// discriminator 匹配但解码失败，包含错误信息
+    Decode(String),
+    /// 处理器回调 panic，包含 panic 信息
+    HandlerPanic(String),
+    /// 解析线程中的用户代码（自定义解码器或死信队列）panic，包含 panic 信息；该交易的事件被丢弃
+    ParsePanic(String),
+}
+
+impl DeadLetterReason {
+    fn kind(&self) -> &'static str {
+        match self {
+            DeadLetterReason::Decode(_) => "decode",
+            DeadLetterReason::HandlerPanic(_) => "panic",
+            DeadLetterReason::ParsePanic(_) => "parse_panic",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            DeadLetterReason::Decode(message)
+            | DeadLetterReason::HandlerPanic(message)
+            | DeadLetterReason::ParsePanic(message) => message,
+        }
+    }
+}
+
+/// 未能正常处理的事件，保留原始数据和上下文以便之后重放
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    pub reason: DeadLetterReason,
+    pub slot: u64,
+    pub tx_index: u64,
+    pub signature: Signature,
+    /// 收到交易时的系统时间
+    pub received_at: SystemTime,
+    /// 事件的原始数据（包含 8 字节 discriminator），即 `Program data:` 日志 base64 解码后的内容
+    ///
+    /// 处理器 panic 时由解码后的事件重新编码，宽容解码模式下的未知末尾字节附加在最后；
+    /// 解析线程 panic 时为空。
+    pub data: Vec<u8>,
+}
+
+impl DeadLetter {
+    pub(crate) fn decode_failure(
+        discriminator: &[u8],
+        data: &[u8],
+        error: &Error,
+        ctx: &EventContext,
+    ) -> Self {
+        let data = [discriminator, data].concat();
+        Self::new(DeadLetterReason::Decode(error.to_string()), data, ctx)
+    }
+
+    pub(crate) fn handler_panic(
+        event: &PumpEvent,
+        panic: &(dyn Any + Send),
+        ctx: &EventContext,
+    ) -> Self {
+        let mut data = encode_program_data(event);
+        if let Some(tail) = &ctx.unknown_tail {
+            data.extend_from_slice(tail);
+        }
+        Self::new(DeadLetterReason::HandlerPanic(panic_message(panic)), data, ctx)
+    }
+
+    pub(crate) fn parse_panic(panic: &(dyn Any + Send), ctx: &EventContext) -> Self {
+        Self::new(DeadLetterReason::ParsePanic(panic_message(panic)), Vec::new(), ctx)
+    }
+
+    fn new(reason: DeadLetterReason, data: Vec<u8>, ctx: &EventContext) -> Self {
+        Self {
+            reason,
+            slot: ctx.slot,
+            tx_index: ctx.tx_index,
+            signature: ctx.signature,
+            received_at: ctx.received_at,
+            data,
+        }
+    }
+
+    /// 事件的 discriminator，数据不足 8 字节时为 None
+    pub fn discriminator(&self) -> Option<&[u8]> {
+        self.data.get(..8)
+    }
+
+    /// 重新解码事件，例如在模型更新后重放解码失败的事件
+    ///
+    /// 先按严格模式解码；数据末尾有模型之外的字节（宽容解码模式下处理器 panic 的事件）时忽略这些字节。
+    pub fn decode(&self) -> Result<PumpEvent> {
+        if self.data.len() < 8 {
+            return Err(Error::ParseError("死信数据不足 8 字节".to_string()));
+        }
+        let (discriminator, data) = self.data.split_at(8);
+        try_decode_event(discriminator, data).or_else(|e| {
+            decode_event_with(discriminator, data, DecodeMode::Tolerant)
+                .map(|(event, _)| event)
+                .ok_or(e)
+        })
+    }
+
+    /// 编码为一行文本：接收时间（毫秒）、槽位、交易索引、签名、原因、base64 数据和原因说明，以制表符分隔
+    fn to_line(&self) -> String {
+        let received_ms = self
+            .received_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let message: String = self
+            .reason
+            .message()
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            received_ms,
+            self.slot,
+            self.tx_index,
+            self.signature,
+            self.reason.kind(),
+            general_purpose::STANDARD.encode(&self.data),
+            message
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(7, '\t');
+        let received_ms: u64 = fields.next()?.parse().ok()?;
+        let slot = fields.next()?.parse().ok()?;
+        let tx_index = fields.next()?.parse().ok()?;
+        let signature = Signature::from_str(fields.next()?).ok()?;
+        let kind = fields.next()?;
+        let data = general_purpose::STANDARD.decode(fields.next()?).ok()?;
+        let message = fields.next().unwrap_or_default().to_string();
+        let reason = match kind {
+            "decode" => DeadLetterReason::Decode(message),
+            "panic" => DeadLetterReason::HandlerPanic(message),
+            "parse_panic" => DeadLetterReason::ParsePanic(message),
+            _ => return None,
+        };
+        Some(Self {
+            reason,
+            slot,
+            tx_index,
+            signature,
+            received_at: UNIX_EPOCH + Duration::from_millis(received_ms),
+            data,
+        })
+    }
+}
+
+/// 死信队列
+///
+/// 配置到 `GrpcClient` 后，解码失败的事件和处理器回调 panic 的事件连同原始数据与上下文写入该队列，
+/// 而不是只留下一行日志。写入在解析或分发线程中同步进行，实现应尽快返回。
+pub trait DeadLetterSink: Send + Sync {
+    /// 写入一条死信
+    fn send(&self, letter: DeadLetter) -> Result<()>;
+}
+
+/// 文件死信队列
+///
+/// 每条死信追加为一行文本，可以用 [`read`](Self::read) 读回后重放。
+pub struct FileDeadLetterSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileDeadLetterSink {
+    /// 以追加模式打开（或创建）文件
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| Error::Unknown(format!("打开死信文件失败: {e}")))?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// 文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 读取文件中的所有死信，格式错误的行被跳过
+    pub fn read(path: impl AsRef<Path>) -> Result<Vec<DeadLetter>> {
+        let file =
+            File::open(path).map_err(|e| Error::Unknown(format!("读取死信文件失败: {e}")))?;
+        let mut letters = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| Error::Unknown(format!("读取死信文件失败: {e}")))?;
+            match DeadLetter::from_line(&line) {
+                Some(letter) => letters.push(letter),
+                None if line.trim().is_empty() => {}
+                None => log::warn!("跳过格式错误的死信: {}", line),
+            }
+        }
+        Ok(letters)
+    }
+}
+
+impl DeadLetterSink for FileDeadLetterSink {
+    fn send(&self, letter: DeadLetter) -> Result<()> {
+        let line = letter.to_line();
+        self.file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .write_all(line.as_bytes())
+            .map_err(|e| Error::Unknown(format!("写入死信失败: {e}")))
+    }
+}
+
+/// 通道死信队列，把死信交给有界通道的接收端（例如由异步任务转存到数据库）
+///
+/// 通道已满时丢弃死信，丢弃数可通过 [`dropped`](Self::dropped) 查询，不会阻塞解析或分发。
+pub struct ChannelDeadLetterSink {
+    sender: mpsc::Sender<DeadLetter>,
+    dropped: AtomicU64,
+}
+
+impl ChannelDeadLetterSink {
+    /// 创建容量为 `capacity`（至少为 1）的通道，返回队列和接收端
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<DeadLetter>) {
+        let (sender, rx) = mpsc::channel(capacity.max(1));
+        let sink = Self {
+            sender,
+            dropped: AtomicU64::new(0),
+        };
+        (sink, rx)
+    }
+
+    /// 因通道已满或接收端已关闭而丢弃的死信数
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl DeadLetterSink for ChannelDeadLetterSink {
+    fn send(&self, letter: DeadLetter) -> Result<()> {
+        if self.sender.try_send(letter).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+/// panic 携带的信息，不是字符串时为空
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+/// 写入死信，失败时只记录日志
+pub(crate) fn deliver(sink: &dyn DeadLetterSink, letter: DeadLetter) {
+    if let Err(e) = sink.send(letter) {
+        log::warn!("写入死信队列失败: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TradeEvent;
+    use solana_sdk::pubkey::Pubkey;
+    use std::{sync::Arc, time::Instant};
+
+    fn ctx(unknown_tail: Option<Arc<[u8]>>) -> EventContext {
+        EventContext {
+            slot: 1,
+            tx_index: 0,
+            signature: Signature::new_unique(),
+            timestamp: Instant::now(),
+            elapsed: Duration::ZERO,
+            received_at: SystemTime::now(),
+            block_time: None,
+            filters: Arc::from(Vec::new()),
+            unknown_tail,
+        }
+    }
+
+    #[test]
+    fn handler_panic_with_unknown_tail_decodes() {
+        let event = PumpEvent::Trade(TradeEvent {
+            mint: Pubkey::new_unique(),
+            sol_amount: 1_000,
+            ix_name: "sell".to_string(),
+            ..Default::default()
+        });
+        let letter = DeadLetter::handler_panic(&event, &"boom", &ctx(Some(Arc::from(&[1u8, 2, 3][..]))));
+        assert_eq!(letter.reason, DeadLetterReason::HandlerPanic("boom".to_string()));
+        assert!(letter.data.ends_with(&[1, 2, 3]));
+        assert_eq!(letter.decode().unwrap(), event);
+
+        let letter = DeadLetter::parse_panic(&"boom", &ctx(None));
+        assert!(letter.decode().is_err());
+    }
+}
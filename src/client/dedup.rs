@@ -0,0 +1,112 @@
+use lru::LruCache;
+use solana_sdk::signature::Signature;
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+/// 去重键：(交易签名, 事件 discriminator)
+type DedupKey = (Signature, [u8; 8]);
+
+/// 事件去重缓存
+///
+/// 以 `(签名, 事件 discriminator)` 为键的有界 LRU 缓存。
+/// 断线重连并从历史槽位回放时，同一笔交易会被重复推送，
+/// 该缓存保证处理器对每个事件只会收到一次。
+///
+/// 缓存内部使用 `Arc` 共享，克隆后的实例（包括克隆的 `GrpcClient`）共用同一份记录。
+#[derive(Clone)]
+pub struct DedupCache {
+    inner: Arc<Mutex<LruCache<DedupKey, ()>>>,
+}
+
+impl DedupCache {
+    /// 创建指定容量的去重缓存，容量为 0 时按 1 处理
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            inner: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// 记录一个事件，首次出现返回 `true`，重复出现返回 `false`
+    pub fn insert(&self, signature: &Signature, discriminator: &[u8]) -> bool {
+        let mut key = [0u8; 8];
+        key.copy_from_slice(&discriminator[..8]);
+        let mut cache = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        cache.put((*signature, key), ()).is_none()
+    }
+
+    /// 当前缓存的事件数量
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// 缓存是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存
+    pub fn clear(&self) {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRADE: [u8; 8] = [1; 8];
+    const CREATE: [u8; 8] = [2; 8];
+
+    #[test]
+    fn suppresses_duplicates_per_signature_and_discriminator() {
+        let cache = DedupCache::new(16);
+        let signature = Signature::new_unique();
+        assert!(cache.insert(&signature, &TRADE));
+        assert!(!cache.insert(&signature, &TRADE));
+        assert!(cache.insert(&signature, &CREATE));
+        assert!(cache.insert(&Signature::new_unique(), &TRADE));
+        assert_eq!(cache.len(), 3);
+
+        // 只使用前 8 字节作为 discriminator
+        assert!(!cache.insert(&signature, &[TRADE.as_slice(), &[9, 9]].concat()));
+    }
+
+    #[test]
+    fn evicts_least_recently_seen_at_capacity() {
+        let cache = DedupCache::new(2);
+        let (a, b, c) = (Signature::new_unique(), Signature::new_unique(), Signature::new_unique());
+        assert!(cache.insert(&a, &TRADE));
+        assert!(cache.insert(&b, &TRADE));
+        // 重复插入刷新 a，b 成为最久未见的记录
+        assert!(!cache.insert(&a, &TRADE));
+        assert!(cache.insert(&c, &TRADE));
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.insert(&a, &TRADE));
+        assert!(cache.insert(&b, &TRADE));
+    }
+
+    #[test]
+    fn zero_capacity_keeps_one_entry() {
+        let cache = DedupCache::new(0);
+        let (a, b) = (Signature::new_unique(), Signature::new_unique());
+        assert!(cache.insert(&a, &TRADE));
+        assert!(!cache.insert(&a, &TRADE));
+        assert!(cache.insert(&b, &TRADE));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.insert(&a, &TRADE));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn clones_share_records() {
+        let cache = DedupCache::new(4);
+        let signature = Signature::new_unique();
+        assert!(cache.clone().insert(&signature, &TRADE));
+        assert!(!cache.insert(&signature, &TRADE));
+    }
+}
@@ -0,0 +1,137 @@
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::watch;
+
+use super::cache::AccountCache;
+use crate::{
+    models::{GlobalAccount, GlobalConfigAccount, ProgramAccount},
+    pda,
+};
+
+/// Pump bonding curve 交易的手续费参数，取自 Global 账户
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PumpFees {
+    /// 手续费接收账户
+    pub fee_recipient: Pubkey,
+    /// 备用手续费接收账户
+    pub fee_recipients: [Pubkey; 7],
+    /// 协议手续费（基点）
+    pub fee_basis_points: u64,
+    /// 创建者手续费（基点）
+    pub creator_fee_basis_points: u64,
+}
+
+impl From<&GlobalAccount> for PumpFees {
+    fn from(global: &GlobalAccount) -> Self {
+        Self {
+            fee_recipient: global.fee_recipient,
+            fee_recipients: global.fee_recipients,
+            fee_basis_points: global.fee_basis_points,
+            creator_fee_basis_points: global.creator_fee_basis_points,
+        }
+    }
+}
+
+/// PumpAmm 交易的手续费参数，取自 GlobalConfig 账户
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AmmFees {
+    /// 协议手续费接收账户
+    pub protocol_fee_recipients: [Pubkey; 8],
+    /// LP 手续费（基点）
+    pub lp_fee_basis_points: u64,
+    /// 协议手续费（基点）
+    pub protocol_fee_basis_points: u64,
+    /// 代币创建者手续费（基点）
+    pub coin_creator_fee_basis_points: u64,
+}
+
+impl From<&GlobalConfigAccount> for AmmFees {
+    fn from(config: &GlobalConfigAccount) -> Self {
+        Self {
+            protocol_fee_recipients: config.protocol_fee_recipients,
+            lp_fee_basis_points: config.lp_fee_basis_points,
+            protocol_fee_basis_points: config.protocol_fee_basis_points,
+            coin_creator_fee_basis_points: config.coin_creator_fee_basis_points,
+        }
+    }
+}
+
+/// 当前的手续费参数，对应账户尚未获取到时为 None
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeParams {
+    pub pump: Option<PumpFees>,
+    pub amm: Option<AmmFees>,
+}
+
+/// Global / GlobalConfig 手续费参数热更新
+///
+/// 通过 [`AccountCache`] 订阅 Pump Global 和 PumpAmm GlobalConfig 账户，
+/// 账户中的手续费接收账户或费率变化时整体替换 [`FeeParams`] 快照并调用回调，
+/// 避免程序管理员调整参数后继续使用过期的手续费配置。账户中其他字段的变化不会触发回调。
+///
+/// 缓存需要挂到 `GrpcClient::with_account_cache` 上才会收到链上更新；
+/// 若缓存设置了 RPC 回退，可先调用 `resolve_global` / `resolve_global_config` 获取初始值。
+#[derive(Clone)]
+pub struct FeeWatcher {
+    params: watch::Receiver<FeeParams>,
+}
+
+impl FeeWatcher {
+    /// 启动后台监听任务，必须在 Tokio 运行时中调用
+    ///
+    /// `callback` 收到变化前后的参数，首次获取到账户时也会调用。
+    pub fn spawn<F>(cache: &AccountCache, callback: F) -> Self
+    where
+        F: Fn(&FeeParams, &FeeParams) + Send + Sync + 'static,
+    {
+        let mut global_rx = cache.subscribe(pda::global());
+        let mut config_rx = cache.subscribe(pda::global_config());
+        let initial = params_of(
+            &global_rx.borrow_and_update(),
+            &config_rx.borrow_and_update(),
+        );
+        let (tx, rx) = watch::channel(initial);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = global_rx.changed() => if changed.is_err() { break },
+                    changed = config_rx.changed() => if changed.is_err() { break },
+                    _ = tx.closed() => break,
+                }
+                let params = params_of(
+                    &global_rx.borrow_and_update(),
+                    &config_rx.borrow_and_update(),
+                );
+                let previous = *tx.borrow();
+                if params != previous {
+                    tx.send_replace(params);
+                    callback(&previous, &params);
+                }
+            }
+        });
+        Self { params: rx }
+    }
+
+    /// 当前的手续费参数
+    pub fn current(&self) -> FeeParams {
+        *self.params.borrow()
+    }
+
+    /// 订阅手续费参数的变化
+    pub fn subscribe(&self) -> watch::Receiver<FeeParams> {
+        self.params.clone()
+    }
+}
+
+fn params_of(global: &Option<ProgramAccount>, config: &Option<ProgramAccount>) -> FeeParams {
+    FeeParams {
+        pump: match global {
+            Some(ProgramAccount::Global(global)) => Some(global.into()),
+            _ => None,
+        },
+        amm: match config {
+            Some(ProgramAccount::GlobalConfig(config)) => Some(config.into()),
+            _ => None,
+        },
+    }
+}
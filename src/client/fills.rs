@@ -0,0 +1,342 @@
+use solana_sdk::{signature::Signature, transaction::TransactionError};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{oneshot, watch};
+use yellowstone_grpc_proto::geyser::{SubscribeRequest, SubscribeRequestFilterTransactions};
+
+use super::updates::TransactionUpdate;
+use crate::models::PumpEvent;
+
+/// 成交跟踪在订阅请求中使用的交易过滤器名称前缀，每个签名一个过滤器
+pub const FILL_FILTER_PREFIX: &str = "fill:";
+
+/// 自己发送的交易的成交结果
+#[derive(Clone, Debug)]
+pub struct FillReport {
+    /// 交易的完整元数据（前后余额、手续费、执行错误等）
+    pub transaction: TransactionUpdate,
+    /// 交易中分发的事件
+    pub events: Vec<PumpEvent>,
+    /// 成交方向，交易中没有成交事件时为 None
+    pub is_buy: Option<bool>,
+    /// 成交的代币数量（原始数量）
+    pub token_amount: u64,
+    /// 成交的 SOL 数量（lamports，不含交易手续费）
+    pub sol_amount: u64,
+    /// 支付的交易手续费（协议、创建者和 LP 手续费，lamports）
+    pub trading_fees: u64,
+}
+
+impl FillReport {
+    /// 由交易元数据和事件汇总，交易中有多笔成交时只累加与第一笔方向相同的成交
+    pub fn new(transaction: TransactionUpdate, events: Vec<PumpEvent>) -> Self {
+        let mut report = Self {
+            transaction,
+            events: Vec::new(),
+            is_buy: None,
+            token_amount: 0,
+            sol_amount: 0,
+            trading_fees: 0,
+        };
+        for event in &events {
+            let (is_buy, tokens, sol, fees) = match event {
+                PumpEvent::Trade(e) => (
+                    e.is_buy,
+                    e.token_amount,
+                    e.sol_amount,
+                    e.fee + e.creator_fee,
+                ),
+                PumpEvent::Buy(e) => (
+                    true,
+                    e.base_amount_out,
+                    e.quote_amount_in,
+                    e.lp_fee + e.protocol_fee + e.coin_creator_fee,
+                ),
+                PumpEvent::Sell(e) => (
+                    false,
+                    e.base_amount_in,
+                    e.quote_amount_out,
+                    e.lp_fee + e.protocol_fee + e.coin_creator_fee,
+                ),
+                _ => continue,
+            };
+            if *report.is_buy.get_or_insert(is_buy) != is_buy {
+                continue;
+            }
+            report.token_amount += tokens;
+            report.sol_amount += sol;
+            report.trading_fees += fees;
+        }
+        report.events = events;
+        report
+    }
+
+    /// 交易是否执行成功
+    pub fn is_success(&self) -> bool {
+        self.transaction.is_success()
+    }
+
+    /// 执行失败时的错误，可交给 `FailureDiagnosis::from_transaction` 诊断
+    pub fn err(&self) -> Option<&TransactionError> {
+        self.transaction.err.as_ref()
+    }
+
+    /// 网络手续费（基础费和优先费，lamports）
+    pub fn network_fee(&self) -> u64 {
+        self.transaction.fee
+    }
+
+    /// 含交易手续费的实际成交价格（lamports / 代币原始单位），没有成交时返回 None
+    ///
+    /// 买入为实际支付的 SOL 除以获得的代币，卖出为实际到手的 SOL 除以卖出的代币。
+    pub fn effective_price(&self) -> Option<f64> {
+        if self.token_amount == 0 {
+            return None;
+        }
+        let sol = match self.is_buy? {
+            true => self.sol_amount + self.trading_fees,
+            false => self.sol_amount.saturating_sub(self.trading_fees),
+        };
+        Some(sol as f64 / self.token_amount as f64)
+    }
+}
+
+/// 跟踪中交易的结果
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum FillOutcome {
+    /// 交易已上链（执行成功或失败）
+    Landed(FillReport),
+    /// 区块高度已超过交易 blockhash 的 `last_valid_block_height`，交易不会再上链，可以放心重新签名发送
+    Expired {
+        last_valid_block_height: u64,
+        /// 判定过期时观察到的区块高度
+        block_height: u64,
+    },
+}
+
+struct Pending {
+    sender: oneshot::Sender<FillOutcome>,
+    last_valid_block_height: Option<u64>,
+}
+
+#[derive(Default)]
+struct State {
+    pending: HashMap<Signature, Pending>,
+    /// 最近观察到的区块高度
+    block_height: Option<u64>,
+}
+
+/// 自己发送的交易的成交跟踪
+///
+/// 通过 `GrpcClient::with_fill_tracker` 挂到客户端上后，交易发送前用 [`track`](Self::track)
+/// 登记签名，客户端会为其添加签名过滤器（包括执行失败的交易，运行中的订阅也会即时更新）；
+/// 交易出现在流中时解析其事件并通过返回的接收端交付 [`FillReport`]，
+/// 比轮询 RPC 确认交易快得多。交付后签名自动移除。
+///
+/// 用 [`track_until`](Self::track_until) 登记时同时跟踪 blockhash 的有效期：客户端从区块元数据中
+/// 获取区块高度，高度超过 `last_valid_block_height` 而交易仍未出现时交付 [`FillOutcome::Expired`]，
+/// 与链上判定过期的时间一致。
+///
+/// 接收端被丢弃的签名会在下次登记时清理。克隆后的实例共享同一份跟踪列表。
+#[derive(Clone)]
+pub struct FillTracker {
+    signatures: Arc<watch::Sender<HashSet<Signature>>>,
+    state: Arc<Mutex<State>>,
+}
+
+impl FillTracker {
+    /// 创建空的成交跟踪
+    pub fn new() -> Self {
+        Self {
+            signatures: Arc::new(watch::Sender::new(HashSet::new())),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// 登记交易签名，返回交付结果的接收端
+    ///
+    /// 应在交易签名后、发送前登记，避免交易在登记前就已上链。
+    /// 不跟踪有效期，交易过期未上链时接收端不会收到结果，调用方应配合超时使用。
+    /// 重复登记同一签名时，之前的接收端会被关闭。
+    pub fn track(&self, signature: Signature) -> oneshot::Receiver<FillOutcome> {
+        self.insert(signature, None)
+    }
+
+    /// 登记交易签名并跟踪有效期，`last_valid_block_height` 为获取 blockhash 时 RPC 一并返回的值
+    ///
+    /// 登记时已观察到的区块高度超过该值时立即交付过期结果。
+    pub fn track_until(
+        &self,
+        signature: Signature,
+        last_valid_block_height: u64,
+    ) -> oneshot::Receiver<FillOutcome> {
+        self.insert(signature, Some(last_valid_block_height))
+    }
+
+    /// 停止跟踪签名，存在时返回 `true`
+    pub fn cancel(&self, signature: &Signature) -> bool {
+        self.lock().pending.remove(signature);
+        self.signatures
+            .send_if_modified(|signatures| signatures.remove(signature))
+    }
+
+    /// 是否正在跟踪指定签名
+    pub fn is_tracked(&self, signature: &Signature) -> bool {
+        self.lock().pending.contains_key(signature)
+    }
+
+    /// 正在跟踪的签名数量
+    pub fn len(&self) -> usize {
+        self.lock().pending.len()
+    }
+
+    /// 是否没有正在跟踪的签名
+    pub fn is_empty(&self) -> bool {
+        self.lock().pending.is_empty()
+    }
+
+    /// 最近观察到的区块高度，尚未收到区块元数据时为 None
+    pub fn block_height(&self) -> Option<u64> {
+        self.lock().block_height
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<HashSet<Signature>> {
+        self.signatures.subscribe()
+    }
+
+    /// 交易出现在流中时交付成交结果，签名未被跟踪时忽略
+    pub(crate) fn complete(&self, transaction: &TransactionUpdate, events: &[PumpEvent]) {
+        let Some(pending) = self.lock().pending.remove(&transaction.signature) else {
+            return;
+        };
+        self.signatures
+            .send_if_modified(|signatures| signatures.remove(&transaction.signature));
+        let report = FillReport::new(transaction.clone(), events.to_vec());
+        let _ = pending.sender.send(FillOutcome::Landed(report));
+    }
+
+    /// 记录区块高度，交付所有已过期交易的结果
+    pub(crate) fn observe_block_height(&self, block_height: u64) {
+        let expired: Vec<(Signature, Pending)> = {
+            let mut state = self.lock();
+            if state
+                .block_height
+                .is_some_and(|height| height >= block_height)
+            {
+                return;
+            }
+            state.block_height = Some(block_height);
+            let signatures: Vec<Signature> = state
+                .pending
+                .iter()
+                .filter(|(_, pending)| {
+                    pending
+                        .last_valid_block_height
+                        .is_some_and(|last_valid| block_height > last_valid)
+                })
+                .map(|(signature, _)| *signature)
+                .collect();
+            signatures
+                .into_iter()
+                .filter_map(|signature| Some((signature, state.pending.remove(&signature)?)))
+                .collect()
+        };
+        if expired.is_empty() {
+            return;
+        }
+        self.signatures.send_if_modified(|signatures| {
+            expired
+                .iter()
+                .filter(|(signature, _)| signatures.remove(signature))
+                .count()
+                > 0
+        });
+        for (_, pending) in expired {
+            let _ = pending.sender.send(FillOutcome::Expired {
+                last_valid_block_height: pending.last_valid_block_height.unwrap_or_default(),
+                block_height,
+            });
+        }
+    }
+
+    fn insert(
+        &self,
+        signature: Signature,
+        last_valid_block_height: Option<u64>,
+    ) -> oneshot::Receiver<FillOutcome> {
+        let (sender, rx) = oneshot::channel();
+        let closed: Vec<Signature> = {
+            let mut state = self.lock();
+            if let (Some(last_valid), Some(height)) = (last_valid_block_height, state.block_height)
+            {
+                if height > last_valid {
+                    let _ = sender.send(FillOutcome::Expired {
+                        last_valid_block_height: last_valid,
+                        block_height: height,
+                    });
+                    return rx;
+                }
+            }
+            state
+                .pending
+                .retain(|_, pending| !pending.sender.is_closed());
+            let closed = self
+                .signatures
+                .borrow()
+                .iter()
+                .filter(|signature| !state.pending.contains_key(signature))
+                .copied()
+                .collect();
+            state.pending.insert(
+                signature,
+                Pending {
+                    sender,
+                    last_valid_block_height,
+                },
+            );
+            closed
+        };
+        self.signatures.send_if_modified(|signatures| {
+            let removed = closed
+                .iter()
+                .filter(|closed| signatures.remove(closed))
+                .count();
+            signatures.insert(signature) || removed > 0
+        });
+        rx
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for FillTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 为跟踪中的每个签名写入一个交易过滤器（包括执行失败的交易），替换之前的成交跟踪过滤器
+pub(crate) fn apply_fill_tracker(signatures: &HashSet<Signature>, request: &mut SubscribeRequest) {
+    request
+        .transactions
+        .retain(|name, _| !name.starts_with(FILL_FILTER_PREFIX));
+    for signature in signatures {
+        let signature = signature.to_string();
+        request.transactions.insert(
+            format!("{FILL_FILTER_PREFIX}{signature}"),
+            SubscribeRequestFilterTransactions {
+                vote: None,
+                failed: None,
+                signature: Some(signature),
+                account_include: vec![],
+                account_exclude: vec![],
+                account_required: vec![],
+            },
+        );
+    }
+}
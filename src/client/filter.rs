@@ -0,0 +1,389 @@
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use yellowstone_grpc_proto::geyser::{
+    SubscribeRequest, SubscribeRequestFilterTransactions, SubscribeUpdate,
+};
+
+use super::{
+    handler::{AccountContext, EventContext, EventHandler},
+    pools::PoolReserves,
+    profiler::LatencyReport,
+    stats::StreamStats,
+    status::StreamLag,
+    updates::{AccountUpdate, BlockMetaUpdate, EntryUpdate, SlotUpdate, TransactionUpdate},
+};
+use crate::{models::*, pda};
+
+/// 代币白名单在订阅请求中使用的交易过滤器名称
+pub const SUBSCRIPTION_FILTER: &str = "mints";
+
+/// 与 mint 相关的账户：bonding curve、关联 bonding curve 账户（同时覆盖 Token 与 Token-2022）
+/// 以及曲线完成后迁移生成的标准 PumpAmm 池
+pub(crate) fn mint_accounts(mint: &Pubkey) -> [Pubkey; 4] {
+    [
+        pda::bonding_curve(mint),
+        pda::associated_bonding_curve(mint, &pda::TOKEN_PROGRAM_ID),
+        pda::associated_bonding_curve(mint, &pda::TOKEN_2022_PROGRAM_ID),
+        pda::canonical_pump_pool(mint),
+    ]
+}
+
+/// 代币白名单
+///
+/// 通过 `GrpcClient::subscribe_filtered` 订阅时分两级过滤：
+///
+/// - 订阅级别：每个 mint 的 bonding curve、关联 bonding curve 账户和标准 PumpAmm 池写入交易过滤器的
+///   `account_include`，只有触及这些账户的交易会被推送（`account_required` 要求同时触及所有账户，
+///   不适用于多个 mint）
+/// - 分发级别：同一交易中其他代币的事件在调用处理器前被丢弃；PumpAmm 的 Buy / Sell 事件只记录池地址，
+///   按白名单代币的标准池匹配
+///
+/// `on_slot_complete` 只交付白名单代币的事件；`on_transaction` 只在交易包含白名单代币的事件时调用，
+/// 并且只传入这些事件。其他非事件回调（账户、槽位、统计等）不经过滤直接转发。
+#[derive(Clone, Debug, Default)]
+pub struct SubscriptionFilter {
+    mints: HashSet<Pubkey>,
+    /// 白名单代币的标准池
+    pools: HashSet<Pubkey>,
+}
+
+impl SubscriptionFilter {
+    /// 创建空的白名单
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加入 mint
+    pub fn with_mint(mut self, mint: Pubkey) -> Self {
+        self.insert(mint);
+        self
+    }
+
+    /// 加入多个 mint
+    pub fn with_mints(mut self, mints: impl IntoIterator<Item = Pubkey>) -> Self {
+        for mint in mints {
+            self.insert(mint);
+        }
+        self
+    }
+
+    /// 白名单中的所有 mint
+    pub fn mints(&self) -> impl Iterator<Item = &Pubkey> {
+        self.mints.iter()
+    }
+
+    /// 是否包含指定 mint
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.mints.contains(mint)
+    }
+
+    /// 事件是否属于白名单中的代币
+    pub fn matches(&self, event: &PumpEvent) -> bool {
+        if let PumpEvent::CreatePool(e) = event {
+            return self.contains(&e.base_mint) || self.contains(&e.quote_mint);
+        }
+        match (event.mint(), event.pool()) {
+            (Some(mint), _) => self.mints.contains(&mint),
+            (None, Some(pool)) => self.pools.contains(&pool),
+            (None, None) => false,
+        }
+    }
+
+    /// 订阅白名单代币相关交易的请求
+    ///
+    /// 白名单为空时不包含交易过滤器：空的 `account_include` 表示不限制账户，会推送全链交易。
+    pub fn request(&self) -> SubscribeRequest {
+        if self.mints.is_empty() {
+            return SubscribeRequest::default();
+        }
+        let account_include = self
+            .mints
+            .iter()
+            .flat_map(mint_accounts)
+            .map(|account| account.to_string())
+            .collect();
+        SubscribeRequest {
+            transactions: HashMap::from([(
+                SUBSCRIPTION_FILTER.to_string(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    signature: None,
+                    account_include,
+                    account_exclude: vec![],
+                    account_required: vec![],
+                },
+            )]),
+            ..Default::default()
+        }
+    }
+
+    fn insert(&mut self, mint: Pubkey) {
+        if self.mints.insert(mint) {
+            self.pools.insert(pda::canonical_pump_pool(&mint));
+        }
+    }
+}
+
+/// 只转发白名单代币事件的处理器包装，供 `subscribe_filtered` 使用
+pub(crate) struct MintFilterHandler<H> {
+    pub filter: SubscriptionFilter,
+    pub inner: H,
+}
+
+impl<H: EventHandler> EventHandler for MintFilterHandler<H> {
+    fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_create_event(event, ctx);
+        }
+    }
+
+    fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_create_v2_event(event, ctx);
+        }
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_complete_event(event, ctx);
+        }
+    }
+
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_trade_event(event, ctx);
+        }
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        if self.filter.pools.contains(&event.pool) {
+            self.inner.on_buy_event(event, ctx);
+        }
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        if self.filter.pools.contains(&event.pool) {
+            self.inner.on_sell_event(event, ctx);
+        }
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.base_mint) || self.filter.contains(&event.quote_mint) {
+            self.inner.on_create_pool_event(event, ctx);
+        }
+    }
+
+    fn on_deposit_event(&self, event: &DepositEvent, ctx: &EventContext) {
+        if self.filter.pools.contains(&event.pool) {
+            self.inner.on_deposit_event(event, ctx);
+        }
+    }
+
+    fn on_withdraw_event(&self, event: &WithdrawEvent, ctx: &EventContext) {
+        if self.filter.pools.contains(&event.pool) {
+            self.inner.on_withdraw_event(event, ctx);
+        }
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_migrate_event(event, ctx);
+        }
+    }
+
+    fn on_set_creator_event(&self, event: &SetCreatorEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_set_creator_event(event, ctx);
+        }
+    }
+
+    fn on_set_metaplex_creator_event(&self, event: &SetMetaplexCreatorEvent, ctx: &EventContext) {
+        if self.filter.contains(&event.mint) {
+            self.inner.on_set_metaplex_creator_event(event, ctx);
+        }
+    }
+
+    fn on_event(&self, event: &PumpEvent, ctx: &EventContext) {
+        if self.filter.matches(event) {
+            self.inner.on_event(event, ctx);
+        }
+    }
+
+    fn dispatch(&self, event: &PumpEvent, ctx: &EventContext) {
+        if self.filter.matches(event) {
+            self.inner.dispatch(event, ctx);
+        }
+    }
+
+    fn on_new_token(&self, token: &NewToken, ctx: &EventContext) {
+        if self.filter.contains(&token.mint) {
+            self.inner.on_new_token(token, ctx);
+        }
+    }
+
+    fn on_slot_complete(&self, slot: u64, events: &[(PumpEvent, EventContext)]) {
+        let events: Vec<_> =
+            events.iter().filter(|(event, _)| self.filter.matches(event)).cloned().collect();
+        if !events.is_empty() {
+            self.inner.on_slot_complete(slot, &events);
+        }
+    }
+
+    fn on_transaction(&self, tx: &TransactionUpdate, events: &[PumpEvent], ctx: &EventContext) {
+        let events: Vec<_> = events.iter().filter(|event| self.filter.matches(event)).cloned().collect();
+        if !events.is_empty() {
+            self.inner.on_transaction(tx, &events, ctx);
+        }
+    }
+
+    fn on_bonding_curve_update(
+        &self,
+        mint: &Pubkey,
+        curve: &BondingCurveAccount,
+        ctx: &AccountContext,
+    ) {
+        self.inner.on_bonding_curve_update(mint, curve, ctx);
+    }
+
+    fn on_pool_reserves(&self, reserves: &PoolReserves, ctx: &AccountContext) {
+        self.inner.on_pool_reserves(reserves, ctx);
+    }
+
+    fn on_account_update(&self, account: &AccountUpdate, ctx: &AccountContext) {
+        self.inner.on_account_update(account, ctx);
+    }
+
+    fn on_program_account(&self, account: &ProgramAccount, ctx: &AccountContext) {
+        self.inner.on_program_account(account, ctx);
+    }
+
+    fn on_slot_update(&self, update: &SlotUpdate) {
+        self.inner.on_slot_update(update);
+    }
+
+    fn on_block_meta(&self, meta: &BlockMetaUpdate) {
+        self.inner.on_block_meta(meta);
+    }
+
+    fn on_entry(&self, entry: &EntryUpdate) {
+        self.inner.on_entry(entry);
+    }
+
+    fn on_lag(&self, lag: &StreamLag) {
+        self.inner.on_lag(lag);
+    }
+
+    fn on_latency_report(&self, report: &LatencyReport) {
+        self.inner.on_latency_report(report);
+    }
+
+    fn on_stats(&self, stats: &StreamStats) {
+        self.inner.on_stats(stats);
+    }
+
+    fn on_raw_update(&self, update: &SubscribeUpdate) {
+        self.inner.on_raw_update(update);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Signature;
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Instant, SystemTime},
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        batches: Mutex<Vec<Vec<PumpEvent>>>,
+        transactions: Mutex<Vec<Vec<PumpEvent>>>,
+    }
+
+    impl EventHandler for Arc<Recorder> {
+        fn on_slot_complete(&self, _slot: u64, events: &[(PumpEvent, EventContext)]) {
+            let events = events.iter().map(|(event, _)| event.clone()).collect();
+            self.batches.lock().unwrap().push(events);
+        }
+
+        fn on_transaction(&self, _tx: &TransactionUpdate, events: &[PumpEvent], _ctx: &EventContext) {
+            self.transactions.lock().unwrap().push(events.to_vec());
+        }
+    }
+
+    fn ctx() -> EventContext {
+        EventContext {
+            slot: 1,
+            tx_index: 0,
+            signature: Signature::default(),
+            timestamp: Instant::now(),
+            elapsed: Default::default(),
+            received_at: SystemTime::now(),
+            block_time: None,
+            filters: Arc::from(Vec::new()),
+            unknown_tail: None,
+        }
+    }
+
+    fn tx() -> TransactionUpdate {
+        TransactionUpdate {
+            signature: Signature::default(),
+            slot: 1,
+            index: 0,
+            is_vote: false,
+            err: None,
+            fee: 0,
+            compute_units_consumed: None,
+            account_keys: Vec::new(),
+            pre_balances: Vec::new(),
+            post_balances: Vec::new(),
+            pre_token_balances: Vec::new(),
+            post_token_balances: Vec::new(),
+        }
+    }
+
+    fn trade(mint: Pubkey) -> PumpEvent {
+        PumpEvent::Trade(TradeEvent { mint, ..Default::default() })
+    }
+
+    #[test]
+    fn matches_mints_and_canonical_pools() {
+        let mint = Pubkey::new_unique();
+        let filter = SubscriptionFilter::new().with_mint(mint);
+        let pool = pda::canonical_pump_pool(&mint);
+
+        assert!(filter.matches(&trade(mint)));
+        assert!(!filter.matches(&trade(Pubkey::new_unique())));
+        assert!(filter.matches(&PumpEvent::Buy(BuyEvent { pool, ..Default::default() })));
+        assert!(!filter.matches(&PumpEvent::Buy(BuyEvent::default())));
+    }
+
+    #[test]
+    fn empty_allowlist_requests_no_transactions() {
+        assert!(SubscriptionFilter::new().request().transactions.is_empty());
+
+        let request = SubscriptionFilter::new().with_mint(Pubkey::new_unique()).request();
+        assert_eq!(request.transactions[SUBSCRIPTION_FILTER].account_include.len(), 4);
+    }
+
+    #[test]
+    fn batches_and_transactions_only_carry_allowlisted_events() {
+        let mint = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let recorder = Arc::new(Recorder::default());
+        let handler = MintFilterHandler {
+            filter: SubscriptionFilter::new().with_mint(mint),
+            inner: Arc::clone(&recorder),
+        };
+
+        handler.on_slot_complete(1, &[(trade(mint), ctx()), (trade(other), ctx())]);
+        handler.on_slot_complete(2, &[(trade(other), ctx())]);
+        assert_eq!(*recorder.batches.lock().unwrap(), vec![vec![trade(mint)]]);
+
+        handler.on_transaction(&tx(), &[trade(other), trade(mint)], &ctx());
+        handler.on_transaction(&tx(), &[trade(other)], &ctx());
+        assert_eq!(*recorder.transactions.lock().unwrap(), vec![vec![trade(mint)]]);
+    }
+}
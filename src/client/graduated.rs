@@ -0,0 +1,205 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use super::handler::{EventContext, EventHandler};
+#[cfg(feature = "rpc")]
+use crate::{
+    enrich::RpcFetcher,
+    error::{Error, Result},
+    models::ProgramAccount,
+};
+use crate::{
+    models::{CompleteEvent, CreatePoolEvent, MigrateEvent, PoolAccount},
+    pda::{self, WSOL_MINT},
+};
+
+/// 已毕业代币及其 PumpAmm 池
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraduatedToken {
+    pub mint: Pubkey,
+    /// bonding curve 完成的链上时间，未观察到 CompleteEvent 时为 None
+    pub completed_at: Option<i64>,
+    /// 交易该代币的池，迁移生成的标准池排在最前，尚未观察到建池时为空
+    pub pools: Vec<Pubkey>,
+}
+
+impl GraduatedToken {
+    /// 首选的池：标准池已知时为标准池，否则为最早登记的池
+    pub fn pool(&self) -> Option<Pubkey> {
+        self.pools.first().copied()
+    }
+}
+
+#[derive(Default)]
+struct State {
+    tokens: HashMap<Pubkey, GraduatedToken>,
+    /// 池地址 -> mint
+    pools: HashMap<Pubkey, Pubkey>,
+}
+
+/// 已毕业代币注册表
+///
+/// 维护 mint -> PumpAmm 池的对应关系，交易和路由组件可以直接查询某个代币在哪个池交易，
+/// 无需 `getProgramAccounts` 扫描。作为处理器挂到订阅上（例如通过 `HandlerRegistry`）后，
+/// 从 CompleteEvent 记录毕业的代币、从 MigrateEvent 和 CreatePoolEvent 登记迁移生成的池；
+/// 订阅开始前已存在的池可以用 `MarketSnapshot::seed_graduated` 批量导入，
+/// 或用 [`insert`](Self::insert) 逐个登记。
+///
+/// 池的代币一侧为 WSOL 时以另一侧为 mint，否则以 base 代币为 mint。同一代币有多个池时，
+/// 迁移生成的标准池（`pda::canonical_pump_pool`）优先。
+///
+/// 启用 `rpc` feature 并设置 [`with_rpc`](Self::with_rpc) 后，[`resolve`](Self::resolve)
+/// 在未命中时通过 RPC 查询标准池账户并写入注册表。
+///
+/// 克隆后的实例共享同一份数据。
+#[derive(Clone, Default)]
+pub struct GraduatedTokens {
+    state: Arc<RwLock<State>>,
+    #[cfg(feature = "rpc")]
+    rpc: Option<RpcFetcher>,
+}
+
+impl GraduatedTokens {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置未命中时使用的 RPC 客户端（需要启用 `rpc` feature）
+    #[cfg(feature = "rpc")]
+    pub fn with_rpc(mut self, rpc: RpcFetcher) -> Self {
+        self.rpc = Some(rpc);
+        self
+    }
+
+    /// 登记池，返回池对应的 mint；池已登记时不做改动
+    pub fn insert(&self, pool: Pubkey, base_mint: Pubkey, quote_mint: Pubkey) -> Pubkey {
+        let mint = pool_mint(&base_mint, &quote_mint);
+        let mut state = self.write();
+        if state.pools.insert(pool, mint).is_some() {
+            return mint;
+        }
+        let token = state.tokens.entry(mint).or_insert_with(|| GraduatedToken {
+            mint,
+            ..Default::default()
+        });
+        if pool == pda::canonical_pump_pool(&mint) {
+            token.pools.insert(0, pool);
+        } else {
+            token.pools.push(pool);
+        }
+        mint
+    }
+
+    /// 由池账户登记池，返回池对应的 mint
+    pub fn insert_pool_account(&self, pool: Pubkey, account: &PoolAccount) -> Pubkey {
+        self.insert(pool, account.base_mint, account.quote_mint)
+    }
+
+    /// 移除代币及其所有池，存在时返回移除的记录
+    pub fn remove(&self, mint: &Pubkey) -> Option<GraduatedToken> {
+        let mut state = self.write();
+        let token = state.tokens.remove(mint)?;
+        for pool in &token.pools {
+            state.pools.remove(pool);
+        }
+        Some(token)
+    }
+
+    /// 代币的记录
+    pub fn get(&self, mint: &Pubkey) -> Option<GraduatedToken> {
+        self.read().tokens.get(mint).cloned()
+    }
+
+    /// 交易该代币的首选池，见 [`GraduatedToken::pool`]
+    pub fn pool(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.read().tokens.get(mint)?.pool()
+    }
+
+    /// 池对应的 mint
+    pub fn mint(&self, pool: &Pubkey) -> Option<Pubkey> {
+        self.read().pools.get(pool).copied()
+    }
+
+    /// 代币是否已毕业（观察到 bonding curve 完成或已登记池）
+    pub fn is_graduated(&self, mint: &Pubkey) -> bool {
+        self.read().tokens.contains_key(mint)
+    }
+
+    /// 已记录的代币数量
+    pub fn len(&self) -> usize {
+        self.read().tokens.len()
+    }
+
+    /// 是否没有记录任何代币
+    pub fn is_empty(&self) -> bool {
+        self.read().tokens.is_empty()
+    }
+
+    /// 读取代币的首选池，未命中时通过 RPC 查询标准池账户，标准池不存在时返回 None
+    ///
+    /// 未设置 RPC 客户端且未命中时返回错误。
+    #[cfg(feature = "rpc")]
+    pub async fn resolve(&self, mint: &Pubkey) -> Result<Option<Pubkey>> {
+        if let Some(pool) = self.pool(mint) {
+            return Ok(Some(pool));
+        }
+        let Some(rpc) = &self.rpc else {
+            return Err(Error::Unknown(format!(
+                "代币 {mint} 的池不在注册表中，且未设置 RPC 客户端"
+            )));
+        };
+        let pool = pda::canonical_pump_pool(mint);
+        let Some(data) = rpc.account_data(&pool).await? else {
+            return Ok(None);
+        };
+        match ProgramAccount::from_account_data(&data)? {
+            ProgramAccount::Pool(account) => {
+                self.insert_pool_account(pool, &account);
+                Ok(Some(pool))
+            }
+            _ => Err(Error::ParseError("账户类型不是 Pool".to_string())),
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, State> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, State> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 池交易的代币：一侧为 WSOL 时取另一侧，否则取 base 代币
+fn pool_mint(base_mint: &Pubkey, quote_mint: &Pubkey) -> Pubkey {
+    if *base_mint == WSOL_MINT {
+        *quote_mint
+    } else {
+        *base_mint
+    }
+}
+
+impl EventHandler for GraduatedTokens {
+    fn on_complete_event(&self, event: &CompleteEvent, _ctx: &EventContext) {
+        self.write()
+            .tokens
+            .entry(event.mint)
+            .or_insert_with(|| GraduatedToken {
+                mint: event.mint,
+                ..Default::default()
+            })
+            .completed_at = Some(event.timestamp);
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, _ctx: &EventContext) {
+        self.insert(event.pool, event.base_mint, event.quote_mint);
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, _ctx: &EventContext) {
+        self.insert(event.pool, event.mint, WSOL_MINT);
+    }
+}
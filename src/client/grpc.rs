@@ -1,38 +1,216 @@
 use futures_util::{SinkExt, StreamExt};
-use log::error;
-use solana_sdk::signature::Signature;
-use std::{collections::HashMap, ops::ControlFlow, sync::Arc};
-use tokio::sync::Mutex;
+use log::{debug, error};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::HashMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+    time::{Instant, SystemTime},
+};
+use tokio::sync::{watch, Mutex};
 use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::prost::Message;
 use yellowstone_grpc_proto::geyser::{
-    subscribe_update::UpdateOneof, SubscribeRequest,
-    SubscribeRequestFilterTransactions, SubscribeRequestPing,
+    subscribe_request_filter_accounts_filter::Filter as AccountsFilter,
+    subscribe_request_filter_accounts_filter_memcmp::Data as MemcmpData,
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeRequestFilterAccounts,
+    SubscribeRequestFilterAccountsFilter, SubscribeRequestFilterAccountsFilterMemcmp,
+    SubscribeRequestFilterBlocksMeta,
+    SubscribeRequestFilterSlots, SubscribeRequestFilterTransactions, SubscribeRequestPing, SubscribeUpdateAccount,
+    SubscribeUpdate, SubscribeUpdateTransaction,
 };
+use yellowstone_grpc_proto::prelude::{Message as TransactionMessage, TransactionStatusMeta};
 
 use crate::{
     error::{Error, Result},
     models::{
-        BuyEvent, CompleteEvent, CreateEvent, CreatePoolEvent, CreateV2Event, SellEvent, TradeEvent,
+        BondingCurveAccount, BuyEvent, CreatePoolEvent, DepositEvent, MigrateEvent, NewToken,
+        ProgramAccount, PumpEvent, SellEvent, WithdrawEvent,
     },
-    parser::events::{
-        visit_program_logs, EventTrait,
-        BUY_DISCRIMINATOR, COMPLETE_DISCRIMINATOR, CREATE_DISCRIMINATOR, CREATE_POOL_DISCRIMINATOR,
-        CREATE_V2_DISCRIMINATOR, SELL_DISCRIMINATOR, TRADE_DISCRIMINATOR,
+    parser::{
+        accounts::{BONDING_CURVE_ACCOUNT_DISCRIMINATOR, POOL_ACCOUNT_DISCRIMINATOR},
+        events::{visit_events_reporting, EVENT_IX_TAG},
     },
+    pda::{self, PUMP_AMM_PROGRAM_ID, PUMP_PROGRAM_ID},
+};
+
+use super::{
+    batch::SlotBatcher,
+    cache::{apply_account_cache, AccountCache},
+    checkpoint::{CheckpointStore, SlotWatermark},
+    custom::{CustomEvent, CustomEvents},
+    deadletter::{deliver, DeadLetter, DeadLetterSink},
+    fills::{apply_fill_tracker, FillTracker},
+    filter::{mint_accounts, MintFilterHandler, SubscriptionFilter},
+    config::{Config, DispatchOrder},
+    dedup::DedupCache,
+    handler::{dispatch_event, AccountContext, EventContext, EventHandler, SharedEventFilter},
+    pipeline::{unknown_tail, ParsePipeline, Parsed},
+    pools::{apply_pool_tracker, PoolReserves, PoolTracker},
+    profiler::{LatencyProfiler, LatencyReport, LatencyStage},
+    recycle::FilterInterner,
+    stats::{StatsRecorder, StreamStats},
+    status::{ClientStatus, StatusTracker, StreamLag},
+    stream::{ChannelHandler, EventStream, DEFAULT_STREAM_CAPACITY},
+    subscription::Subscription,
+    updates::{AccountUpdate, BlockMetaUpdate, EntryUpdate, SlotUpdate, TransactionUpdate},
+    watchlist::{apply_watchlist, Watchlist},
 };
 
-use super::{config::Config, handler::EventHandler, handler::EventContext};
+/// (日志, `emit_cpi!` 事件指令数据, 基础事件上下文, 交易元数据)
+type PreparedTransaction = (Vec<String>, Vec<Vec<u8>>, EventContext, Option<TransactionUpdate>);
 
 /// gRPC客户端
 #[derive(Clone)]
 pub struct GrpcClient {
     config: Config,
+    /// 事件去重缓存，跨重连共享（未启用时为 None）
+    dedup: Option<DedupCache>,
+    /// 代币观察列表（未启用时为 None）
+    watchlist: Option<Watchlist>,
+    /// PumpAmm 池储备跟踪（未启用时为 None）
+    pool_tracker: Option<PoolTracker>,
+    /// 程序账户缓存（未启用时为 None）
+    account_cache: Option<AccountCache>,
+    /// 分阶段延迟剖析器（未启用时为 None）
+    profiler: Option<LatencyProfiler>,
+    /// 连接状态记录，所有克隆共享
+    status: Arc<StatusTracker>,
+    /// 槽位检查点存储（未启用时为 None）
+    checkpoint: Option<Arc<dyn CheckpointStore>>,
+    /// 自己发送的交易的成交跟踪（未启用时为 None）
+    fills: Option<FillTracker>,
+    /// 自定义事件类型注册表（未启用时为 None）
+    custom: Option<CustomEvents>,
+    /// 死信队列（未启用时为 None）
+    dead_letters: Option<Arc<dyn DeadLetterSink>>,
 }
 
 impl GrpcClient {
     /// 创建新的gRPC客户端
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let dedup = config.dedup_capacity.map(DedupCache::new);
+        Self {
+            config,
+            dedup,
+            watchlist: None,
+            pool_tracker: None,
+            account_cache: None,
+            profiler: None,
+            status: Arc::new(StatusTracker::default()),
+            checkpoint: None,
+            fills: None,
+            custom: None,
+            dead_letters: None,
+        }
+    }
+
+    /// 获取客户端连接状态快照
+    ///
+    /// 包含连接状态、使用的端点、最近收到消息的时间、已观察到的最高槽位和重连次数，
+    /// 适合用于嵌入 SDK 的服务的健康检查接口。
+    pub fn status(&self) -> ClientStatus {
+        self.status.snapshot(&self.config.url)
+    }
+
+    /// 挂载代币观察列表
+    ///
+    /// 之后的每个订阅都会为列表中 mint 的 bonding curve 添加账户订阅，
+    /// 并通过 `EventHandler::on_bonding_curve_update` 分发解码后的储备状态。
+    pub fn with_watchlist(mut self, watchlist: Watchlist) -> Self {
+        self.watchlist = Some(watchlist);
+        self
+    }
+
+    /// 挂载 PumpAmm 池储备跟踪
+    ///
+    /// 之后的每个订阅都会为跟踪中的池的代币账户添加账户订阅，
+    /// 并通过 `EventHandler::on_pool_reserves` 分发更新后的储备。
+    pub fn with_pool_tracker(mut self, tracker: PoolTracker) -> Self {
+        self.pool_tracker = Some(tracker);
+        self
+    }
+
+    /// 挂载程序账户缓存
+    ///
+    /// 之后的每个订阅都会为缓存订阅的账户添加账户订阅，账户变化后更新缓存中解码后的数据。
+    pub fn with_account_cache(mut self, cache: AccountCache) -> Self {
+        self.account_cache = Some(cache);
+        self
+    }
+
+    /// 挂载分阶段延迟剖析器
+    ///
+    /// 记录每个事件在网络、排队、解码、分发和处理器各阶段的耗时，
+    /// 按剖析器的统计周期通过 `EventHandler::on_latency_report` 交付延迟分布。
+    pub fn with_latency_profiler(mut self, profiler: LatencyProfiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// 挂载槽位检查点存储
+    ///
+    /// 订阅过程中已完整处理的最高槽位会写入该存储；重新订阅（包括进程重启后）时，
+    /// 若请求未设置 `from_slot`，则从检查点的下一个槽位开始回放，实现至少一次处理。
+    /// 回放依赖节点保留的历史槽位，超出保留范围时订阅会返回错误。
+    ///
+    /// 槽位在其区块元数据到达且交易全部分发后才视为完整处理，订阅请求会自动加入区块元数据过滤器。
+    /// Processed / Confirmed 承诺级别下分叉可能让检查点越过未被确认的槽位，需要精确续传时使用 Finalized。
+    pub fn with_checkpoint_store<S: CheckpointStore + 'static>(mut self, store: S) -> Self {
+        self.checkpoint = Some(Arc::new(store));
+        self
+    }
+
+    /// 挂载成交跟踪
+    ///
+    /// 之后的每个订阅都会为跟踪中的签名添加交易过滤器（包括执行失败的交易），
+    /// 交易出现在流中时通过 [`FillTracker::track`] 返回的接收端交付成交结果。
+    pub fn with_fill_tracker(mut self, tracker: FillTracker) -> Self {
+        self.fills = Some(tracker);
+        self
+    }
+
+    /// 挂载自定义事件类型注册表
+    ///
+    /// 之后的每个订阅都会在解析内置事件的同时解码注册表中的事件类型，并调用其回调。
+    pub fn with_custom_events(mut self, events: CustomEvents) -> Self {
+        self.custom = Some(events);
+        self
+    }
+
+    /// 挂载死信队列
+    ///
+    /// 之后的每个订阅都会把解码失败的事件和处理器回调 panic 的事件连同原始数据与上下文写入该队列，
+    /// 便于之后重放。处理器 panic 时跳过该事件继续处理，不再中止订阅。
+    pub fn with_dead_letter_sink<S: DeadLetterSink + 'static>(mut self, sink: S) -> Self {
+        self.dead_letters = Some(Arc::new(sink));
+        self
+    }
+
+    /// 判断事件是否首次出现，未启用去重时总是返回 `true`
+    #[inline]
+    fn first_seen(&self, signature: &Signature, discriminator: &[u8]) -> bool {
+        match &self.dedup {
+            Some(cache) => cache.insert(signature, discriminator),
+            None => true,
+        }
+    }
+
+    /// 槽位是否落后最新槽位超过 `Config::with_stale_slot_limit` 设置的限制
+    #[inline]
+    fn is_stale(&self, slot: u64) -> bool {
+        let Some(limit) = self.config.stale_slot_limit else {
+            return false;
+        };
+        let tip = self.status.last_slot();
+        if tip.saturating_sub(slot) > limit {
+            debug!(
+                "跳过过期事件: slot {} 落后最新槽位 {} 超过 {} 个槽位",
+                slot, tip, limit
+            );
+            return true;
+        }
+        false
     }
 
     /// 订阅指定程序ID的事件
@@ -45,7 +223,7 @@ impl GrpcClient {
     /// # 示例
     /// 
     /// ```no_run
-    /// use solana_pump_grpc_sdk::{GrpcClient, Config, EventHandler};
+    /// use solana_pump_grpc_sdk::{GrpcClient, Config, EventContext, EventHandler};
     /// use solana_pump_grpc_sdk::models::*;
     /// 
     /// struct MyHandler;
@@ -63,10 +241,211 @@ impl GrpcClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn subscribe<H: EventHandler>(
+    pub fn subscribe<H: EventHandler + 'static>(
         &self,
         program_id: String,
         handler: H,
+    ) -> Subscription {
+        self.subscribe_with_request(program_request(vec![program_id]), handler)
+    }
+
+    /// 在同一个 gRPC 流中订阅多个程序ID的事件
+    ///
+    /// 所有程序放入同一个交易过滤器的 `account_include`，触及其中任一程序的交易都会被推送。
+    /// 与逐个调用 `subscribe` 相比只占用一个连接，同一槽位内不同程序的事件按交易顺序分发，
+    /// 同时触及多个程序的交易只会推送一次。
+    ///
+    /// ```no_run
+    /// # use solana_pump_grpc_sdk::{Config, GrpcClient, LoggingEventHandler};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GrpcClient::new(Config::default());
+    /// let program_ids = vec![
+    ///     "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P".to_string(), // Pump
+    ///     "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA".to_string(), // PumpAmm
+    /// ];
+    /// client.subscribe_many(program_ids, LoggingEventHandler).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_many<H: EventHandler + 'static>(
+        &self,
+        program_ids: Vec<String>,
+        handler: H,
+    ) -> Subscription {
+        self.subscribe_with_request(program_request(program_ids), handler)
+    }
+
+    /// 只订阅单个代币相关的交易
+    ///
+    /// 根据 mint 推导 bonding curve、关联 bonding curve 账户（同时覆盖 Token 与 Token-2022）
+    /// 以及曲线完成后迁移生成的标准 PumpAmm 池地址，并以它们构建 `account_include` 过滤器。
+    /// 池地址在代币毕业前并不存在，但提前包含它可以在迁移后无缝接收 PumpAmm 事件。
+    ///
+    /// 注意：过滤发生在交易级别，同一交易中其他代币的事件也会被分发。
+    pub fn subscribe_mint<H: EventHandler + 'static>(&self, mint: Pubkey, handler: H) -> Subscription {
+        let account_include = mint_accounts(&mint)
+            .iter()
+            .map(|account| account.to_string())
+            .collect();
+        let subscribe_request = SubscribeRequest {
+            transactions: HashMap::from([(
+                "mint".to_string(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    signature: None,
+                    account_include,
+                    account_exclude: vec![],
+                    account_required: vec![],
+                },
+            )]),
+            ..Default::default()
+        };
+
+        self.subscribe_with_request(subscribe_request, handler)
+    }
+
+    /// 只订阅白名单中代币相关的交易，并且只分发这些代币的事件，见 [`SubscriptionFilter`]
+    ///
+    /// 白名单为空时不订阅任何交易。
+    ///
+    /// ```no_run
+    /// # use solana_pump_grpc_sdk::{Config, GrpcClient, LoggingEventHandler, SubscriptionFilter};
+    /// # use solana_sdk::pubkey::Pubkey;
+    /// # async fn example(mint_a: Pubkey, mint_b: Pubkey) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GrpcClient::new(Config::default());
+    /// let filter = SubscriptionFilter::new().with_mints([mint_a, mint_b]);
+    /// client.subscribe_filtered(filter, LoggingEventHandler).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_filtered<H: EventHandler + 'static>(
+        &self,
+        filter: SubscriptionFilter,
+        handler: H,
+    ) -> Subscription {
+        let subscribe_request = filter.request();
+        self.subscribe_with_request(subscribe_request, MintFilterHandler { filter, inner: handler })
+    }
+
+    /// 只订阅单个 PumpAmm 池相关的交易
+    ///
+    /// 以池地址构建 `account_include` 过滤器（池的所有指令都会引用池账户本身），
+    /// 并在分发阶段只保留 `pool` 字段等于该池的 PumpAmm 事件，其他事件会被丢弃。
+    /// `on_slot_complete` / `on_transaction` 只交付该池的事件，账户、槽位、统计等非事件回调直接转发。
+    pub fn subscribe_pool<H: EventHandler + 'static>(&self, pool: Pubkey, handler: H) -> Subscription {
+        let subscribe_request = SubscribeRequest {
+            transactions: HashMap::from([(
+                "pool".to_string(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    signature: None,
+                    account_include: vec![pool.to_string()],
+                    account_exclude: vec![],
+                    account_required: vec![],
+                },
+            )]),
+            ..Default::default()
+        };
+
+        self.subscribe_with_request(subscribe_request, PoolEventHandler { pool, inner: handler })
+    }
+
+    /// 订阅所有 bonding curve 和 PumpAmm 池账户的更新
+    ///
+    /// 以程序 owner 加账户 discriminator 构建两个账户过滤器（`bonding_curves` / `pools`），
+    /// 订阅建立时先推送现有账户的快照，之后推送每次变化。账户解码后通过
+    /// `EventHandler::on_program_account` 分发，可以直接读取实时的虚拟储备，
+    /// 原始数据仍然经过 `on_account_update`。
+    ///
+    /// 注意：全部账户的数据量很大，只关心少数代币时使用 [`Watchlist`] 或 [`PoolTracker`]。
+    pub fn subscribe_accounts<H: EventHandler + 'static>(&self, handler: H) -> Subscription {
+        let subscribe_request = SubscribeRequest {
+            accounts: HashMap::from([
+                (
+                    "bonding_curves".to_string(),
+                    owner_accounts(PUMP_PROGRAM_ID, BONDING_CURVE_ACCOUNT_DISCRIMINATOR),
+                ),
+                (
+                    "pools".to_string(),
+                    owner_accounts(PUMP_AMM_PROGRAM_ID, POOL_ACCOUNT_DISCRIMINATOR),
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        self.subscribe_with_request(subscribe_request, handler)
+    }
+
+    /// 订阅指定程序ID的事件，以 [`EventStream`] 交付，队列长度为 [`DEFAULT_STREAM_CAPACITY`]
+    ///
+    /// 适合在 `select!` 循环中消费事件：
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use solana_pump_grpc_sdk::{Config, GrpcClient, PumpEvent};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GrpcClient::new(Config::default());
+    /// let mut events = client.subscribe_stream("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P".to_string());
+    /// while let Some((event, ctx)) = events.next().await {
+    ///     if let PumpEvent::Create(create) = event {
+    ///         println!("{} {}", ctx.slot, create.mint);
+    ///     }
+    /// }
+    /// events.finish().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_stream(&self, program_id: String) -> EventStream {
+        let subscribe_request = program_request(vec![program_id]);
+        self.subscribe_stream_with_request(subscribe_request, DEFAULT_STREAM_CAPACITY)
+    }
+
+    /// 使用自定义的 `SubscribeRequest` 订阅事件，以 [`EventStream`] 交付
+    ///
+    /// `capacity` 为尚未读取的事件的队列长度（至少为 1），队列已满时丢弃新事件。
+    pub fn subscribe_stream_with_request(
+        &self,
+        subscribe_request: SubscribeRequest,
+        capacity: usize,
+    ) -> EventStream {
+        let (handler, events) = ChannelHandler::new(capacity);
+        let dropped = handler.dropped();
+        let subscription = self.subscribe_with_request(subscribe_request, handler);
+        EventStream::new(events, subscription, dropped)
+    }
+
+    /// 使用自定义的 `SubscribeRequest` 订阅事件
+    ///
+    /// 可以在一个请求中配置多个具名交易过滤器，命中的过滤器名称会通过
+    /// `EventContext::filters` 传递给处理器，从而让一个处理器服务多个逻辑订阅。
+    /// 若请求未设置 `commitment`，则使用配置中的承诺级别。
+    ///
+    /// 订阅在后台任务中运行，必须在 Tokio 运行时中调用。返回的 [`Subscription`] 可用于查询状态、
+    /// 查看生效的过滤器或中止订阅；它本身也是一个 future，`.await` 得到订阅的最终结果。
+    pub fn subscribe_with_request<H: EventHandler + 'static>(
+        &self,
+        subscribe_request: SubscribeRequest,
+        handler: H,
+    ) -> Subscription {
+        let (filter_tx, filter_rx) = watch::channel(subscribe_request.clone());
+        let client = self.clone();
+        let task = tokio::spawn(async move {
+            client
+                .run_subscription(subscribe_request, handler, filter_tx)
+                .await
+        });
+        Subscription::new(task, filter_rx)
+    }
+
+    /// 订阅主循环，生效的请求（包括观察列表等合并进来的过滤器）通过 `filter_tx` 发布
+    async fn run_subscription<H: EventHandler>(
+        &self,
+        mut subscribe_request: SubscribeRequest,
+        handler: H,
+        filter_tx: watch::Sender<SubscribeRequest>,
     ) -> Result<()> {
         let tls_config = ClientTlsConfig::new().with_native_roots();
 
@@ -80,6 +459,16 @@ impl GrpcClient {
             .keep_alive_while_idle(self.config.keep_alive_while_idle)
             .timeout(self.config.timeout);
 
+        if let Some(interval) = self.config.http2_keep_alive_interval {
+            builder = builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.config.keep_alive_timeout {
+            builder = builder.keep_alive_timeout(timeout);
+        }
+        if let Some(enabled) = self.config.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(enabled);
+        }
+
         let client = builder
             .connect()
             .await
@@ -87,247 +476,873 @@ impl GrpcClient {
 
         let client = Arc::new(Mutex::new(client));
 
-        let addrs = vec![program_id.clone()];
-        let subscribe_request = SubscribeRequest {
-            transactions: HashMap::from([(
-                "client".to_string(),
-                SubscribeRequestFilterTransactions {
-                    vote: Some(false),
-                    failed: Some(false),
-                    signature: None,
-                    account_include: addrs,
-                    account_exclude: vec![],
-                    account_required: vec![],
+        if subscribe_request.commitment.is_none() {
+            subscribe_request.commitment = Some(self.config.commitment.into());
+        }
+
+        // 延迟告警和过期过滤需要链上最新槽位，请求中没有槽位订阅时自动添加
+        let tracks_tip =
+            self.config.lag_threshold.is_some() || self.config.stale_slot_limit.is_some();
+        if tracks_tip && subscribe_request.slots.is_empty() {
+            subscribe_request.slots.insert(
+                "lag".to_string(),
+                SubscribeRequestFilterSlots {
+                    filter_by_commitment: Some(false),
+                    interslot_updates: Some(false),
                 },
-            )]),
-            commitment: Some(self.config.commitment.into()),
-            ..Default::default()
-        };
+            );
+        }
+
+        // 按槽位整批交付以区块元数据判断槽位完成，成交跟踪从区块元数据获取区块高度判断交易过期，
+        // 请求中没有区块元数据订阅时自动添加
+        let needs_blocks_meta =
+            self.config.slot_batching || self.fills.is_some() || self.checkpoint.is_some();
+        if needs_blocks_meta && subscribe_request.blocks_meta.is_empty() {
+            subscribe_request.blocks_meta.insert(
+                "blocks_meta".to_string(),
+                SubscribeRequestFilterBlocksMeta {},
+            );
+        }
+        let mut batcher = self.config.slot_batching.then(SlotBatcher::new);
 
+        // 检查点：从上次完整处理的槽位之后继续
+        if subscribe_request.from_slot.is_none() {
+            if let Some(store) = &self.checkpoint {
+                if let Some(slot) = store.load()? {
+                    debug!("从检查点槽位 {} 之后恢复订阅", slot);
+                    subscribe_request.from_slot = Some(slot + 1);
+                }
+            }
+        }
+        let mut watermark = self.checkpoint.clone().map(SlotWatermark::new);
+
+        // 观察列表：把 bonding curve 账户过滤器合并进请求，列表变化时重新发送
+        let mut watch_rx = self.watchlist.as_ref().map(|w| w.subscribe());
+        if let Some(rx) = watch_rx.as_mut() {
+            apply_watchlist(&rx.borrow_and_update(), &mut subscribe_request);
+        }
+        let mut vaults_rx = self.pool_tracker.as_ref().map(|t| t.subscribe_vaults());
+        if let Some(rx) = vaults_rx.as_mut() {
+            apply_pool_tracker(&rx.borrow_and_update(), &mut subscribe_request);
+        }
+        let mut cached_rx = self.account_cache.as_ref().map(|c| c.subscribe_watched());
+        if let Some(rx) = cached_rx.as_mut() {
+            apply_account_cache(&rx.borrow_and_update(), &mut subscribe_request);
+        }
+        let mut fills_rx = self.fills.as_ref().map(|f| f.subscribe());
+        if let Some(rx) = fills_rx.as_mut() {
+            apply_fill_tracker(&rx.borrow_and_update(), &mut subscribe_request);
+        }
+
+        filter_tx.send_replace(subscribe_request.clone());
         let (mut subscribe_tx, mut stream) = client
             .lock()
             .await
-            .subscribe_with_request(Some(subscribe_request))
+            .subscribe_with_request(Some(subscribe_request.clone()))
             .await
             .map_err(|e| Error::SubscribeError(e.to_string()))?;
+        let _connection = self.status.connected();
+
+        // 并行解析模式：交易日志交给工作线程解析，按配置的顺序语义分发
+        let mut pipeline = self
+            .config
+            .parse_workers
+            .map(|workers| {
+                ParsePipeline::new(
+                    workers,
+                    self.config.dispatch_order,
+                    self.config.decode_mode,
+                    self.config.event_source,
+                    self.config.buffer_reuse,
+                    self.custom.clone(),
+                    self.dead_letters.clone(),
+                )
+            })
+            .transpose()?;
+        let mut filters = FilterInterner::new(self.config.buffer_reuse);
+        // 并行解析时区块高度等在途交易分发完后再交给成交跟踪，避免把最后一个有效区块中的交易判为过期
+        let mut pending_block_height = None;
+        let mut stats = self
+            .config
+            .stats_interval
+            .map(|interval| StatsRecorder::new(&self.config.url, interval));
 
-        while let Some(message) = stream.next().await {
-            match message {
-                Ok(msg) => match msg.update_oneof {
-                    Some(UpdateOneof::Transaction(sut)) => {
-                        let slot = sut.slot;
-                        if let Some(tx_info) = sut.transaction {
-                            let tx_index = tx_info.index;
-                            let signature = Signature::try_from(tx_info.signature.as_slice())
-                                .map_err(|_| Error::SignatureParse)?;
-                                   if let Some(meta) = tx_info.meta {
-                                       let start = std::time::Instant::now();
-                                       let logs = meta.log_messages;
-                                       if !logs.is_empty() {
-                                           self.handle_logs(
-                                               slot,
-                                               tx_index,
-                                               &signature,
-                                               &logs,
-                                               start,
-                                               &handler,
-                                           )
-                                           .await?;
-                                       }
-                                   }
+        loop {
+            if let Some(report) = self.profiler.as_ref().and_then(LatencyProfiler::take_due) {
+                handler.on_latency_report(&report);
+            }
+            if let Some(recorder) = stats.as_mut() {
+                if let Some(report) = recorder.take_due() {
+                    handler.on_stats(&report);
+                    // 主动 ping，测量下一周期的往返时间
+                    let _ = subscribe_tx.send(recorder.ping_request()).await;
+                }
+            }
+            let message = tokio::select! {
+                message = stream.next() => match message {
+                    Some(message) => message,
+                    None => break,
+                },
+                Some(mut batch) = next_parsed(&mut pipeline) => {
+                    if let Some(recorder) = stats.as_mut() {
+                        recorder.on_decode_failures(batch.iter().map(|p| p.decode_failures).sum());
+                    }
+                    let max_slot = self.dispatch_parsed(&mut batch, &handler, batcher.as_mut());
+                    if let Some(pipeline) = pipeline.as_ref() {
+                        pipeline.recycle(batch);
+                    }
+                    let idle = pipeline.as_ref().is_some_and(|p| !p.in_flight());
+                    if let (true, Some(fills), Some(height)) =
+                        (idle, &self.fills, pending_block_height.take())
+                    {
+                        fills.observe_block_height(height);
+                    }
+                    // 无序模式下只有在没有在途交易时，之前的槽位才确定已全部分发
+                    let settled = self.config.dispatch_order == DispatchOrder::Ordered || idle;
+                    if let Some(batcher) = batcher.as_mut() {
+                        let tip = self.status.last_slot();
+                        if idle {
+                            batcher.flush(None, tip, &handler);
+                        } else if let (true, Some(slot)) = (settled, max_slot) {
+                            // 有序模式下已分发到该槽位，之前的槽位必然已经全部分发
+                            batcher.flush(Some(slot.saturating_sub(1)), tip, &handler);
                         }
                     }
-                    Some(UpdateOneof::Ping(_)) => {
-                        let _ = subscribe_tx
-                            .send(SubscribeRequest {
-                                ping: Some(SubscribeRequestPing { id: 1 }),
-                                ..Default::default()
-                            })
-                            .await;
+                    if let Some(watermark) = watermark.as_mut() {
+                        if idle {
+                            watermark.commit(None);
+                        } else if let (true, Some(slot)) = (settled, max_slot) {
+                            watermark.commit(Some(slot.saturating_sub(1)));
+                        }
                     }
-                    _ => {}
-                },
+                    continue;
+                }
+                Some(curves) = watch_changed(&mut watch_rx) => {
+                    apply_watchlist(&curves, &mut subscribe_request);
+                    subscribe_tx
+                        .send(subscribe_request.clone())
+                        .await
+                        .map_err(|e| Error::SubscribeError(e.to_string()))?;
+                    filter_tx.send_replace(subscribe_request.clone());
+                    continue;
+                }
+                Some(vaults) = watch_changed(&mut vaults_rx) => {
+                    apply_pool_tracker(&vaults, &mut subscribe_request);
+                    subscribe_tx
+                        .send(subscribe_request.clone())
+                        .await
+                        .map_err(|e| Error::SubscribeError(e.to_string()))?;
+                    filter_tx.send_replace(subscribe_request.clone());
+                    continue;
+                }
+                Some(watched) = watch_changed(&mut cached_rx) => {
+                    apply_account_cache(&watched, &mut subscribe_request);
+                    subscribe_tx
+                        .send(subscribe_request.clone())
+                        .await
+                        .map_err(|e| Error::SubscribeError(e.to_string()))?;
+                    filter_tx.send_replace(subscribe_request.clone());
+                    continue;
+                }
+                Some(signatures) = watch_changed(&mut fills_rx) => {
+                    apply_fill_tracker(&signatures, &mut subscribe_request);
+                    subscribe_tx
+                        .send(subscribe_request.clone())
+                        .await
+                        .map_err(|e| Error::SubscribeError(e.to_string()))?;
+                    filter_tx.send_replace(subscribe_request.clone());
+                    continue;
+                }
+            };
+
+            let msg = match message {
+                Ok(msg) => msg,
                 Err(e) => {
                     error!("Stream error: {:?}", e);
                     return Err(Error::SubscribeError(e.to_string()));
                 }
+            };
+            self.status.on_message();
+            if let Some(recorder) = stats.as_mut() {
+                recorder.on_message(msg.encoded_len());
+            }
+            if let Some(profiler) = &self.profiler {
+                profiler.record_network(msg.created_at.as_ref(), SystemTime::now());
+            }
+            handler.on_raw_update(&msg);
+
+            // 所有更新类型都在同一个流中到达，按类型路由到对应的处理方法
+            match msg.update_oneof {
+                Some(UpdateOneof::Transaction(sut)) => {
+                    let slot = sut.slot;
+                    self.status.on_slot(slot);
+                    if let Some((logs, cpi, base_ctx, transaction)) =
+                        self.prepare_transaction(sut, filters.intern(msg.filters))?
+                    {
+                        match pipeline.as_mut() {
+                            Some(pipeline) => {
+                                pipeline.submit(logs, cpi, base_ctx, transaction).await?
+                            }
+                            None => {
+                                let failures = self.handle_logs(
+                                    &logs,
+                                    &cpi,
+                                    base_ctx,
+                                    transaction,
+                                    &handler,
+                                    batcher.as_mut(),
+                                );
+                                if let Some(recorder) = stats.as_mut() {
+                                    recorder.on_decode_failures(failures);
+                                }
+                            }
+                        }
+                    }
+                    if pipeline.is_none() {
+                        if let Some(batcher) = batcher.as_mut() {
+                            batcher.flush(None, self.status.last_slot(), &handler);
+                        }
+                    }
+                }
+                Some(UpdateOneof::Account(account_update)) => {
+                    self.status.on_slot(account_update.slot);
+                    let watch_ref = watch_rx.as_ref().map(|rx| rx.borrow());
+                    self.handle_account(
+                        account_update,
+                        filters.intern(msg.filters),
+                        watch_ref.as_deref(),
+                        &handler,
+                    );
+                }
+                Some(UpdateOneof::Slot(slot_update)) => {
+                    self.status.on_slot(slot_update.slot);
+                    handler.on_slot_update(&slot_update.into());
+                }
+                Some(UpdateOneof::BlockMeta(block_meta)) => {
+                    let idle = pipeline.as_ref().is_none_or(|p| !p.in_flight());
+                    if let Some(batcher) = batcher.as_mut() {
+                        batcher.block_completed(block_meta.slot);
+                        if idle {
+                            batcher.flush(None, self.status.last_slot(), &handler);
+                        }
+                    }
+                    if let Some(watermark) = watermark.as_mut() {
+                        watermark.block_completed(block_meta.slot);
+                        if idle {
+                            watermark.commit(None);
+                        }
+                    }
+                    if let (Some(fills), Some(height)) = (&self.fills, &block_meta.block_height) {
+                        if idle {
+                            fills.observe_block_height(height.block_height);
+                        } else {
+                            pending_block_height = Some(height.block_height);
+                        }
+                    }
+                    handler.on_block_meta(&block_meta.into());
+                }
+                Some(UpdateOneof::Entry(entry)) => {
+                    handler.on_entry(&entry.into());
+                }
+                Some(UpdateOneof::Ping(_)) => {
+                    let ping = match stats.as_mut() {
+                        Some(recorder) => recorder.ping_request(),
+                        None => SubscribeRequest {
+                            ping: Some(SubscribeRequestPing { id: 1 }),
+                            ..Default::default()
+                        },
+                    };
+                    let _ = subscribe_tx.send(ping).await;
+                }
+                Some(UpdateOneof::Pong(pong)) => {
+                    if let Some(recorder) = stats.as_mut() {
+                        recorder.on_pong(pong.id);
+                    }
+                }
+                // 其余类型（Block、TransactionStatus）可通过 on_raw_update 获取
+                _ => {}
+            }
+        }
+
+        // 流结束后分发仍在解析中的交易
+        if let Some(pipeline) = pipeline.as_mut() {
+            while let Some(mut batch) = pipeline.next_ready().await {
+                self.dispatch_parsed(&mut batch, &handler, batcher.as_mut());
+                pipeline.recycle(batch);
             }
         }
+        if let Some(batcher) = batcher.as_mut() {
+            batcher.flush_all(&handler);
+        }
         Ok(())
     }
 
-    async fn handle_logs<H: EventHandler>(
+    /// 从交易更新中取出日志和 `emit_cpi!` 事件指令数据并构建基础事件上下文，两者都没有时返回 None
+    ///
+    /// 事件指令数据只在事件来源包含内部指令（`Config::with_event_source`）时提取。
+    /// 启用了交易元数据交付或交易的签名正在被成交跟踪时，同时构建交易元数据。
+    fn prepare_transaction(
         &self,
-        slot: u64,
-        tx_index: u64,
-        signature: &Signature,
-        logs: &[String],
-        start_time: std::time::Instant,
-        handler: &H,
-    ) -> Result<()> {
-        // 优化：使用 events.rs 中导出的 discriminator 常量，避免重复定义
-
-        let mut logged_create = false;
-        let mut logged_create_v2 = false;
-        let mut logged_complete = false;
-        let mut logged_trade = false;
-        let mut logged_buy = false;
-        let mut logged_create_pool = false;
-        let mut logged_sell = false;
+        sut: SubscribeUpdateTransaction,
+        filters: Arc<[String]>,
+    ) -> Result<Option<PreparedTransaction>> {
+        let Some(tx_info) = sut.transaction else {
+            return Ok(None);
+        };
+        let signature =
+            Signature::try_from(tx_info.signature.as_slice()).map_err(|_| Error::SignatureParse)?;
+        let Some(mut meta) = tx_info.meta else {
+            return Ok(None);
+        };
+        let message = tx_info.transaction.and_then(|tx| tx.message);
+        let cpi = if self.config.event_source.cpi() {
+            cpi_event_data(message.as_ref(), &meta)
+        } else {
+            Vec::new()
+        };
+        if meta.log_messages.is_empty() && cpi.is_empty() {
+            return Ok(None);
+        }
+        let logs = std::mem::take(&mut meta.log_messages);
+        let transaction_meta = self.config.transaction_meta
+            || self.fills.as_ref().is_some_and(|fills| fills.is_tracked(&signature));
+        let transaction = transaction_meta.then(|| {
+            TransactionUpdate::from_proto(
+                signature,
+                sut.slot,
+                tx_info.index,
+                tx_info.is_vote,
+                message,
+                meta,
+            )
+        });
 
         // 优化：预先创建基础 EventContext，只更新 elapsed
         let base_ctx = EventContext {
-            slot,
-            tx_index,
-            signature: *signature,
-            timestamp: start_time,
+            slot: sut.slot,
+            tx_index: tx_info.index,
+            signature,
+            timestamp: std::time::Instant::now(),
             elapsed: std::time::Duration::ZERO,
+            received_at: std::time::SystemTime::now(),
+            block_time: None,
+            filters,
+            unknown_tail: None,
         };
+        Ok(Some((logs, cpi, base_ctx, transaction)))
+    }
 
-        // 优化：内联函数检查是否所有事件都已找到（避免重复代码）
-        #[inline(always)]
-        fn all_logged(
-            create: bool,
-            create_v2: bool,
-            complete: bool,
-            trade: bool,
-            buy: bool,
-            create_pool: bool,
-            sell: bool,
-        ) -> bool {
-            create && create_v2 && complete && trade && buy && create_pool && sell
-        }
-
-        visit_program_logs(logs, |discriminator, data| {
-            // 优化：使用直接字节比较，避免函数调用开销
-            // 优化：优先检查最常见的事件类型（Buy/Sell > Trade > 其他）
-            if discriminator == BUY_DISCRIMINATOR {
-                if !logged_buy {
-                    if let Ok(buy_event) = BuyEvent::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_buy_event(
-                            &buy_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_buy = true;
+    /// 按顺序分发并行流水线解析完成的交易，返回本批交易的最高槽位
+    ///
+    /// 分发后各交易的事件列表被清空，批次可交还流水线复用。
+    fn dispatch_parsed<H: EventHandler>(
+        &self,
+        batch: &mut [Parsed],
+        handler: &H,
+        mut batcher: Option<&mut SlotBatcher>,
+    ) -> Option<u64> {
+        let mut max_slot = None;
+        for parsed in batch.iter_mut() {
+            let decoded = self.profiler.as_ref().map(|profiler| {
+                let started = parsed.decode_started;
+                profiler.record(LatencyStage::Queue, started.duration_since(parsed.ctx.timestamp));
+                profiler.record(LatencyStage::Decode, parsed.decoded_at.duration_since(started));
+                parsed.decoded_at
+            });
+            let mut new_tokens = Vec::new();
+            let parsed_any = !parsed.events.is_empty();
+            let mut dispatched = Vec::new();
+            for (discriminator, event, tail) in parsed.events.drain(..) {
+                if let Some(ctx) =
+                    self.dispatch(&discriminator, &event, tail, &parsed.ctx, decoded, handler)
+                {
+                    NewToken::observe(&mut new_tokens, &event);
+                    if parsed.transaction.is_some() {
+                        dispatched.push(event.clone());
+                    }
+                    if let Some(batcher) = batcher.as_deref_mut() {
+                        batcher.push(event, ctx);
                     }
                 }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
-                }
-                return ControlFlow::Continue(());
             }
+            self.dispatch_custom(std::mem::take(&mut parsed.custom), &parsed.ctx);
+            self.dispatch_new_tokens(new_tokens, &parsed.ctx, handler);
+            if let Some(transaction) = parsed.transaction.take() {
+                self.dispatch_transaction(
+                    &transaction,
+                    &dispatched,
+                    parsed_any,
+                    &parsed.ctx,
+                    handler,
+                );
+            }
+            max_slot = max_slot.max(Some(parsed.ctx.slot));
+        }
+        max_slot
+    }
 
-            if discriminator == SELL_DISCRIMINATOR {
-                if !logged_sell {
-                    if let Ok(sell_event) = SellEvent::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_sell_event(
-                            &sell_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_sell = true;
-                    }
-                }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
-                }
-                return ControlFlow::Continue(());
+    /// 分发账户更新；若账户是观察列表中的 bonding curve，额外解码并分发储备状态
+    fn handle_account<H: EventHandler>(
+        &self,
+        account_update: SubscribeUpdateAccount,
+        filters: Arc<[String]>,
+        curves: Option<&HashMap<Pubkey, Pubkey>>,
+        handler: &H,
+    ) {
+        let Some(info) = account_update.account else {
+            return;
+        };
+        let write_version = info.write_version;
+        let signature = info
+            .txn_signature
+            .as_deref()
+            .and_then(|sig| Signature::try_from(sig).ok());
+        let Some(account) = AccountUpdate::from_proto(info, account_update.is_startup) else {
+            return;
+        };
+        let ctx = AccountContext {
+            slot: account_update.slot,
+            pubkey: account.pubkey,
+            write_version,
+            signature,
+            received_at: std::time::SystemTime::now(),
+            filters,
+        };
+        handler.on_account_update(&account, &ctx);
+
+        if account.owner == PUMP_PROGRAM_ID || account.owner == PUMP_AMM_PROGRAM_ID {
+            if let Ok(program_account) = ProgramAccount::from_account_data(&account.data) {
+                handler.on_program_account(&program_account, &ctx);
             }
+        }
 
-            if discriminator == TRADE_DISCRIMINATOR {
-                if !logged_trade {
-                    if let Ok(trade_event) = TradeEvent::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_trade_event(
-                            &trade_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_trade = true;
-                    }
-                }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
-                }
-                return ControlFlow::Continue(());
+        if let Some(cache) = &self.account_cache {
+            cache.apply_account(&account.pubkey, &account.data, ctx.slot);
+        }
+
+        if let Some(tracker) = &self.pool_tracker {
+            if let Some(reserves) = tracker.apply_account(&account.pubkey, &account.data, ctx.slot)
+            {
+                handler.on_pool_reserves(&reserves, &ctx);
+                return;
             }
+        }
 
-            if discriminator == CREATE_DISCRIMINATOR {
-                if !logged_create {
-                    if let Ok(create_event) = CreateEvent::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_create_event(
-                            &create_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_create = true;
-                    }
+        let Some(mint) = curves.and_then(|curves| curves.get(&account.pubkey)) else {
+            return;
+        };
+        match BondingCurveAccount::from_account_data(&account.data) {
+            Ok(curve) => handler.on_bonding_curve_update(mint, &curve, &ctx),
+            Err(e) => debug!("bonding curve {} 解码失败: {}", account.pubkey, e),
+        }
+    }
+
+    /// 解析交易日志并依次分发事件，返回解码失败的事件数量
+    fn handle_logs<H: EventHandler>(
+        &self,
+        logs: &[String],
+        cpi: &[Vec<u8>],
+        base_ctx: EventContext,
+        transaction: Option<TransactionUpdate>,
+        handler: &H,
+        mut batcher: Option<&mut SlotBatcher>,
+    ) -> usize {
+        let profiler = self.profiler.as_ref();
+        // 内联解析时解码与分发交替进行，每个事件的解码耗时从上一个处理器返回时算起
+        let mut stage_start = profiler.map(|profiler| {
+            let now = Instant::now();
+            profiler.record(LatencyStage::Queue, now.duration_since(base_ctx.timestamp));
+            now
+        });
+        let mut new_tokens = Vec::new();
+        let mut parsed_any = false;
+        let mut dispatched = Vec::new();
+        let mode = self.config.decode_mode;
+        let source = self.config.event_source;
+        let visitor = |discriminator: &[u8], event: PumpEvent, tail: &[u8]| {
+            parsed_any = true;
+            let decoded = profiler.zip(stage_start).map(|(profiler, start)| {
+                let now = Instant::now();
+                profiler.record(LatencyStage::Decode, now.duration_since(start));
+                now
+            });
+            let tail = unknown_tail(tail);
+            if let Some(ctx) = self.dispatch(discriminator, &event, tail, &base_ctx, decoded, handler)
+            {
+                NewToken::observe(&mut new_tokens, &event);
+                if transaction.is_some() {
+                    dispatched.push(event.clone());
                 }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
+                if let Some(batcher) = batcher.as_deref_mut() {
+                    batcher.push(event, ctx);
                 }
-                return ControlFlow::Continue(());
             }
-
-            if discriminator == CREATE_V2_DISCRIMINATOR {
-                if !logged_create_v2 {
-                    if let Ok(create_v2_event) = CreateV2Event::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_create_v2_event(
-                            &create_v2_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_create_v2 = true;
-                    }
+            if stage_start.is_some() {
+                stage_start = Some(Instant::now());
+            }
+        };
+        let failures =
+            visit_events_reporting(logs, cpi, source, mode, visitor, |discriminator, data, e| {
+                if let Some(sink) = &self.dead_letters {
+                    let letter = DeadLetter::decode_failure(discriminator, data, e, &base_ctx);
+                    deliver(sink.as_ref(), letter);
                 }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
+            });
+        if let Some(custom) = &self.custom {
+            self.dispatch_custom(custom.decode(logs), &base_ctx);
+        }
+        self.dispatch_new_tokens(new_tokens, &base_ctx, handler);
+        if let Some(transaction) = transaction {
+            self.dispatch_transaction(&transaction, &dispatched, parsed_any, &base_ctx, handler);
+        }
+        failures
+    }
+
+    /// 过滤过期事件并去重后，依次调用自定义事件的回调
+    ///
+    /// 去重按 discriminator 判断一次，同一交易中的多个同类事件要么全部分发，要么全部跳过。
+    fn dispatch_custom(&self, events: Vec<CustomEvent>, base_ctx: &EventContext) {
+        if events.is_empty() || self.is_stale(base_ctx.slot) {
+            return;
+        }
+        let mut decided: Vec<([u8; 8], bool)> = Vec::new();
+        for event in events {
+            let first_seen = match decided.iter().find(|(d, _)| *d == event.discriminator) {
+                Some((_, first_seen)) => *first_seen,
+                None => {
+                    let first_seen = self.first_seen(&base_ctx.signature, &event.discriminator);
+                    decided.push((event.discriminator, first_seen));
+                    first_seen
                 }
-                return ControlFlow::Continue(());
+            };
+            if !first_seen {
+                continue;
             }
+            let ctx = EventContext {
+                elapsed: base_ctx.timestamp.elapsed(),
+                ..base_ctx.clone()
+            };
+            (event.call)(&ctx);
+        }
+    }
 
-            if discriminator == COMPLETE_DISCRIMINATOR {
-                if !logged_complete {
-                    if let Ok(complete_event) = CompleteEvent::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_complete_event(
-                            &complete_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_complete = true;
-                    }
-                }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
+    /// 交易的所有事件分发完成后，分发组合出的新币事件
+    fn dispatch_new_tokens<H: EventHandler>(
+        &self,
+        tokens: Vec<NewToken>,
+        base_ctx: &EventContext,
+        handler: &H,
+    ) {
+        let filter = self.config.event_filter.as_ref().map(SharedEventFilter::load);
+        for token in tokens {
+            if let Some(filter) = &filter {
+                let enabled = if token.is_v2 { filter.create_v2 } else { filter.create };
+                if !enabled {
+                    continue;
                 }
-                return ControlFlow::Continue(());
             }
+            let ctx = EventContext {
+                elapsed: base_ctx.timestamp.elapsed(),
+                block_time: Some(token.timestamp),
+                ..base_ctx.clone()
+            };
+            handler.on_new_token(&token, &ctx);
+        }
+    }
 
-            if discriminator == CREATE_POOL_DISCRIMINATOR {
-                if !logged_create_pool {
-                    if let Ok(create_pool_event) = CreatePoolEvent::from_bytes(data) {
-                        let elapsed = std::time::Instant::now().duration_since(start_time);
-                        handler.on_create_pool_event(
-                            &create_pool_event,
-                            &EventContext { elapsed, ..base_ctx },
-                        );
-                        logged_create_pool = true;
-                    }
-                }
-                if all_logged(logged_create, logged_create_v2, logged_complete, logged_trade,
-                        logged_buy, logged_create_pool, logged_sell) {
-                    return ControlFlow::Break(());
-                }
+    /// 交易的所有事件分发完成后，交付成交结果并分发交易元数据
+    ///
+    /// `parsed_any` 表示交易中解析出了事件；这些事件全部被去重或过期过滤跳过时不分发。
+    fn dispatch_transaction<H: EventHandler>(
+        &self,
+        transaction: &TransactionUpdate,
+        events: &[PumpEvent],
+        parsed_any: bool,
+        base_ctx: &EventContext,
+        handler: &H,
+    ) {
+        if let Some(fills) = &self.fills {
+            fills.complete(transaction, events);
+        }
+        if !self.config.transaction_meta || (parsed_any && events.is_empty()) {
+            return;
+        }
+        let ctx = EventContext {
+            elapsed: base_ctx.timestamp.elapsed(),
+            block_time: events.first().map(PumpEvent::timestamp),
+            ..base_ctx.clone()
+        };
+        handler.on_transaction(transaction, events, &ctx);
+    }
+
+    /// 过滤过期事件并去重后，把单个事件分发给处理器，返回分发时使用的上下文，未分发时返回 None
+    ///
+    /// `decoded` 为事件解码完成的时间，仅在启用延迟剖析时提供。
+    #[inline]
+    fn dispatch<H: EventHandler>(
+        &self,
+        discriminator: &[u8],
+        event: &PumpEvent,
+        unknown_tail: Option<Arc<[u8]>>,
+        base_ctx: &EventContext,
+        decoded: Option<Instant>,
+        handler: &H,
+    ) -> Option<EventContext> {
+        if self.is_stale(base_ctx.slot) {
+            return None;
+        }
+        if !self.first_seen(&base_ctx.signature, discriminator) {
+            return None;
+        }
+        let ctx = EventContext {
+            elapsed: base_ctx.timestamp.elapsed(),
+            block_time: Some(event.timestamp()),
+            unknown_tail,
+            ..base_ctx.clone()
+        };
+        if let Some(lag) = self
+            .status
+            .on_dispatch(ctx.slot, event.timestamp(), self.config.lag_threshold)
+        {
+            handler.on_lag(&lag);
+        }
+        // 被静音的事件照常记为已分发，只跳过处理器回调
+        if let Some(filter) = &self.config.event_filter {
+            if !filter.matches(event) {
+                return Some(ctx);
+            }
+        }
+        match self.profiler.as_ref().zip(decoded) {
+            Some((profiler, decoded)) => {
+                let called = Instant::now();
+                self.call_handler(handler, event, &ctx);
+                profiler.record_dispatch(decoded, called, Instant::now());
             }
+            None => self.call_handler(handler, event, &ctx),
+        }
+        Some(ctx)
+    }
 
-            ControlFlow::Continue(())
-        });
-        Ok(())
+    /// 调用处理器；配置了死信队列时捕获 panic，把事件写入死信队列后继续处理
+    #[inline]
+    fn call_handler<H: EventHandler>(&self, handler: &H, event: &PumpEvent, ctx: &EventContext) {
+        let Some(sink) = &self.dead_letters else {
+            dispatch_event(handler, event, ctx);
+            return;
+        };
+        if let Err(panic) = catch_unwind(AssertUnwindSafe(|| dispatch_event(handler, event, ctx))) {
+            error!("处理器处理交易 {} 的事件时 panic，已写入死信队列", ctx.signature);
+            deliver(sink.as_ref(), DeadLetter::handler_panic(event, panic.as_ref(), ctx));
+        }
+    }
+}
+
+/// 订阅触及任一指定程序的交易的请求
+fn program_request(program_ids: Vec<String>) -> SubscribeRequest {
+    SubscribeRequest {
+        transactions: HashMap::from([(
+            "client".to_string(),
+            SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(false),
+                signature: None,
+                account_include: program_ids,
+                account_exclude: vec![],
+                account_required: vec![],
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+/// 属于 `owner` 且以 `discriminator` 开头的账户的过滤器
+fn owner_accounts(owner: Pubkey, discriminator: &[u8]) -> SubscribeRequestFilterAccounts {
+    SubscribeRequestFilterAccounts {
+        account: vec![],
+        owner: vec![owner.to_string()],
+        filters: vec![SubscribeRequestFilterAccountsFilter {
+            filter: Some(AccountsFilter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+                offset: 0,
+                data: Some(MemcmpData::Bytes(discriminator.to_vec())),
+            })),
+        }],
+        nonempty_txn_signature: None,
+    }
+}
+
+/// 取出 Pump / PumpAmm 通过 `emit_cpi!` 自调用发出的事件指令数据，按执行顺序排列
+///
+/// 内部指令的程序下标按消息的账户列表加上地址查找表加载的可写、只读地址解析。
+fn cpi_event_data(
+    message: Option<&TransactionMessage>,
+    meta: &TransactionStatusMeta,
+) -> Vec<Vec<u8>> {
+    let static_keys = message.map(|message| message.account_keys.as_slice()).unwrap_or_default();
+    let is_pump_program = |index: u32| {
+        static_keys
+            .iter()
+            .chain(&meta.loaded_writable_addresses)
+            .chain(&meta.loaded_readonly_addresses)
+            .nth(index as usize)
+            .is_some_and(|key| {
+                key.as_slice() == PUMP_PROGRAM_ID.as_ref()
+                    || key.as_slice() == PUMP_AMM_PROGRAM_ID.as_ref()
+            })
+    };
+    meta.inner_instructions
+        .iter()
+        .flat_map(|inner| &inner.instructions)
+        .filter(|ix| ix.data.starts_with(EVENT_IX_TAG) && is_pump_program(ix.program_id_index))
+        .map(|ix| ix.data.clone())
+        .collect()
+}
+
+/// 等待并行流水线的下一批结果，未启用或没有在途交易时永远挂起
+async fn next_parsed(pipeline: &mut Option<ParsePipeline>) -> Option<Vec<Parsed>> {
+    if let Some(pipeline) = pipeline {
+        if let Some(batch) = pipeline.next_ready().await {
+            return Some(batch);
+        }
+    }
+    std::future::pending().await
+}
+
+/// 等待观察列表或池跟踪器变化，未启用时永远挂起
+async fn watch_changed<T: Clone>(rx: &mut Option<watch::Receiver<T>>) -> Option<T> {
+    if let Some(rx) = rx {
+        if rx.changed().await.is_ok() {
+            return Some(rx.borrow_and_update().clone());
+        }
+    }
+    std::future::pending().await
+}
+
+/// 只转发指定池事件的处理器包装，供 `subscribe_pool` 使用
+struct PoolEventHandler<H> {
+    pool: Pubkey,
+    inner: H,
+}
+
+impl<H: EventHandler> EventHandler for PoolEventHandler<H> {
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        if event.pool == self.pool {
+            self.inner.on_buy_event(event, ctx);
+        }
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        if event.pool == self.pool {
+            self.inner.on_sell_event(event, ctx);
+        }
     }
-}
\ No newline at end of file
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, ctx: &EventContext) {
+        if event.pool == self.pool {
+            self.inner.on_create_pool_event(event, ctx);
+        }
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, ctx: &EventContext) {
+        if event.pool == self.pool {
+            self.inner.on_migrate_event(event, ctx);
+        }
+    }
+
+    fn on_deposit_event(&self, event: &DepositEvent, ctx: &EventContext) {
+        if event.pool == self.pool {
+            self.inner.on_deposit_event(event, ctx);
+        }
+    }
+
+    fn on_withdraw_event(&self, event: &WithdrawEvent, ctx: &EventContext) {
+        if event.pool == self.pool {
+            self.inner.on_withdraw_event(event, ctx);
+        }
+    }
+
+    fn on_event(&self, event: &PumpEvent, ctx: &EventContext) {
+        if event.pool() == Some(self.pool) {
+            self.inner.on_event(event, ctx);
+        }
+    }
+
+    fn dispatch(&self, event: &PumpEvent, ctx: &EventContext) {
+        if event.pool() == Some(self.pool) {
+            self.inner.dispatch(event, ctx);
+        }
+    }
+
+    fn on_new_token(&self, token: &NewToken, ctx: &EventContext) {
+        if pda::canonical_pump_pool(&token.mint) == self.pool {
+            self.inner.on_new_token(token, ctx);
+        }
+    }
+
+    fn on_slot_complete(&self, slot: u64, events: &[(PumpEvent, EventContext)]) {
+        let events: Vec<_> = events
+            .iter()
+            .filter(|(event, _)| event.pool() == Some(self.pool))
+            .cloned()
+            .collect();
+        if !events.is_empty() {
+            self.inner.on_slot_complete(slot, &events);
+        }
+    }
+
+    fn on_transaction(&self, tx: &TransactionUpdate, events: &[PumpEvent], ctx: &EventContext) {
+        let events: Vec<_> =
+            events.iter().filter(|event| event.pool() == Some(self.pool)).cloned().collect();
+        if !events.is_empty() {
+            self.inner.on_transaction(tx, &events, ctx);
+        }
+    }
+
+    fn on_bonding_curve_update(
+        &self,
+        mint: &Pubkey,
+        curve: &BondingCurveAccount,
+        ctx: &AccountContext,
+    ) {
+        self.inner.on_bonding_curve_update(mint, curve, ctx);
+    }
+
+    fn on_pool_reserves(&self, reserves: &PoolReserves, ctx: &AccountContext) {
+        self.inner.on_pool_reserves(reserves, ctx);
+    }
+
+    fn on_account_update(&self, account: &AccountUpdate, ctx: &AccountContext) {
+        self.inner.on_account_update(account, ctx);
+    }
+
+    fn on_program_account(&self, account: &ProgramAccount, ctx: &AccountContext) {
+        self.inner.on_program_account(account, ctx);
+    }
+
+    fn on_slot_update(&self, update: &SlotUpdate) {
+        self.inner.on_slot_update(update);
+    }
+
+    fn on_block_meta(&self, meta: &BlockMetaUpdate) {
+        self.inner.on_block_meta(meta);
+    }
+
+    fn on_entry(&self, entry: &EntryUpdate) {
+        self.inner.on_entry(entry);
+    }
+
+    fn on_lag(&self, lag: &StreamLag) {
+        self.inner.on_lag(lag);
+    }
+
+    fn on_latency_report(&self, report: &LatencyReport) {
+        self.inner.on_latency_report(report);
+    }
+
+    fn on_stats(&self, stats: &StreamStats) {
+        self.inner.on_stats(stats);
+    }
+
+    fn on_raw_update(&self, update: &SubscribeUpdate) {
+        self.inner.on_raw_update(update);
+    }
+}
@@ -1,5 +1,17 @@
+use super::{
+    pools::PoolReserves,
+    profiler::LatencyReport,
+    stats::StreamStats,
+    status::StreamLag,
+    updates::{AccountUpdate, BlockMetaUpdate, EntryUpdate, SlotUpdate, TransactionUpdate},
+};
 use crate::models::*;
-use solana_sdk::signature::Signature;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::sync::{
+    atomic::{AtomicU16, Ordering},
+    Arc,
+};
+use yellowstone_grpc_proto::geyser::SubscribeUpdate;
 
 /// 事件上下文，包含事件发生的上下文信息
 #[derive(Clone, Debug)]
@@ -14,6 +26,38 @@ pub struct EventContext {
     pub timestamp: std::time::Instant,
     /// 从开始处理到当前事件的耗时
     pub elapsed: std::time::Duration,
+    /// 收到交易时的系统时间，可序列化并跨进程比较
+    pub received_at: std::time::SystemTime,
+    /// 链上区块时间（Unix 秒），取自事件中记录的链上时钟，不可用时为 None
+    pub block_time: Option<i64>,
+    /// 命中的订阅过滤器名称（对应 `SubscribeRequest` 中的过滤器键）
+    pub filters: Arc<[String]>,
+    /// 宽容解码模式下事件数据中未被模型消费的末尾字节，通常意味着程序升级新增了字段
+    pub unknown_tail: Option<Arc<[u8]>>,
+}
+
+impl EventContext {
+    /// 判断事件所在交易是否命中了指定名称的过滤器
+    pub fn matched(&self, filter: &str) -> bool {
+        self.filters.iter().any(|f| f == filter)
+    }
+}
+
+/// 账户更新上下文
+#[derive(Clone, Debug)]
+pub struct AccountContext {
+    /// 区块槽位
+    pub slot: u64,
+    /// 账户地址
+    pub pubkey: Pubkey,
+    /// 账户写入版本，可用于判断同一槽位内更新的先后
+    pub write_version: u64,
+    /// 引起该更新的交易签名（如果有）
+    pub signature: Option<Signature>,
+    /// 收到更新时的系统时间
+    pub received_at: std::time::SystemTime,
+    /// 命中的订阅过滤器名称
+    pub filters: Arc<[String]>,
 }
 
 /// 事件处理器trait
@@ -41,6 +85,169 @@ pub trait EventHandler: Send + Sync {
 
     /// 处理 CreatePoolEvent
     fn on_create_pool_event(&self, _event: &CreatePoolEvent, _ctx: &EventContext) {}
+
+    /// 处理 SetParamsEvent（Pump 全局参数修改）
+    fn on_set_params_event(&self, _event: &SetParamsEvent, _ctx: &EventContext) {}
+
+    /// 处理 UpdateGlobalAuthorityEvent（Pump 全局管理员变更）
+    fn on_update_global_authority_event(
+        &self,
+        _event: &UpdateGlobalAuthorityEvent,
+        _ctx: &EventContext,
+    ) {
+    }
+
+    /// 处理 SetCreatorEvent
+    fn on_set_creator_event(&self, _event: &SetCreatorEvent, _ctx: &EventContext) {}
+
+    /// 处理 SetMetaplexCreatorEvent
+    fn on_set_metaplex_creator_event(
+        &self,
+        _event: &SetMetaplexCreatorEvent,
+        _ctx: &EventContext,
+    ) {
+    }
+
+    /// 处理 DepositEvent（PumpAmm 添加流动性）
+    fn on_deposit_event(&self, _event: &DepositEvent, _ctx: &EventContext) {}
+
+    /// 处理 WithdrawEvent（PumpAmm 移除流动性）
+    fn on_withdraw_event(&self, _event: &WithdrawEvent, _ctx: &EventContext) {}
+
+    /// 处理 CollectCreatorFeeEvent（Pump 创建者领取手续费）
+    fn on_collect_creator_fee_event(
+        &self,
+        _event: &CollectCreatorFeeEvent,
+        _ctx: &EventContext,
+    ) {
+    }
+
+    /// 处理 CollectCoinCreatorFeeEvent（PumpAmm 代币创建者领取手续费）
+    fn on_collect_coin_creator_fee_event(
+        &self,
+        _event: &CollectCoinCreatorFeeEvent,
+        _ctx: &EventContext,
+    ) {
+    }
+
+    /// 处理 MigrateEvent（bonding curve 迁移到 PumpAmm）
+    fn on_migrate_event(&self, _event: &MigrateEvent, _ctx: &EventContext) {}
+
+    /// 处理任意事件
+    ///
+    /// 每个事件在对应的逐类型回调之后调用一次，适合不区分事件类型的通用处理器
+    /// （例如统一序列化或转发），只需实现这一个方法。
+    fn on_event(&self, _event: &PumpEvent, _ctx: &EventContext) {}
+
+    /// 分发一个事件：先调用对应的逐类型回调，再调用 `on_event`
+    ///
+    /// SDK 对每个事件只调用这一个入口，普通处理器不需要实现。包装其他处理器的组合处理器
+    /// （例如 `HandlerRegistry`）可以覆盖它，把同一事件的两次回调作为一个整体转发。
+    fn dispatch(&self, event: &PumpEvent, ctx: &EventContext) {
+        dispatch_typed(self, event, ctx);
+        self.on_event(event, ctx);
+    }
+
+    /// 槽位的全部交易处理完成后，整批交付该槽位的事件（需要 `Config::with_slot_batching`）
+    ///
+    /// 事件按交易在区块中的顺序排列，只交付包含事件的槽位。事件在此之前已经逐个分发过。
+    fn on_slot_complete(&self, _slot: u64, _events: &[(PumpEvent, EventContext)]) {}
+
+    /// 处理新币上线的组合事件
+    ///
+    /// 在创建交易的所有事件分发完成后调用，包含创建者的 dev buy 和交易结束时的 bonding curve 状态。
+    /// 与 `on_create_event` / `on_create_v2_event` 一样经过去重，同一代币只分发一次。
+    fn on_new_token(&self, _token: &NewToken, _ctx: &EventContext) {}
+
+    /// 处理交易的完整元数据（需要 `Config::with_transaction_meta`）
+    ///
+    /// 在交易的所有事件（包括 `on_new_token`）分发完成后调用，`events` 为本交易中实际分发的事件。
+    /// 交易的事件全部被去重或过期过滤跳过时不调用；没有事件的交易（例如执行失败的交易）照常交付。
+    fn on_transaction(
+        &self,
+        _tx: &TransactionUpdate,
+        _events: &[PumpEvent],
+        _ctx: &EventContext,
+    ) {
+    }
+
+    /// 处理观察列表中代币的 bonding curve 账户更新
+    fn on_bonding_curve_update(
+        &self,
+        _mint: &Pubkey,
+        _curve: &BondingCurveAccount,
+        _ctx: &AccountContext,
+    ) {
+    }
+
+    /// 处理池跟踪器中 PumpAmm 池的储备变化
+    fn on_pool_reserves(&self, _reserves: &PoolReserves, _ctx: &AccountContext) {}
+
+    /// 处理账户更新（需要在订阅请求中配置 `accounts` 过滤器）
+    fn on_account_update(&self, _account: &AccountUpdate, _ctx: &AccountContext) {}
+
+    /// 处理解码后的 Pump / PumpAmm 程序账户更新
+    ///
+    /// 属于 Pump / PumpAmm 且 discriminator 可识别的账户在 `on_account_update` 之后调用，
+    /// 配合 `GrpcClient::subscribe_accounts` 可以持续获得所有 bonding curve 和池的最新状态。
+    fn on_program_account(&self, _account: &ProgramAccount, _ctx: &AccountContext) {}
+
+    /// 处理槽位状态更新（需要在订阅请求中配置 `slots` 过滤器）
+    fn on_slot_update(&self, _update: &SlotUpdate) {}
+
+    /// 处理区块元数据更新（需要在订阅请求中配置 `blocks_meta` 过滤器）
+    fn on_block_meta(&self, _meta: &BlockMetaUpdate) {}
+
+    /// 处理 entry 更新（需要在订阅请求中配置 `entry` 过滤器）
+    fn on_entry(&self, _entry: &EntryUpdate) {}
+
+    /// 事件流延迟超过 `Config::with_lag_threshold` 设置的阈值时调用
+    ///
+    /// 延迟恢复到阈值以下之前只调用一次，当前延迟也可以通过 `GrpcClient::status` 查询。
+    fn on_lag(&self, _lag: &StreamLag) {}
+
+    /// 延迟剖析器的统计周期结束时调用（需要 `GrpcClient::with_latency_profiler`）
+    fn on_latency_report(&self, _report: &LatencyReport) {}
+
+    /// 订阅流统计周期结束时调用（需要 `Config::with_stats_interval`）
+    fn on_stats(&self, _stats: &StreamStats) {}
+
+    /// 处理原始的 `SubscribeUpdate`
+    ///
+    /// 在 SDK 解析之前对收到的每条更新调用，可以用来访问 SDK 尚未建模的更新类型。
+    fn on_raw_update(&self, _update: &SubscribeUpdate) {}
+}
+
+/// 把事件分发到处理器对应的回调方法
+#[inline]
+pub(crate) fn dispatch_event<H: EventHandler + ?Sized>(
+    handler: &H,
+    event: &PumpEvent,
+    ctx: &EventContext,
+) {
+    handler.dispatch(event, ctx);
+}
+
+/// 调用事件对应的逐类型回调
+fn dispatch_typed<H: EventHandler + ?Sized>(handler: &H, event: &PumpEvent, ctx: &EventContext) {
+    match event {
+        PumpEvent::Create(e) => handler.on_create_event(e, ctx),
+        PumpEvent::CreateV2(e) => handler.on_create_v2_event(e, ctx),
+        PumpEvent::Complete(e) => handler.on_complete_event(e, ctx),
+        PumpEvent::Trade(e) => handler.on_trade_event(e, ctx),
+        PumpEvent::Buy(e) => handler.on_buy_event(e, ctx),
+        PumpEvent::Sell(e) => handler.on_sell_event(e, ctx),
+        PumpEvent::CreatePool(e) => handler.on_create_pool_event(e, ctx),
+        PumpEvent::SetParams(e) => handler.on_set_params_event(e, ctx),
+        PumpEvent::UpdateGlobalAuthority(e) => handler.on_update_global_authority_event(e, ctx),
+        PumpEvent::SetCreator(e) => handler.on_set_creator_event(e, ctx),
+        PumpEvent::SetMetaplexCreator(e) => handler.on_set_metaplex_creator_event(e, ctx),
+        PumpEvent::Deposit(e) => handler.on_deposit_event(e, ctx),
+        PumpEvent::Withdraw(e) => handler.on_withdraw_event(e, ctx),
+        PumpEvent::CollectCreatorFee(e) => handler.on_collect_creator_fee_event(e, ctx),
+        PumpEvent::CollectCoinCreatorFee(e) => handler.on_collect_coin_creator_fee_event(e, ctx),
+        PumpEvent::Migrate(e) => handler.on_migrate_event(e, ctx),
+    }
 }
 
 /// 默认的事件处理器实现（什么都不做）
@@ -65,6 +272,24 @@ pub struct EventFilter {
     pub sell: bool,
     /// 是否打印 CreatePoolEvent
     pub create_pool: bool,
+    /// 是否打印 SetParamsEvent
+    pub set_params: bool,
+    /// 是否打印 UpdateGlobalAuthorityEvent
+    pub update_global_authority: bool,
+    /// 是否打印 SetCreatorEvent
+    pub set_creator: bool,
+    /// 是否打印 SetMetaplexCreatorEvent
+    pub set_metaplex_creator: bool,
+    /// 是否打印 DepositEvent
+    pub deposit: bool,
+    /// 是否打印 WithdrawEvent
+    pub withdraw: bool,
+    /// 是否打印 CollectCreatorFeeEvent
+    pub collect_creator_fee: bool,
+    /// 是否打印 CollectCoinCreatorFeeEvent
+    pub collect_coin_creator_fee: bool,
+    /// 是否打印 MigrateEvent
+    pub migrate: bool,
 }
 
 impl EventFilter {
@@ -78,6 +303,15 @@ impl EventFilter {
             buy: true,
             sell: true,
             create_pool: true,
+            set_params: true,
+            update_global_authority: true,
+            set_creator: true,
+            set_metaplex_creator: true,
+            deposit: true,
+            withdraw: true,
+            collect_creator_fee: true,
+            collect_coin_creator_fee: true,
+            migrate: true,
         }
     }
 
@@ -91,10 +325,19 @@ impl EventFilter {
             buy: false,
             sell: false,
             create_pool: false,
+            set_params: false,
+            update_global_authority: false,
+            set_creator: false,
+            set_metaplex_creator: false,
+            deposit: false,
+            withdraw: false,
+            collect_creator_fee: false,
+            collect_coin_creator_fee: false,
+            migrate: false,
         }
     }
 
-    /// 只打印 Pump 相关事件（Create, CreateV2, Complete, Trade）
+    /// 只打印 Pump 相关事件（Create, CreateV2, Complete, Migrate, Trade, CollectCreatorFee 以及 SetParams 等管理类事件）
     pub fn pump_only() -> Self {
         Self {
             create: true,
@@ -104,10 +347,19 @@ impl EventFilter {
             buy: false,
             sell: false,
             create_pool: false,
+            set_params: true,
+            update_global_authority: true,
+            set_creator: true,
+            set_metaplex_creator: true,
+            deposit: false,
+            withdraw: false,
+            collect_creator_fee: true,
+            collect_coin_creator_fee: false,
+            migrate: true,
         }
     }
 
-    /// 只打印 PumpAmm 相关事件（Buy, Sell, CreatePool）
+    /// 只打印 PumpAmm 相关事件（Buy, Sell, CreatePool, Deposit, Withdraw, CollectCoinCreatorFee）
     pub fn pumpamm_only() -> Self {
         Self {
             create: false,
@@ -117,6 +369,85 @@ impl EventFilter {
             buy: true,
             sell: true,
             create_pool: true,
+            set_params: false,
+            update_global_authority: false,
+            set_creator: false,
+            set_metaplex_creator: false,
+            deposit: true,
+            withdraw: true,
+            collect_creator_fee: false,
+            collect_coin_creator_fee: true,
+            migrate: false,
+        }
+    }
+}
+
+impl EventFilter {
+    /// 事件的类型是否启用
+    pub fn matches(&self, event: &PumpEvent) -> bool {
+        match event {
+            PumpEvent::Create(_) => self.create,
+            PumpEvent::CreateV2(_) => self.create_v2,
+            PumpEvent::Complete(_) => self.complete,
+            PumpEvent::Trade(_) => self.trade,
+            PumpEvent::Buy(_) => self.buy,
+            PumpEvent::Sell(_) => self.sell,
+            PumpEvent::CreatePool(_) => self.create_pool,
+            PumpEvent::SetParams(_) => self.set_params,
+            PumpEvent::UpdateGlobalAuthority(_) => self.update_global_authority,
+            PumpEvent::SetCreator(_) => self.set_creator,
+            PumpEvent::SetMetaplexCreator(_) => self.set_metaplex_creator,
+            PumpEvent::Deposit(_) => self.deposit,
+            PumpEvent::Withdraw(_) => self.withdraw,
+            PumpEvent::CollectCreatorFee(_) => self.collect_creator_fee,
+            PumpEvent::CollectCoinCreatorFee(_) => self.collect_coin_creator_fee,
+            PumpEvent::Migrate(_) => self.migrate,
+        }
+    }
+
+    fn to_bits(&self) -> u16 {
+        [
+            self.create,
+            self.create_v2,
+            self.complete,
+            self.trade,
+            self.buy,
+            self.sell,
+            self.create_pool,
+            self.set_params,
+            self.update_global_authority,
+            self.set_creator,
+            self.set_metaplex_creator,
+            self.deposit,
+            self.withdraw,
+            self.collect_creator_fee,
+            self.collect_coin_creator_fee,
+            self.migrate,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, enabled)| bits | (u16::from(*enabled) << i))
+    }
+
+    fn from_bits(bits: u16) -> Self {
+        let enabled = |i: u16| bits & (1 << i) != 0;
+        Self {
+            create: enabled(0),
+            create_v2: enabled(1),
+            complete: enabled(2),
+            trade: enabled(3),
+            buy: enabled(4),
+            sell: enabled(5),
+            create_pool: enabled(6),
+            set_params: enabled(7),
+            update_global_authority: enabled(8),
+            set_creator: enabled(9),
+            set_metaplex_creator: enabled(10),
+            deposit: enabled(11),
+            withdraw: enabled(12),
+            collect_creator_fee: enabled(13),
+            collect_coin_creator_fee: enabled(14),
+            migrate: enabled(15),
         }
     }
 }
@@ -127,6 +458,63 @@ impl Default for EventFilter {
     }
 }
 
+/// 可在运行中原子替换的事件过滤器
+///
+/// 克隆后的实例共享同一份配置，在任意克隆上调用 [`store`](Self::store) 后，
+/// 所有使用者的下一个事件即按新配置过滤，不需要重启订阅。读取是一次原子加载，可以放在热路径上。
+#[derive(Clone)]
+pub struct SharedEventFilter {
+    bits: Arc<AtomicU16>,
+}
+
+impl SharedEventFilter {
+    /// 以初始配置创建
+    pub fn new(filter: EventFilter) -> Self {
+        Self {
+            bits: Arc::new(AtomicU16::new(filter.to_bits())),
+        }
+    }
+
+    /// 当前配置
+    pub fn load(&self) -> EventFilter {
+        EventFilter::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+
+    /// 替换为新的配置
+    pub fn store(&self, filter: EventFilter) {
+        self.bits.store(filter.to_bits(), Ordering::Relaxed);
+    }
+
+    /// 在当前配置的基础上修改，例如 `filter.update(|f| f.trade = false)` 静音 TradeEvent
+    pub fn update(&self, mut f: impl FnMut(&mut EventFilter)) {
+        let _ = self
+            .bits
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                let mut filter = EventFilter::from_bits(bits);
+                f(&mut filter);
+                Some(filter.to_bits())
+            });
+    }
+
+    /// 事件的类型在当前配置中是否启用
+    #[inline]
+    pub fn matches(&self, event: &PumpEvent) -> bool {
+        self.load().matches(event)
+    }
+}
+
+impl Default for SharedEventFilter {
+    fn default() -> Self {
+        Self::new(EventFilter::default())
+    }
+}
+
+impl std::fmt::Debug for SharedEventFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedEventFilter").field(&self.load()).finish()
+    }
+}
+
 /// 日志事件处理器
 /// 
 /// 一个简单的事件处理器实现，将所有事件记录到日志中。
@@ -183,33 +571,114 @@ impl EventHandler for LoggingEventHandler {
             ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
         );
     }
+
+    fn on_set_params_event(&self, event: &SetParamsEvent, ctx: &EventContext) {
+        log::info!(
+            "SetParamsEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_update_global_authority_event(
+        &self,
+        event: &UpdateGlobalAuthorityEvent,
+        ctx: &EventContext,
+    ) {
+        log::info!(
+            "UpdateGlobalAuthorityEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_set_creator_event(&self, event: &SetCreatorEvent, ctx: &EventContext) {
+        log::info!(
+            "SetCreatorEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_set_metaplex_creator_event(&self, event: &SetMetaplexCreatorEvent, ctx: &EventContext) {
+        log::info!(
+            "SetMetaplexCreatorEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_deposit_event(&self, event: &DepositEvent, ctx: &EventContext) {
+        log::info!(
+            "DepositEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_withdraw_event(&self, event: &WithdrawEvent, ctx: &EventContext) {
+        log::info!(
+            "WithdrawEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_collect_creator_fee_event(&self, event: &CollectCreatorFeeEvent, ctx: &EventContext) {
+        log::info!(
+            "CollectCreatorFeeEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_collect_coin_creator_fee_event(
+        &self,
+        event: &CollectCoinCreatorFeeEvent,
+        ctx: &EventContext,
+    ) {
+        log::info!(
+            "CollectCoinCreatorFeeEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, ctx: &EventContext) {
+        log::info!(
+            "MigrateEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+            ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+        );
+    }
 }
 
 /// 可过滤的日志事件处理器
 /// 
-/// 根据 `EventFilter` 配置选择性打印事件
+/// 根据 `EventFilter` 配置选择性打印事件，配置可以通过 [`filter`](Self::filter) 在运行中替换
 #[derive(Clone)]
 pub struct FilteredLoggingEventHandler {
-    filter: EventFilter,
+    filter: SharedEventFilter,
 }
 
 impl FilteredLoggingEventHandler {
     /// 创建新的过滤器日志事件处理器
     pub fn new(filter: EventFilter) -> Self {
+        Self::with_shared(SharedEventFilter::new(filter))
+    }
+
+    /// 使用共享的过滤器创建，例如与 `Config::with_event_filter` 使用同一份配置
+    pub fn with_shared(filter: SharedEventFilter) -> Self {
         Self { filter }
     }
 
+    /// 处理器使用的过滤器，修改后立即生效
+    pub fn filter(&self) -> &SharedEventFilter {
+        &self.filter
+    }
+}
+
+impl Default for FilteredLoggingEventHandler {
     /// 使用默认过滤器（所有事件都启用）创建处理器
-    pub fn default() -> Self {
-        Self {
-            filter: EventFilter::default(),
-        }
+    fn default() -> Self {
+        Self::new(EventFilter::default())
     }
 }
 
 impl EventHandler for FilteredLoggingEventHandler {
     fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
-        if self.filter.create {
+        if self.filter.load().create {
             log::info!(
                 "CreateEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
@@ -218,7 +687,7 @@ impl EventHandler for FilteredLoggingEventHandler {
     }
 
     fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
-        if self.filter.create_v2 {
+        if self.filter.load().create_v2 {
             log::info!(
                 "CreateV2Event {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
@@ -227,7 +696,7 @@ impl EventHandler for FilteredLoggingEventHandler {
     }
 
     fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
-        if self.filter.complete {
+        if self.filter.load().complete {
             log::info!(
                 "CompleteEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
@@ -236,7 +705,7 @@ impl EventHandler for FilteredLoggingEventHandler {
     }
 
     fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
-        if self.filter.trade {
+        if self.filter.load().trade {
             log::info!(
                 "TradeEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
@@ -245,7 +714,7 @@ impl EventHandler for FilteredLoggingEventHandler {
     }
 
     fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
-        if self.filter.buy {
+        if self.filter.load().buy {
             log::info!(
                 "BuyEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
@@ -254,7 +723,7 @@ impl EventHandler for FilteredLoggingEventHandler {
     }
 
     fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
-        if self.filter.sell {
+        if self.filter.load().sell {
             log::info!(
                 "SellEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
@@ -263,11 +732,100 @@ impl EventHandler for FilteredLoggingEventHandler {
     }
 
     fn on_create_pool_event(&self, event: &CreatePoolEvent, ctx: &EventContext) {
-        if self.filter.create_pool {
+        if self.filter.load().create_pool {
             log::info!(
                 "CreatePoolEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
                 ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
             );
         }
     }
+
+    fn on_set_params_event(&self, event: &SetParamsEvent, ctx: &EventContext) {
+        if self.filter.load().set_params {
+            log::info!(
+                "SetParamsEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_update_global_authority_event(
+        &self,
+        event: &UpdateGlobalAuthorityEvent,
+        ctx: &EventContext,
+    ) {
+        if self.filter.load().update_global_authority {
+            log::info!(
+                "UpdateGlobalAuthorityEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_set_creator_event(&self, event: &SetCreatorEvent, ctx: &EventContext) {
+        if self.filter.load().set_creator {
+            log::info!(
+                "SetCreatorEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_set_metaplex_creator_event(&self, event: &SetMetaplexCreatorEvent, ctx: &EventContext) {
+        if self.filter.load().set_metaplex_creator {
+            log::info!(
+                "SetMetaplexCreatorEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_deposit_event(&self, event: &DepositEvent, ctx: &EventContext) {
+        if self.filter.load().deposit {
+            log::info!(
+                "DepositEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_withdraw_event(&self, event: &WithdrawEvent, ctx: &EventContext) {
+        if self.filter.load().withdraw {
+            log::info!(
+                "WithdrawEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_collect_creator_fee_event(&self, event: &CollectCreatorFeeEvent, ctx: &EventContext) {
+        if self.filter.load().collect_creator_fee {
+            log::info!(
+                "CollectCreatorFeeEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_collect_coin_creator_fee_event(
+        &self,
+        event: &CollectCoinCreatorFeeEvent,
+        ctx: &EventContext,
+    ) {
+        if self.filter.load().collect_coin_creator_fee {
+            log::info!(
+                "CollectCoinCreatorFeeEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, ctx: &EventContext) {
+        if self.filter.load().migrate {
+            log::info!(
+                "MigrateEvent {{ elapsed:{:?}, slot:{}, tx_index:{}, signature:{}, event:{:?} }}",
+                ctx.elapsed, ctx.slot, ctx.tx_index, ctx.signature, event
+            );
+        }
+    }
 }
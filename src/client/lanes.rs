@@ -0,0 +1,485 @@
+use log::{debug, error};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::VecDeque,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex, MutexGuard,
+    },
+    thread,
+};
+use yellowstone_grpc_proto::geyser::SubscribeUpdate;
+
+use super::{
+    handler::{AccountContext, EventContext, EventFilter, EventHandler},
+    pools::PoolReserves,
+    profiler::LatencyReport,
+    stats::StreamStats,
+    status::StreamLag,
+    updates::{AccountUpdate, BlockMetaUpdate, EntryUpdate, SlotUpdate, TransactionUpdate},
+};
+use crate::{
+    error::{Error, Result},
+    models::*,
+};
+
+/// 交给通道工作线程执行的一次回调
+type Job<H> = Box<dyn FnOnce(&H) + Send>;
+
+/// 通道队列已满时的取舍策略
+#[derive(Clone, Debug, Default)]
+pub enum ShedPolicy {
+    /// 丢弃新到的回调，已入队的回调不受影响
+    #[default]
+    DropNewest,
+    /// 按事件类型取舍：过滤器中启用的事件类型从不丢弃，队列已满时照常入队（可以超出队列长度）；
+    /// 其余事件先丢弃队列中最早入队的回调为新回调腾出空间，队列中只剩受保护的回调时才丢弃新回调
+    Priority(EventFilter),
+}
+
+impl ShedPolicy {
+    /// 保护 Create / CreateV2 / Complete / Migrate 事件（以及随创建事件的 `on_new_token`），
+    /// 过载时优先丢弃最早入队的 Trade / Buy / Sell 等事件
+    pub fn priority() -> Self {
+        ShedPolicy::Priority(EventFilter {
+            create: true,
+            create_v2: true,
+            complete: true,
+            migrate: true,
+            ..EventFilter::none()
+        })
+    }
+}
+
+/// 单个通道的队列指标
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LaneMetrics {
+    /// 当前排队的回调数
+    pub queued: usize,
+    /// 队列已满时丢弃的新回调数
+    pub dropped: u64,
+    /// 为新回调腾出空间而丢弃的已入队回调数（`ShedPolicy::Priority`）
+    pub shed: u64,
+    /// 队列已满时仍然入队的受保护回调数（`ShedPolicy::Priority`）
+    pub overflowed: u64,
+}
+
+/// 队列中的一个回调
+struct Queued<H> {
+    /// 是否受取舍策略保护
+    protected: bool,
+    job: Job<H>,
+}
+
+struct QueueState<H> {
+    jobs: VecDeque<Queued<H>>,
+    /// 所有 `EventLanes` 克隆都已被丢弃
+    closed: bool,
+}
+
+/// 通道的有界队列，由分发线程写入、工作线程读取
+struct LaneQueue<H> {
+    state: Mutex<QueueState<H>>,
+    ready: Condvar,
+    capacity: usize,
+    policy: ShedPolicy,
+    dropped: AtomicU64,
+    shed: AtomicU64,
+    overflowed: AtomicU64,
+}
+
+impl<H> LaneQueue<H> {
+    fn new(capacity: usize, policy: ShedPolicy) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                jobs: VecDeque::new(),
+                closed: false,
+            }),
+            ready: Condvar::new(),
+            capacity: capacity.max(1),
+            policy,
+            dropped: AtomicU64::new(0),
+            shed: AtomicU64::new(0),
+            overflowed: AtomicU64::new(0),
+        }
+    }
+
+    /// 是否受取舍策略保护，`selected` 为事件在过滤器中对应的开关
+    fn protects(&self, selected: impl Fn(&EventFilter) -> bool) -> bool {
+        match &self.policy {
+            ShedPolicy::DropNewest => false,
+            ShedPolicy::Priority(protected) => selected(protected),
+        }
+    }
+
+    fn push(&self, index: usize, job: Job<H>, protected: bool) {
+        let mut state = self.lock();
+        // 被挤出的回调在释放锁之后再丢弃
+        let mut evicted = None;
+        if state.jobs.len() >= self.capacity {
+            let oldest = match self.policy {
+                ShedPolicy::DropNewest => None,
+                ShedPolicy::Priority(_) => state.jobs.iter().position(|queued| !queued.protected),
+            };
+            match oldest {
+                Some(position) => {
+                    evicted = state.jobs.remove(position);
+                    self.note_discard(index, &self.shed);
+                }
+                None if protected => {
+                    self.overflowed.fetch_add(1, Ordering::Relaxed);
+                }
+                None => {
+                    drop(state);
+                    self.note_discard(index, &self.dropped);
+                    return;
+                }
+            }
+        }
+        state.jobs.push_back(Queued { protected, job });
+        drop(state);
+        drop(evicted);
+        self.ready.notify_one();
+    }
+
+    fn note_discard(&self, index: usize, counter: &AtomicU64) {
+        let first = self.dropped.load(Ordering::Relaxed) + self.shed.load(Ordering::Relaxed) == 0;
+        counter.fetch_add(1, Ordering::Relaxed);
+        if first {
+            debug!("事件通道 {} 的队列已满，开始丢弃回调", index);
+        }
+    }
+
+    /// 取出下一个回调，队列已关闭且为空时返回 None
+    fn pop(&self) -> Option<Job<H>> {
+        let mut state = self.lock();
+        loop {
+            if let Some(queued) = state.jobs.pop_front() {
+                return Some(queued.job);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.ready.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.ready.notify_all();
+    }
+
+    fn metrics(&self) -> LaneMetrics {
+        LaneMetrics {
+            queued: self.lock().jobs.len(),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            shed: self.shed.load(Ordering::Relaxed),
+            overflowed: self.overflowed.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState<H>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 队列的写入端，所有克隆被丢弃后关闭队列，工作线程处理完已入队的回调后退出
+struct LaneSender<H>(Arc<LaneQueue<H>>);
+
+impl<H> Drop for LaneSender<H> {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+struct Lane<H> {
+    /// 进入该通道的事件类型
+    filter: EventFilter,
+    queue: Arc<LaneSender<H>>,
+}
+
+impl<H> Clone for Lane<H> {
+    fn clone(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            queue: Arc::clone(&self.queue),
+        }
+    }
+}
+
+/// 按事件类型分通道分发的处理器包装
+///
+/// 每个通道有自己的有界队列和工作线程，事件按类型进入第一个匹配的通道，
+/// 例如把 Create 事件放到独立的低延迟通道、把 Trade 事件放到批量通道，
+/// 成交洪峰时交易事件只会在自己的队列中排队，不会拖慢新币通知。
+///
+/// - 没有匹配通道的事件以及非事件回调（账户、槽位、统计等）在分发线程中直接调用
+/// - `on_new_token` 与 CreateEvent 走同一通道，`on_event` 与对应的逐类型回调走同一通道（各自入队）
+/// - 通道队列已满时按 [`ShedPolicy`] 丢弃回调（可通过 [`metrics`](Self::metrics) 查询），
+///   不会阻塞订阅的接收循环，默认丢弃新到的回调
+/// - 通道有多个工作线程时，通道内的回调不保证顺序
+///
+/// 克隆后的实例共享同一组通道，所有克隆被丢弃后工作线程处理完已入队的回调后退出。
+pub struct EventLanes<H> {
+    handler: Arc<H>,
+    lanes: Vec<Lane<H>>,
+    /// 之后添加的通道使用的取舍策略
+    policy: ShedPolicy,
+}
+
+impl<H> Clone for EventLanes<H> {
+    fn clone(&self) -> Self {
+        Self {
+            handler: Arc::clone(&self.handler),
+            lanes: self.lanes.clone(),
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+impl<H: EventHandler + 'static> EventLanes<H> {
+    /// 包装处理器，尚未添加通道时所有回调都直接调用
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            lanes: Vec::new(),
+            policy: ShedPolicy::default(),
+        }
+    }
+
+    /// 设置队列已满时的取舍策略，见 [`ShedPolicy`]
+    ///
+    /// 只对之后添加的通道生效，应在添加通道前设置。
+    pub fn with_shed_policy(mut self, policy: ShedPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 添加通道，`filter` 中启用的事件类型进入该通道
+    ///
+    /// `workers` 为工作线程数、`queue_capacity` 为待处理回调的队列长度（均至少为 1）。
+    /// 同一事件类型出现在多个通道中时，先添加的通道优先。通道按添加顺序从 0 开始编号。
+    pub fn with_lane(
+        mut self,
+        filter: EventFilter,
+        workers: usize,
+        queue_capacity: usize,
+    ) -> Result<Self> {
+        let index = self.lanes.len();
+        let queue = Arc::new(LaneQueue::<H>::new(queue_capacity, self.policy.clone()));
+        // 工作线程启动失败时关闭已启动的线程
+        let sender = LaneSender(Arc::clone(&queue));
+        for worker in 0..workers.max(1) {
+            let queue = Arc::clone(&queue);
+            let handler = Arc::clone(&self.handler);
+            thread::Builder::new()
+                .name(format!("pump-lane-{index}-{worker}"))
+                .spawn(move || run_worker(index, &queue, &handler))
+                .map_err(|e| Error::Unknown(format!("启动事件通道线程失败: {e}")))?;
+        }
+        self.lanes.push(Lane {
+            filter,
+            queue: Arc::new(sender),
+        });
+        Ok(self)
+    }
+
+    /// 通道因队列已满丢弃的回调数（包括被挤出的已入队回调），通道不存在时返回 None
+    pub fn dropped(&self, lane: usize) -> Option<u64> {
+        self.metrics(lane)
+            .map(|metrics| metrics.dropped + metrics.shed)
+    }
+
+    /// 通道的队列指标，通道不存在时返回 None
+    pub fn metrics(&self, lane: usize) -> Option<LaneMetrics> {
+        self.lanes.get(lane).map(|lane| lane.queue.0.metrics())
+    }
+
+    /// 把事件交给匹配的通道，没有匹配的通道时直接调用
+    fn route<E>(
+        &self,
+        selected: impl Fn(&EventFilter) -> bool,
+        event: &E,
+        ctx: &EventContext,
+        call: fn(&H, &E, &EventContext),
+    ) where
+        E: Clone + Send + 'static,
+    {
+        match self
+            .lanes
+            .iter()
+            .enumerate()
+            .find(|(_, lane)| selected(&lane.filter))
+        {
+            Some((index, lane)) => {
+                let queue = &lane.queue.0;
+                let protected = queue.protects(&selected);
+                let (event, ctx) = (event.clone(), ctx.clone());
+                queue.push(
+                    index,
+                    Box::new(move |handler| call(handler, &event, &ctx)),
+                    protected,
+                );
+            }
+            None => call(&self.handler, event, ctx),
+        }
+    }
+}
+
+fn run_worker<H: EventHandler>(index: usize, queue: &LaneQueue<H>, handler: &H) {
+    while let Some(job) = queue.pop() {
+        if catch_unwind(AssertUnwindSafe(|| job(handler))).is_err() {
+            error!("事件通道 {} 的处理器 panic，已跳过该回调", index);
+        }
+    }
+}
+
+impl<H: EventHandler + 'static> EventHandler for EventLanes<H> {
+    fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
+        self.route(|f| f.create, event, ctx, H::on_create_event);
+    }
+
+    fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
+        self.route(|f| f.create_v2, event, ctx, H::on_create_v2_event);
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
+        self.route(|f| f.complete, event, ctx, H::on_complete_event);
+    }
+
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        self.route(|f| f.trade, event, ctx, H::on_trade_event);
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        self.route(|f| f.buy, event, ctx, H::on_buy_event);
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        self.route(|f| f.sell, event, ctx, H::on_sell_event);
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, ctx: &EventContext) {
+        self.route(|f| f.create_pool, event, ctx, H::on_create_pool_event);
+    }
+
+    fn on_set_params_event(&self, event: &SetParamsEvent, ctx: &EventContext) {
+        self.route(|f| f.set_params, event, ctx, H::on_set_params_event);
+    }
+
+    fn on_update_global_authority_event(
+        &self,
+        event: &UpdateGlobalAuthorityEvent,
+        ctx: &EventContext,
+    ) {
+        self.route(
+            |f| f.update_global_authority,
+            event,
+            ctx,
+            H::on_update_global_authority_event,
+        );
+    }
+
+    fn on_set_creator_event(&self, event: &SetCreatorEvent, ctx: &EventContext) {
+        self.route(|f| f.set_creator, event, ctx, H::on_set_creator_event);
+    }
+
+    fn on_set_metaplex_creator_event(&self, event: &SetMetaplexCreatorEvent, ctx: &EventContext) {
+        self.route(|f| f.set_metaplex_creator, event, ctx, H::on_set_metaplex_creator_event);
+    }
+
+    fn on_deposit_event(&self, event: &DepositEvent, ctx: &EventContext) {
+        self.route(|f| f.deposit, event, ctx, H::on_deposit_event);
+    }
+
+    fn on_withdraw_event(&self, event: &WithdrawEvent, ctx: &EventContext) {
+        self.route(|f| f.withdraw, event, ctx, H::on_withdraw_event);
+    }
+
+    fn on_collect_creator_fee_event(&self, event: &CollectCreatorFeeEvent, ctx: &EventContext) {
+        self.route(|f| f.collect_creator_fee, event, ctx, H::on_collect_creator_fee_event);
+    }
+
+    fn on_collect_coin_creator_fee_event(
+        &self,
+        event: &CollectCoinCreatorFeeEvent,
+        ctx: &EventContext,
+    ) {
+        self.route(
+            |f| f.collect_coin_creator_fee,
+            event,
+            ctx,
+            H::on_collect_coin_creator_fee_event,
+        );
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, ctx: &EventContext) {
+        self.route(|f| f.migrate, event, ctx, H::on_migrate_event);
+    }
+
+    fn on_new_token(&self, token: &NewToken, ctx: &EventContext) {
+        self.route(|f| f.create, token, ctx, H::on_new_token);
+    }
+
+    fn on_event(&self, event: &PumpEvent, ctx: &EventContext) {
+        self.route(|f| f.matches(event), event, ctx, H::on_event);
+    }
+
+    fn on_slot_complete(&self, slot: u64, events: &[(PumpEvent, EventContext)]) {
+        self.handler.on_slot_complete(slot, events);
+    }
+
+    fn on_transaction(&self, tx: &TransactionUpdate, events: &[PumpEvent], ctx: &EventContext) {
+        self.handler.on_transaction(tx, events, ctx);
+    }
+
+    fn on_bonding_curve_update(
+        &self,
+        mint: &Pubkey,
+        curve: &BondingCurveAccount,
+        ctx: &AccountContext,
+    ) {
+        self.handler.on_bonding_curve_update(mint, curve, ctx);
+    }
+
+    fn on_pool_reserves(&self, reserves: &PoolReserves, ctx: &AccountContext) {
+        self.handler.on_pool_reserves(reserves, ctx);
+    }
+
+    fn on_account_update(&self, account: &AccountUpdate, ctx: &AccountContext) {
+        self.handler.on_account_update(account, ctx);
+    }
+
+    fn on_program_account(&self, account: &ProgramAccount, ctx: &AccountContext) {
+        self.handler.on_program_account(account, ctx);
+    }
+
+    fn on_slot_update(&self, update: &SlotUpdate) {
+        self.handler.on_slot_update(update);
+    }
+
+    fn on_block_meta(&self, meta: &BlockMetaUpdate) {
+        self.handler.on_block_meta(meta);
+    }
+
+    fn on_entry(&self, entry: &EntryUpdate) {
+        self.handler.on_entry(entry);
+    }
+
+    fn on_lag(&self, lag: &StreamLag) {
+        self.handler.on_lag(lag);
+    }
+
+    fn on_latency_report(&self, report: &LatencyReport) {
+        self.handler.on_latency_report(report);
+    }
+
+    fn on_stats(&self, stats: &StreamStats) {
+        self.handler.on_stats(stats);
+    }
+
+    fn on_raw_update(&self, update: &SubscribeUpdate) {
+        self.handler.on_raw_update(update);
+    }
+}
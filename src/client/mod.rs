@@ -1,9 +1,59 @@
+mod batch;
+pub mod cache;
+pub mod checkpoint;
 pub mod config;
+pub mod custom;
+pub mod deadletter;
+pub mod dedup;
+pub mod fees;
+pub mod fills;
+pub mod filter;
+pub mod graduated;
 pub mod grpc;
 pub mod handler;
+pub mod lanes;
+mod pipeline;
+pub mod pools;
+pub mod profiler;
+mod recycle;
+pub mod registry;
+pub mod router;
+pub mod stats;
+pub mod status;
+pub mod stream;
+pub mod subscription;
+pub mod updates;
+pub mod watchlist;
 
-pub use config::Config;
+pub use cache::AccountCache;
+#[cfg(feature = "sqlite")]
+pub use checkpoint::SqliteCheckpointStore;
+pub use checkpoint::{CheckpointStore, FileCheckpointStore, MemoryCheckpointStore};
+pub use config::{Config, DispatchOrder};
+pub use custom::CustomEvents;
+pub use deadletter::{
+    ChannelDeadLetterSink, DeadLetter, DeadLetterReason, DeadLetterSink, FileDeadLetterSink,
+};
+pub use dedup::DedupCache;
+pub use fees::{AmmFees, FeeParams, FeeWatcher, PumpFees};
+pub use fills::{FillOutcome, FillReport, FillTracker};
+pub use filter::SubscriptionFilter;
 pub use handler::{
-    EventContext, EventFilter, EventHandler, FilteredLoggingEventHandler, LoggingEventHandler,
+    AccountContext, EventContext, EventFilter, EventHandler, FilteredLoggingEventHandler,
+    LoggingEventHandler, SharedEventFilter,
 };
+pub use graduated::{GraduatedToken, GraduatedTokens};
 pub use grpc::GrpcClient;
+pub use lanes::{EventLanes, LaneMetrics, ShedPolicy};
+pub use pools::{PoolKeys, PoolReserves, PoolTracker};
+pub use profiler::{LatencyHistogram, LatencyProfiler, LatencyReport, LatencyStage};
+pub use registry::{HandlerId, HandlerMetrics, HandlerRegistry};
+pub use router::EventRouter;
+pub use stats::StreamStats;
+pub use status::{ClientStatus, ConnectionState, StreamLag};
+pub use stream::EventStream;
+pub use subscription::Subscription;
+pub use updates::{
+    AccountUpdate, BlockMetaUpdate, EntryUpdate, SlotUpdate, TokenBalance, TransactionUpdate,
+};
+pub use watchlist::Watchlist;
@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+    thread,
+    time::Instant,
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    error::{Error, Result},
+    models::PumpEvent,
+    parser::events::{visit_events_reporting, DecodeMode, EventSource},
+};
+
+use super::{
+    config::DispatchOrder,
+    custom::{CustomEvent, CustomEvents},
+    deadletter::{deliver, DeadLetter, DeadLetterSink},
+    handler::EventContext,
+    recycle::VecPool,
+    updates::TransactionUpdate,
+};
+
+/// 每个解析线程对应的待处理任务队列长度
+const QUEUE_DEPTH_PER_WORKER: usize = 256;
+
+/// 一笔交易的解析任务
+struct Job {
+    seq: u64,
+    logs: Vec<String>,
+    /// `emit_cpi!` 事件指令数据
+    cpi: Vec<Vec<u8>>,
+    ctx: EventContext,
+    transaction: Option<TransactionUpdate>,
+}
+
+/// (discriminator, 事件, 未知的末尾字节)
+pub(crate) type ParsedEvent = ([u8; 8], PumpEvent, Option<Arc<[u8]>>);
+
+/// 一笔交易的解析结果
+pub(crate) struct Parsed {
+    pub ctx: EventContext,
+    /// 顺序与内联解析时的分发顺序一致
+    pub events: Vec<ParsedEvent>,
+    /// 解析线程开始解码的时间
+    pub decode_started: Instant,
+    /// 整笔交易解码完成的时间
+    pub decoded_at: Instant,
+    /// 解码失败的事件数量
+    pub decode_failures: usize,
+    /// 交易元数据（未启用 `Config::with_transaction_meta` 时为 None）
+    pub transaction: Option<TransactionUpdate>,
+    /// 按日志顺序解码出的自定义事件
+    pub custom: Vec<CustomEvent>,
+}
+
+/// 把事件数据中未知的末尾字节转换为可跨线程共享的形式，没有时为 None
+#[inline]
+pub(crate) fn unknown_tail(tail: &[u8]) -> Option<Arc<[u8]>> {
+    (!tail.is_empty()).then(|| Arc::from(tail))
+}
+
+/// 并行解析流水线
+///
+/// 交易日志的 base64 解码和 Borsh 解析交给固定数量的工作线程完成，
+/// `Ordered` 模式下结果按提交顺序（即流中交易的到达顺序）重新排好后再交给调用方分发，
+/// 因此处理器看到的事件顺序与单线程模式相同；`Unordered` 模式下解析完成即交付。
+pub(crate) struct ParsePipeline {
+    order: DispatchOrder,
+    jobs: mpsc::Sender<Job>,
+    results: mpsc::UnboundedReceiver<(u64, Parsed)>,
+    next_submit: u64,
+    /// Ordered 模式下为下一个待交付的序号，Unordered 模式下为已交付数量
+    next_dispatch: u64,
+    /// 已解析完成但前序结果尚未到达的交易
+    pending: BTreeMap<u64, Parsed>,
+    /// 事件列表缓冲池（未启用缓冲区复用时为 None）
+    events_pool: Option<VecPool<ParsedEvent>>,
+    /// 交付批次缓冲池（未启用缓冲区复用时为 None）
+    batch_pool: Option<VecPool<Parsed>>,
+}
+
+/// 解码一笔交易的事件和自定义事件，返回 (事件, 解码失败数, 自定义事件)
+fn decode_job(
+    job: &Job,
+    source: EventSource,
+    mode: DecodeMode,
+    events_pool: &Option<VecPool<ParsedEvent>>,
+    custom: &Option<CustomEvents>,
+    dead_letters: &Option<Arc<dyn DeadLetterSink>>,
+) -> (Vec<ParsedEvent>, usize, Vec<CustomEvent>) {
+    let mut events = events_pool.as_ref().map(VecPool::take).unwrap_or_default();
+    let decode_failures = visit_events_reporting(
+        &job.logs,
+        &job.cpi,
+        source,
+        mode,
+        |discriminator, event, tail| {
+            let mut key = [0u8; 8];
+            key.copy_from_slice(discriminator);
+            events.push((key, event, unknown_tail(tail)));
+        },
+        |discriminator, data, e| {
+            if let Some(sink) = dead_letters {
+                let letter = DeadLetter::decode_failure(discriminator, data, e, &job.ctx);
+                deliver(sink.as_ref(), letter);
+            }
+        },
+    );
+    let custom = custom.as_ref().map(|custom| custom.decode(&job.logs)).unwrap_or_default();
+    (events, decode_failures, custom)
+}
+
+impl ParsePipeline {
+    /// 启动 `workers` 个解析线程，流水线被 drop 后线程自动退出
+    ///
+    /// `reuse_buffers` 为 true 时，事件列表和交付批次从缓冲池中借用，
+    /// 调用方分发完成后通过 [`recycle`](Self::recycle) 归还。
+    /// 提供 `custom` 时，解析线程同时解码其中注册的自定义事件；
+    /// 提供 `dead_letters` 时，解码失败的事件由解析线程写入死信队列。
+    pub(crate) fn new(
+        workers: usize,
+        order: DispatchOrder,
+        mode: DecodeMode,
+        source: EventSource,
+        reuse_buffers: bool,
+        custom: Option<CustomEvents>,
+        dead_letters: Option<Arc<dyn DeadLetterSink>>,
+    ) -> Result<Self> {
+        let workers = workers.max(1);
+        let events_pool = reuse_buffers.then(VecPool::<ParsedEvent>::default);
+        let (jobs_tx, jobs_rx) = mpsc::channel::<Job>(workers * QUEUE_DEPTH_PER_WORKER);
+        let (results_tx, results_rx) = mpsc::unbounded_channel();
+        let jobs_rx = Arc::new(Mutex::new(jobs_rx));
+
+        for i in 0..workers {
+            let jobs_rx = Arc::clone(&jobs_rx);
+            let results_tx = results_tx.clone();
+            let events_pool = events_pool.clone();
+            let custom = custom.clone();
+            let dead_letters = dead_letters.clone();
+            thread::Builder::new()
+                .name(format!("pump-parse-{i}"))
+                .spawn(move || loop {
+                    let job = {
+                        let mut rx = jobs_rx.lock().unwrap_or_else(|e| e.into_inner());
+                        rx.blocking_recv()
+                    };
+                    let Some(job) = job else {
+                        break;
+                    };
+                    let decode_started = Instant::now();
+                    // 自定义解码器和死信队列是用户代码，panic 时仍然交付空结果，
+                    // 否则 Ordered 模式会一直等待这个序号，后续结果在 `pending` 中无限堆积
+                    let decoded = catch_unwind(AssertUnwindSafe(|| {
+                        decode_job(&job, source, mode, &events_pool, &custom, &dead_letters)
+                    }));
+                    let (events, decode_failures, custom_events) = match decoded {
+                        Ok(decoded) => decoded,
+                        Err(panic) => {
+                            log::error!("解析交易 {} 时 panic，已丢弃其事件", job.ctx.signature);
+                            if let Some(sink) = &dead_letters {
+                                let letter = DeadLetter::parse_panic(panic.as_ref(), &job.ctx);
+                                let reported = catch_unwind(AssertUnwindSafe(|| {
+                                    deliver(sink.as_ref(), letter)
+                                }));
+                                if reported.is_err() {
+                                    log::error!("写入死信队列时 panic");
+                                }
+                            }
+                            let events = events_pool.as_ref().map(VecPool::take).unwrap_or_default();
+                            (events, 0, Vec::new())
+                        }
+                    };
+                    let parsed = Parsed {
+                        ctx: job.ctx,
+                        events,
+                        decode_started,
+                        decoded_at: Instant::now(),
+                        decode_failures,
+                        transaction: job.transaction,
+                        custom: custom_events,
+                    };
+                    if results_tx.send((job.seq, parsed)).is_err() {
+                        break;
+                    }
+                })
+                .map_err(|e| Error::Unknown(format!("启动解析线程失败: {e}")))?;
+        }
+
+        Ok(Self {
+            order,
+            jobs: jobs_tx,
+            results: results_rx,
+            next_submit: 0,
+            next_dispatch: 0,
+            pending: BTreeMap::new(),
+            events_pool,
+            batch_pool: reuse_buffers.then(VecPool::default),
+        })
+    }
+
+    /// 提交一笔交易的日志和事件指令数据，队列已满时等待
+    pub(crate) async fn submit(
+        &mut self,
+        logs: Vec<String>,
+        cpi: Vec<Vec<u8>>,
+        ctx: EventContext,
+        transaction: Option<TransactionUpdate>,
+    ) -> Result<()> {
+        let seq = self.next_submit;
+        self.jobs
+            .send(Job {
+                seq,
+                logs,
+                cpi,
+                ctx,
+                transaction,
+            })
+            .await
+            .map_err(|_| Error::Unknown("解析线程已退出".to_string()))?;
+        self.next_submit += 1;
+        Ok(())
+    }
+
+    /// 是否还有已提交但未交付的交易
+    pub(crate) fn in_flight(&self) -> bool {
+        self.next_dispatch < self.next_submit
+    }
+
+    /// 等待下一批可以按顺序分发的解析结果
+    ///
+    /// 没有在途交易时返回 None。
+    pub(crate) async fn next_ready(&mut self) -> Option<Vec<Parsed>> {
+        while self.in_flight() {
+            let (seq, parsed) = self.results.recv().await?;
+            let mut ready = self.batch_buffer();
+            if self.order == DispatchOrder::Unordered {
+                self.next_dispatch += 1;
+                ready.push(parsed);
+                return Some(ready);
+            }
+            self.pending.insert(seq, parsed);
+
+            while let Some(parsed) = self.pending.remove(&self.next_dispatch) {
+                ready.push(parsed);
+                self.next_dispatch += 1;
+            }
+            if !ready.is_empty() {
+                return Some(ready);
+            }
+            self.recycle(ready);
+        }
+        None
+    }
+
+    /// 归还已分发完的批次及其中的事件列表，未启用缓冲区复用时直接释放
+    pub(crate) fn recycle(&self, mut batch: Vec<Parsed>) {
+        let (Some(events_pool), Some(batch_pool)) = (&self.events_pool, &self.batch_pool) else {
+            return;
+        };
+        for parsed in batch.drain(..) {
+            events_pool.put(parsed.events);
+        }
+        batch_pool.put(batch);
+    }
+
+    fn batch_buffer(&self) -> Vec<Parsed> {
+        self.batch_pool
+            .as_ref()
+            .map(VecPool::take)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::deadletter::{ChannelDeadLetterSink, DeadLetterReason};
+    use base64::{engine::general_purpose, Engine};
+    use solana_sdk::signature::Signature;
+    use std::time::SystemTime;
+
+    const PANIC_DISCRIMINATOR: [u8; 8] = [0xee; 8];
+
+    fn ctx(slot: u64) -> EventContext {
+        EventContext {
+            slot,
+            tx_index: 0,
+            signature: Signature::default(),
+            timestamp: Instant::now(),
+            elapsed: Default::default(),
+            received_at: SystemTime::now(),
+            block_time: None,
+            filters: Arc::from(Vec::new()),
+            unknown_tail: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn panicking_custom_decoder_does_not_stall_ordered_dispatch() {
+        let custom = CustomEvents::new();
+        custom.register(
+            PANIC_DISCRIMINATOR,
+            |_: &[u8]| -> Option<()> { panic!("bad decoder") },
+            |_: &(), _: &EventContext| {},
+        );
+        let (sink, mut letters) = ChannelDeadLetterSink::new(4);
+        let mut pipeline = ParsePipeline::new(
+            2,
+            DispatchOrder::Ordered,
+            DecodeMode::Strict,
+            EventSource::Logs,
+            false,
+            Some(custom),
+            Some(Arc::new(sink)),
+        )
+        .unwrap();
+
+        let log = format!("Program data: {}", general_purpose::STANDARD.encode(PANIC_DISCRIMINATOR));
+        pipeline.submit(vec![log], Vec::new(), ctx(1), None).await.unwrap();
+        pipeline.submit(Vec::new(), Vec::new(), ctx(2), None).await.unwrap();
+
+        let mut slots = Vec::new();
+        while let Some(batch) = pipeline.next_ready().await {
+            slots.extend(batch.iter().map(|parsed| parsed.ctx.slot));
+            assert!(batch.iter().all(|parsed| parsed.events.is_empty()));
+        }
+        assert_eq!(slots, [1, 2]);
+
+        let letter = letters.recv().await.unwrap();
+        assert_eq!(letter.slot, 1);
+        assert_eq!(letter.reason, DeadLetterReason::ParsePanic("bad decoder".to_string()));
+    }
+}
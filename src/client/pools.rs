@@ -0,0 +1,315 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::watch;
+use yellowstone_grpc_proto::geyser::{SubscribeRequest, SubscribeRequestFilterAccounts};
+
+use crate::{
+    models::PoolAccount,
+    pda::{self, TOKEN_PROGRAM_ID, WSOL_MINT},
+};
+
+/// 池跟踪器在订阅请求中使用的账户过滤器名称
+pub const POOL_TRACKER_FILTER: &str = "pool_vaults";
+
+/// 代币账户中 amount 字段的偏移
+const AMOUNT_OFFSET: usize = 64;
+
+/// PumpAmm 池的地址信息
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PoolKeys {
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// 池持有 base 代币的账户
+    pub base_vault: Pubkey,
+    /// 池持有 quote 代币的账户
+    pub quote_vault: Pubkey,
+}
+
+impl PoolKeys {
+    /// 由池地址和两种代币推导池的代币账户（池的关联代币账户）
+    pub fn derive(
+        pool: Pubkey,
+        base_mint: Pubkey,
+        quote_mint: Pubkey,
+        base_token_program: &Pubkey,
+        quote_token_program: &Pubkey,
+    ) -> Self {
+        Self {
+            pool,
+            base_mint,
+            quote_mint,
+            base_vault: pda::associated_token_account(&pool, &base_mint, base_token_program),
+            quote_vault: pda::associated_token_account(&pool, &quote_mint, quote_token_program),
+        }
+    }
+
+    /// 由池账户中记录的代币和代币账户构建
+    pub fn from_pool_account(pool: Pubkey, account: &PoolAccount) -> Self {
+        Self {
+            pool,
+            base_mint: account.base_mint,
+            quote_mint: account.quote_mint,
+            base_vault: account.pool_base_token_account,
+            quote_vault: account.pool_quote_token_account,
+        }
+    }
+
+    /// bonding curve 完成后迁移生成的标准池（mint/WSOL）
+    ///
+    /// `token_program` 为代币所属的 Token 程序，可从 `NewToken::token_program` 获得。
+    pub fn canonical(mint: Pubkey, token_program: &Pubkey) -> Self {
+        Self::derive(
+            pda::canonical_pump_pool(&mint),
+            mint,
+            WSOL_MINT,
+            token_program,
+            &TOKEN_PROGRAM_ID,
+        )
+    }
+}
+
+/// 池的当前储备
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolReserves {
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_reserves: u64,
+    pub quote_reserves: u64,
+    /// 最近一次储备变化所在的槽位
+    pub slot: u64,
+}
+
+impl PoolReserves {
+    /// 以 quote 计价的锁仓价值（两侧价值相等，为 quote 储备的两倍）
+    pub fn tvl(&self) -> u128 {
+        self.quote_reserves as u128 * 2
+    }
+
+    /// 用 `quote_in` 买入可以得到的 base 数量，`fee_bps` 为从输入中扣除的总费率
+    pub fn buy_base_out(&self, quote_in: u64, fee_bps: u64) -> u64 {
+        swap_out(self.quote_reserves, self.base_reserves, quote_in, fee_bps)
+    }
+
+    /// 卖出 `base_in` 可以得到的 quote 数量，`fee_bps` 为从输出中扣除的总费率
+    pub fn sell_quote_out(&self, base_in: u64, fee_bps: u64) -> u64 {
+        let out = swap_out(self.base_reserves, self.quote_reserves, base_in, 0);
+        let out = out as u128;
+        out.saturating_sub(out * fee_bps as u128 / 10_000) as u64
+    }
+}
+
+/// 恒定乘积公式的输出数量
+fn swap_out(reserve_in: u64, reserve_out: u64, amount_in: u64, fee_bps: u64) -> u64 {
+    let amount_in = amount_in as u128 * 10_000u128.saturating_sub(fee_bps as u128) / 10_000;
+    (reserve_out as u128 * amount_in)
+        .checked_div(reserve_in as u128 + amount_in)
+        .unwrap_or_default() as u64
+}
+
+/// 代币账户属于池的哪一侧
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum VaultSide {
+    Base,
+    Quote,
+}
+
+struct TrackedPool {
+    keys: PoolKeys,
+    base_reserves: Option<u64>,
+    quote_reserves: Option<u64>,
+    slot: u64,
+    sender: watch::Sender<Option<PoolReserves>>,
+}
+
+impl TrackedPool {
+    fn reserves(&self) -> Option<PoolReserves> {
+        Some(PoolReserves {
+            pool: self.keys.pool,
+            base_mint: self.keys.base_mint,
+            quote_mint: self.keys.quote_mint,
+            base_reserves: self.base_reserves?,
+            quote_reserves: self.quote_reserves?,
+            slot: self.slot,
+        })
+    }
+}
+
+/// PumpAmm 池储备跟踪
+///
+/// 通过 `GrpcClient::with_pool_tracker` 挂到客户端上后，跟踪中的池会自动为两个代币账户
+/// 添加账户订阅（运行中的订阅也会即时更新），代币账户余额变化后更新池的储备并通过
+/// `EventHandler::on_pool_reserves` 分发，交易时计算报价无需任何 RPC 调用。
+///
+/// 两个代币账户的更新分别到达，同一槽位内两侧更新之间的短暂时刻储备可能不一致。
+/// 克隆后的实例共享同一份数据。
+#[derive(Clone)]
+pub struct PoolTracker {
+    pools: Arc<Mutex<HashMap<Pubkey, TrackedPool>>>,
+    /// 代币账户 -> (池地址, 所属侧)
+    vaults: Arc<watch::Sender<HashMap<Pubkey, (Pubkey, VaultSide)>>>,
+}
+
+impl Default for PoolTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoolTracker {
+    /// 创建空的池跟踪器
+    pub fn new() -> Self {
+        Self {
+            pools: Arc::new(Mutex::new(HashMap::new())),
+            vaults: Arc::new(watch::Sender::new(HashMap::new())),
+        }
+    }
+
+    /// 开始跟踪池，新加入时返回 `true`
+    pub fn track(&self, keys: PoolKeys) -> bool {
+        {
+            let mut pools = self.lock();
+            if pools.contains_key(&keys.pool) {
+                return false;
+            }
+            pools.insert(
+                keys.pool,
+                TrackedPool {
+                    keys,
+                    base_reserves: None,
+                    quote_reserves: None,
+                    slot: 0,
+                    sender: watch::Sender::new(None),
+                },
+            );
+        }
+        self.vaults.send_modify(|vaults| {
+            vaults.insert(keys.base_vault, (keys.pool, VaultSide::Base));
+            vaults.insert(keys.quote_vault, (keys.pool, VaultSide::Quote));
+        });
+        true
+    }
+
+    /// 停止跟踪池，存在时返回 `true`
+    pub fn untrack(&self, pool: &Pubkey) -> bool {
+        let Some(tracked) = self.lock().remove(pool) else {
+            return false;
+        };
+        self.vaults.send_modify(|vaults| {
+            vaults.remove(&tracked.keys.base_vault);
+            vaults.remove(&tracked.keys.quote_vault);
+        });
+        true
+    }
+
+    /// 池的当前储备，两侧余额都已知之前返回 None
+    pub fn reserves(&self, pool: &Pubkey) -> Option<PoolReserves> {
+        self.lock().get(pool)?.reserves()
+    }
+
+    /// 订阅池的储备变化，池未被跟踪时返回 None
+    pub fn subscribe(&self, pool: &Pubkey) -> Option<watch::Receiver<Option<PoolReserves>>> {
+        Some(self.lock().get(pool)?.sender.subscribe())
+    }
+
+    /// 跟踪中的所有池
+    pub fn pools(&self) -> Vec<PoolKeys> {
+        self.lock().values().map(|tracked| tracked.keys).collect()
+    }
+
+    pub(crate) fn subscribe_vaults(&self) -> watch::Receiver<HashMap<Pubkey, (Pubkey, VaultSide)>> {
+        self.vaults.subscribe()
+    }
+
+    /// 用代币账户数据更新储备，账户不属于跟踪中的池或储备没有变化时返回 None
+    pub(crate) fn apply_account(
+        &self,
+        vault: &Pubkey,
+        data: &[u8],
+        slot: u64,
+    ) -> Option<PoolReserves> {
+        let (pool, side) = *self.vaults.borrow().get(vault)?;
+        let amount = u64::from_le_bytes(
+            data.get(AMOUNT_OFFSET..AMOUNT_OFFSET + 8)?
+                .try_into()
+                .ok()?,
+        );
+
+        let mut pools = self.lock();
+        let tracked = pools.get_mut(&pool)?;
+        let side_reserves = match side {
+            VaultSide::Base => &mut tracked.base_reserves,
+            VaultSide::Quote => &mut tracked.quote_reserves,
+        };
+        // 旧槽位的更新不覆盖新数据
+        if slot < tracked.slot || *side_reserves == Some(amount) {
+            return None;
+        }
+        *side_reserves = Some(amount);
+        tracked.slot = slot;
+        let reserves = tracked.reserves()?;
+        tracked.sender.send_replace(Some(reserves));
+        Some(reserves)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Pubkey, TrackedPool>> {
+        self.pools.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 将跟踪中的池代币账户写入订阅请求的账户过滤器，没有跟踪的池时移除该过滤器
+pub(crate) fn apply_pool_tracker(
+    vaults: &HashMap<Pubkey, (Pubkey, VaultSide)>,
+    request: &mut SubscribeRequest,
+) {
+    if vaults.is_empty() {
+        request.accounts.remove(POOL_TRACKER_FILTER);
+        return;
+    }
+    request.accounts.insert(
+        POOL_TRACKER_FILTER.to_string(),
+        SubscribeRequestFilterAccounts {
+            account: vaults.keys().map(|vault| vault.to_string()).collect(),
+            owner: vec![],
+            filters: vec![],
+            nonempty_txn_signature: None,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserves() -> PoolReserves {
+        PoolReserves {
+            pool: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            quote_mint: WSOL_MINT,
+            base_reserves: 1_000_000,
+            quote_reserves: 1_000_000,
+            slot: 1,
+        }
+    }
+
+    #[test]
+    fn quotes_deduct_fees() {
+        let reserves = reserves();
+        assert_eq!(reserves.buy_base_out(1_000, 0), 999);
+        assert_eq!(reserves.buy_base_out(1_000, 100), 989);
+        assert_eq!(reserves.sell_quote_out(1_000, 0), 999);
+        assert_eq!(reserves.sell_quote_out(1_000, 100), 990);
+    }
+
+    #[test]
+    fn fees_above_100_percent_quote_zero() {
+        let reserves = reserves();
+        assert_eq!(reserves.buy_base_out(1_000, 20_000), 0);
+        assert_eq!(reserves.sell_quote_out(1_000, 20_000), 0);
+        assert_eq!(reserves.sell_quote_out(1_000, u64::MAX), 0);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+use yellowstone_grpc_proto::prost_types::Timestamp;
+
+/// 直方图桶数：第 0 个桶为 0 微秒，第 i 个桶覆盖 [2^(i-1), 2^i) 微秒，
+/// 最后一个桶收纳所有更大的值（约 18 分钟以上）
+const BUCKETS: usize = 32;
+
+/// 事件处理的阶段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LatencyStage {
+    /// 端点生成更新到 SDK 收到更新（依赖两端时钟同步）
+    Network,
+    /// 收到交易到开始解码，并行解析模式下主要是等待解析线程的时间
+    Queue,
+    /// 日志 base64 解码与 Borsh 解析
+    Decode,
+    /// 解码完成到调用处理器，包括去重、过期检查和有序模式下的重排等待
+    Dispatch,
+    /// 处理器回调本身的耗时
+    Handler,
+}
+
+impl LatencyStage {
+    /// 按处理顺序排列的全部阶段
+    pub const ALL: [LatencyStage; 5] = [
+        LatencyStage::Network,
+        LatencyStage::Queue,
+        LatencyStage::Decode,
+        LatencyStage::Dispatch,
+        LatencyStage::Handler,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LatencyStage::Network => "network",
+            LatencyStage::Queue => "queue",
+            LatencyStage::Decode => "decode",
+            LatencyStage::Dispatch => "dispatch",
+            LatencyStage::Handler => "handler",
+        }
+    }
+}
+
+/// 按 2 的幂分桶的延迟直方图（微秒精度）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    buckets: [u64; BUCKETS],
+    count: u64,
+    total_us: u64,
+    max_us: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: [0; BUCKETS],
+            count: 0,
+            total_us: 0,
+            max_us: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// 记录一个样本
+    pub fn record(&mut self, latency: Duration) {
+        let us = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let index = ((u64::BITS - us.leading_zeros()) as usize).min(BUCKETS - 1);
+        self.buckets[index] += 1;
+        self.count += 1;
+        self.total_us = self.total_us.saturating_add(us);
+        self.max_us = self.max_us.max(us);
+    }
+
+    /// 样本数量
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 平均值
+    pub fn mean(&self) -> Duration {
+        Duration::from_micros(self.total_us.checked_div(self.count).unwrap_or_default())
+    }
+
+    /// 最大值
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max_us)
+    }
+
+    /// 分位数（`quantile` 取 0.0 ~ 1.0），返回样本所在桶的上界，不超过最大值
+    pub fn percentile(&self, quantile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((self.count as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let upper = if index == 0 { 0 } else { (1u64 << index) - 1 };
+                return Duration::from_micros(upper.min(self.max_us));
+            }
+        }
+        self.max()
+    }
+
+    /// 各桶的样本数，第 i 个桶的上界为 2^i - 1 微秒
+    pub fn buckets(&self) -> &[u64] {
+        &self.buckets
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "n={} mean={:?} p50={:?} p90={:?} p99={:?} max={:?}",
+            self.count,
+            self.mean(),
+            self.percentile(0.5),
+            self.percentile(0.9),
+            self.percentile(0.99),
+            self.max()
+        )
+    }
+}
+
+/// 一个统计周期内各阶段的延迟分布
+#[derive(Clone, Debug, Default)]
+pub struct LatencyReport {
+    /// 统计周期长度
+    pub interval: Duration,
+    /// 端点生成更新到收到更新，每条更新记录一次
+    pub network: LatencyHistogram,
+    /// 收到交易到开始解码，每笔交易记录一次
+    pub queue: LatencyHistogram,
+    /// 解码耗时，内联模式下按事件记录，并行解析模式下为整笔交易的解码耗时
+    pub decode: LatencyHistogram,
+    /// 解码完成到调用处理器，每个事件记录一次
+    pub dispatch: LatencyHistogram,
+    /// 处理器回调耗时，每个事件记录一次
+    pub handler: LatencyHistogram,
+}
+
+impl LatencyReport {
+    /// 指定阶段的延迟分布
+    pub fn stage(&self, stage: LatencyStage) -> &LatencyHistogram {
+        match stage {
+            LatencyStage::Network => &self.network,
+            LatencyStage::Queue => &self.queue,
+            LatencyStage::Decode => &self.decode,
+            LatencyStage::Dispatch => &self.dispatch,
+            LatencyStage::Handler => &self.handler,
+        }
+    }
+
+    /// p99 最高的阶段，没有样本时为 None
+    pub fn slowest_stage(&self) -> Option<LatencyStage> {
+        LatencyStage::ALL
+            .into_iter()
+            .filter(|stage| self.stage(*stage).count() > 0)
+            .max_by_key(|stage| self.stage(*stage).percentile(0.99))
+    }
+
+    fn stage_mut(&mut self, stage: LatencyStage) -> &mut LatencyHistogram {
+        match stage {
+            LatencyStage::Network => &mut self.network,
+            LatencyStage::Queue => &mut self.queue,
+            LatencyStage::Decode => &mut self.decode,
+            LatencyStage::Dispatch => &mut self.dispatch,
+            LatencyStage::Handler => &mut self.handler,
+        }
+    }
+}
+
+impl fmt::Display for LatencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "延迟统计（{:?}）", self.interval)?;
+        for stage in LatencyStage::ALL {
+            write!(f, "\n  {:<8} {}", stage.name(), self.stage(stage))?;
+        }
+        Ok(())
+    }
+}
+
+struct State {
+    started: Instant,
+    report: LatencyReport,
+}
+
+/// 分阶段延迟剖析器
+///
+/// 通过 `GrpcClient::with_latency_profiler` 启用后，客户端在收到更新、开始解码、解码完成、
+/// 调用处理器和处理器返回时记录时间点，按阶段累积到直方图中，每隔 `report_interval`
+/// 通过 `EventHandler::on_latency_report` 交付一次统计并清零，
+/// 用于判断延迟来自端点、SDK 还是处理器本身。
+///
+/// 内部使用 `Arc` 共享，克隆后的实例共用同一份统计，也可以随时调用 [`snapshot`] 读取当前周期的数据。
+///
+/// [`snapshot`]: LatencyProfiler::snapshot
+#[derive(Clone)]
+pub struct LatencyProfiler {
+    inner: Arc<Mutex<State>>,
+    report_interval: Duration,
+}
+
+impl LatencyProfiler {
+    /// 创建剖析器，每隔 `report_interval` 交付一次统计
+    pub fn new(report_interval: Duration) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(State {
+                started: Instant::now(),
+                report: LatencyReport::default(),
+            })),
+            report_interval,
+        }
+    }
+
+    /// 统计周期
+    pub fn report_interval(&self) -> Duration {
+        self.report_interval
+    }
+
+    /// 当前周期到目前为止的统计，不清零
+    pub fn snapshot(&self) -> LatencyReport {
+        let state = self.lock();
+        LatencyReport {
+            interval: state.started.elapsed(),
+            ..state.report.clone()
+        }
+    }
+
+    /// 清空当前周期的统计
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        state.started = Instant::now();
+        state.report = LatencyReport::default();
+    }
+
+    /// 周期已结束时取出统计并开始新的周期
+    pub(crate) fn take_due(&self) -> Option<LatencyReport> {
+        let mut state = self.lock();
+        let interval = state.started.elapsed();
+        if interval < self.report_interval {
+            return None;
+        }
+        state.started = Instant::now();
+        let report = std::mem::take(&mut state.report);
+        Some(LatencyReport { interval, ..report })
+    }
+
+    /// 记录一个阶段的耗时
+    pub(crate) fn record(&self, stage: LatencyStage, latency: Duration) {
+        self.lock().report.stage_mut(stage).record(latency);
+    }
+
+    /// 记录端点生成更新到收到更新的时间，端点时钟超前时记为 0
+    pub(crate) fn record_network(&self, created_at: Option<&Timestamp>, received_at: SystemTime) {
+        let Some(created_at) = created_at.and_then(|ts| SystemTime::try_from(*ts).ok()) else {
+            return;
+        };
+        let latency = received_at.duration_since(created_at).unwrap_or_default();
+        self.record(LatencyStage::Network, latency);
+    }
+
+    /// 记录一个事件从解码完成到处理器返回的耗时
+    pub(crate) fn record_dispatch(&self, decoded: Instant, called: Instant, returned: Instant) {
+        let mut state = self.lock();
+        state.report.dispatch.record(called.duration_since(decoded));
+        state.report.handler.record(returned.duration_since(called));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex};
+
+/// 池中最多保留的缓冲区数量，超过时归还的缓冲区直接释放
+const MAX_POOLED: usize = 1024;
+
+/// 可跨线程借还的 `Vec` 缓冲池
+///
+/// 解析线程从池中取出缓冲区填充事件，分发完成后由接收循环清空并归还，
+/// 缓冲区的容量在交易之间保留，高峰期不再为每笔交易重新分配。
+pub(crate) struct VecPool<T> {
+    buffers: Arc<Mutex<Vec<Vec<T>>>>,
+}
+
+impl<T> Clone for VecPool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buffers: Arc::clone(&self.buffers),
+        }
+    }
+}
+
+impl<T> Default for VecPool<T> {
+    fn default() -> Self {
+        Self {
+            buffers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl<T> VecPool<T> {
+    /// 取出一个空缓冲区，池为空时新建
+    pub(crate) fn take(&self) -> Vec<T> {
+        self.lock().pop().unwrap_or_default()
+    }
+
+    /// 清空并归还缓冲区
+    pub(crate) fn put(&self, mut buffer: Vec<T>) {
+        buffer.clear();
+        if buffer.capacity() == 0 {
+            return;
+        }
+        let mut buffers = self.lock();
+        if buffers.len() < MAX_POOLED {
+            buffers.push(buffer);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<T>>> {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 更新所匹配的过滤器名称
+///
+/// 同一个订阅中绝大多数更新的过滤器名称相同，启用复用时与上一次的结果比较，
+/// 相同则共享同一个 `Arc<[String]>`，省去每条更新一次的分配和拷贝。
+pub(crate) struct FilterInterner {
+    enabled: bool,
+    last: Option<Arc<[String]>>,
+}
+
+impl FilterInterner {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            last: None,
+        }
+    }
+
+    pub(crate) fn intern(&mut self, filters: Vec<String>) -> Arc<[String]> {
+        if !self.enabled {
+            return filters.into();
+        }
+        if let Some(last) = &self.last {
+            if last[..] == filters[..] {
+                return Arc::clone(last);
+            }
+        }
+        let filters: Arc<[String]> = filters.into();
+        self.last = Some(Arc::clone(&filters));
+        filters
+    }
+}
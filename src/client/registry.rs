@@ -0,0 +1,510 @@
+use log::{debug, error};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+        Arc, Mutex, RwLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use yellowstone_grpc_proto::geyser::SubscribeUpdate;
+
+use super::{
+    handler::{AccountContext, EventContext, EventHandler},
+    pools::PoolReserves,
+    profiler::{LatencyHistogram, LatencyReport},
+    stats::StreamStats,
+    status::StreamLag,
+    updates::{AccountUpdate, BlockMetaUpdate, EntryUpdate, SlotUpdate, TransactionUpdate},
+};
+use crate::{
+    error::{Error, Result},
+    models::*,
+};
+
+/// 交给独立线程执行的一次回调
+type Job = Box<dyn FnOnce(&dyn EventHandler) + Send>;
+
+/// 注册到 [`HandlerRegistry`] 的处理器标识，用于移除处理器
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+#[derive(Clone)]
+enum Target {
+    /// 在分发线程中直接调用
+    Inline(Arc<dyn EventHandler>),
+    /// 在处理器自己的线程中调用，队列满时丢弃
+    Isolated {
+        jobs: SyncSender<Job>,
+        dropped: Arc<AtomicU64>,
+    },
+}
+
+#[derive(Clone)]
+struct Entry {
+    id: HandlerId,
+    name: Arc<str>,
+    target: Target,
+    /// 调用指标（未启用时为 None）
+    stats: Option<Arc<HandlerStats>>,
+}
+
+/// 单个处理器的调用指标（需要 [`HandlerRegistry::with_metrics`]）
+///
+/// 一次调用（invocation）指注册表调用处理器的一个入口，不论处理器是否覆盖了对应回调的默认空实现：
+///
+/// - 每个事件计一次：逐类型回调（如 `on_trade_event`）和随后的 `on_event` 在同一次调用中执行，
+///   耗时为两者之和
+/// - 其他非事件回调（`on_new_token`、账户、槽位等）每次计一次
+///
+/// 注册表被只转发单个回调的包装器（例如 `EventLanes`）包裹时收不到整体的事件分发，
+/// 逐类型回调和 `on_event` 各计一次。
+#[derive(Clone, Debug)]
+pub struct HandlerMetrics {
+    pub id: HandlerId,
+    /// 处理器名称，未指定时为处理器的类型名
+    pub name: Arc<str>,
+    /// 调用次数，包括 panic 的调用
+    pub invocations: u64,
+    /// panic 的调用次数
+    pub panics: u64,
+    /// 独立线程处理器因队列已满丢弃的回调数，直接调用的处理器总是为 0
+    pub dropped: u64,
+    /// 每次调用的耗时分布
+    pub duration: LatencyHistogram,
+}
+
+#[derive(Default)]
+struct HandlerStats {
+    invocations: AtomicU64,
+    panics: AtomicU64,
+    duration: Mutex<LatencyHistogram>,
+}
+
+impl HandlerStats {
+    fn record(&self, elapsed: Duration, panicked: bool) {
+        self.invocations.fetch_add(1, Ordering::Relaxed);
+        if panicked {
+            self.panics.fetch_add(1, Ordering::Relaxed);
+        }
+        self.lock().record(elapsed);
+    }
+
+    fn reset(&self) {
+        self.invocations.store(0, Ordering::Relaxed);
+        self.panics.store(0, Ordering::Relaxed);
+        *self.lock() = LatencyHistogram::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LatencyHistogram> {
+        self.duration.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 调用处理器并捕获 panic，启用指标时记录调用次数、panic 次数和耗时
+fn invoke(id: HandlerId, stats: Option<&HandlerStats>, call: impl FnOnce()) {
+    let started = stats.map(|_| Instant::now());
+    let panicked = catch_unwind(AssertUnwindSafe(call)).is_err();
+    if panicked {
+        error!("处理器 {:?} panic，已跳过该回调", id);
+    }
+    if let Some((stats, started)) = stats.zip(started) {
+        stats.record(started.elapsed(), panicked);
+    }
+}
+
+/// 多处理器注册表
+///
+/// 本身实现了 `EventHandler`，作为订阅的处理器后把每个回调转发给所有已注册的处理器，
+/// 处理器可以在订阅运行期间随时添加和移除。
+///
+/// 处理器之间相互隔离：
+///
+/// - 处理器 panic 时记录错误日志并继续分发，不影响其他处理器和订阅本身
+/// - [`add_isolated`](Self::add_isolated) 添加的处理器在自己的线程中运行，参数克隆后放入有界队列，
+///   处理慢时只会丢弃它自己的回调（可通过 [`dropped`](Self::dropped) 查询），不会拖慢其他处理器
+///
+/// 用 [`with_metrics`](Self::with_metrics) 启用后，按处理器记录调用次数、panic 次数和耗时分布
+/// （调用的定义见 [`HandlerMetrics`]），
+/// 多处理器部署中可以据此找出拖慢分发的处理器。
+///
+/// 克隆后的实例共享同一份注册表。
+#[derive(Clone, Default)]
+pub struct HandlerRegistry {
+    /// 写时复制：分发时只克隆 `Arc`，回调期间不持有锁，处理器内部也可以增删处理器
+    entries: Arc<RwLock<Arc<Vec<Entry>>>>,
+    next_id: Arc<AtomicU64>,
+    /// 是否为之后添加的处理器记录调用指标
+    metrics: bool,
+}
+
+impl HandlerRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 启用按处理器的调用指标，通过 [`metrics`](Self::metrics) 读取
+    ///
+    /// 只对之后添加的处理器生效，应在添加处理器前设置。每次回调额外读取两次时钟。
+    pub fn with_metrics(mut self, enabled: bool) -> Self {
+        self.metrics = enabled;
+        self
+    }
+
+    /// 添加在分发线程中直接调用的处理器，以处理器的类型名作为名称
+    pub fn add<H: EventHandler + 'static>(&self, handler: H) -> HandlerId {
+        self.add_named(std::any::type_name::<H>(), handler)
+    }
+
+    /// 添加在分发线程中直接调用的处理器，`name` 用于在指标中区分处理器
+    pub fn add_named<H: EventHandler + 'static>(
+        &self,
+        name: impl Into<Arc<str>>,
+        handler: H,
+    ) -> HandlerId {
+        let id = HandlerId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.push(Entry {
+            id,
+            name: name.into(),
+            target: Target::Inline(Arc::new(handler)),
+            stats: self.metrics.then(Arc::default),
+        });
+        id
+    }
+
+    /// 添加在独立线程中运行的处理器，`queue_capacity` 为待处理回调的队列长度（至少为 1）
+    ///
+    /// 回调的参数会被克隆，适合处理较慢（例如写数据库、发送网络请求）的处理器；
+    /// 处理器被移除后，线程处理完已入队的回调后退出。以处理器的类型名作为名称。
+    pub fn add_isolated<H: EventHandler + 'static>(
+        &self,
+        handler: H,
+        queue_capacity: usize,
+    ) -> Result<HandlerId> {
+        self.add_isolated_named(std::any::type_name::<H>(), handler, queue_capacity)
+    }
+
+    /// 添加在独立线程中运行的处理器，`name` 用于在指标中区分处理器
+    pub fn add_isolated_named<H: EventHandler + 'static>(
+        &self,
+        name: impl Into<Arc<str>>,
+        handler: H,
+        queue_capacity: usize,
+    ) -> Result<HandlerId> {
+        let (jobs, rx) = mpsc::sync_channel::<Job>(queue_capacity.max(1));
+        let id = HandlerId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let stats: Option<Arc<HandlerStats>> = self.metrics.then(Arc::default);
+        let worker_stats = stats.clone();
+        thread::Builder::new()
+            .name(format!("pump-handler-{}", id.0))
+            .spawn(move || {
+                for job in rx {
+                    invoke(id, worker_stats.as_deref(), || job(&handler));
+                }
+            })
+            .map_err(|e| Error::Unknown(format!("启动处理器线程失败: {e}")))?;
+        let dropped = Arc::new(AtomicU64::new(0));
+        self.push(Entry {
+            id,
+            name: name.into(),
+            target: Target::Isolated { jobs, dropped },
+            stats,
+        });
+        Ok(id)
+    }
+
+    /// 移除处理器，处理器不存在时返回 false
+    pub fn remove(&self, id: HandlerId) -> bool {
+        let mut entries = self.write();
+        if !entries.iter().any(|entry| entry.id == id) {
+            return false;
+        }
+        let remaining = entries
+            .iter()
+            .filter(|entry| entry.id != id)
+            .cloned()
+            .collect();
+        *entries = Arc::new(remaining);
+        true
+    }
+
+    /// 独立线程处理器因队列已满丢弃的回调数，处理器不存在或不是独立线程处理器时返回 None
+    pub fn dropped(&self, id: HandlerId) -> Option<u64> {
+        self.snapshot()
+            .iter()
+            .find(|entry| entry.id == id)
+            .and_then(|entry| match &entry.target {
+                Target::Isolated { dropped, .. } => Some(dropped.load(Ordering::Relaxed)),
+                Target::Inline(_) => None,
+            })
+    }
+
+    /// 各处理器的调用指标（自启用或上次重置以来的累计值），按添加顺序排列
+    ///
+    /// 未启用指标时返回空列表。
+    pub fn metrics(&self) -> Vec<HandlerMetrics> {
+        self.snapshot()
+            .iter()
+            .filter_map(|entry| {
+                let stats = entry.stats.as_ref()?;
+                Some(HandlerMetrics {
+                    id: entry.id,
+                    name: Arc::clone(&entry.name),
+                    invocations: stats.invocations.load(Ordering::Relaxed),
+                    panics: stats.panics.load(Ordering::Relaxed),
+                    dropped: match &entry.target {
+                        Target::Isolated { dropped, .. } => dropped.load(Ordering::Relaxed),
+                        Target::Inline(_) => 0,
+                    },
+                    duration: stats.lock().clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// 清零所有处理器的调用指标，可配合 [`metrics`](Self::metrics) 按周期统计
+    pub fn reset_metrics(&self) {
+        for entry in self.snapshot().iter() {
+            if let Some(stats) = &entry.stats {
+                stats.reset();
+            }
+            if let Target::Isolated { dropped, .. } = &entry.target {
+                dropped.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// 已注册的处理器数量
+    pub fn len(&self) -> usize {
+        self.snapshot().len()
+    }
+
+    /// 是否没有注册处理器
+    pub fn is_empty(&self) -> bool {
+        self.snapshot().is_empty()
+    }
+
+    fn push(&self, entry: Entry) {
+        let mut entries = self.write();
+        let mut updated = Vec::with_capacity(entries.len() + 1);
+        updated.extend(entries.iter().cloned());
+        updated.push(entry);
+        *entries = Arc::new(updated);
+    }
+
+    /// 把一次回调转发给所有处理器
+    ///
+    /// `call` 用于直接调用的处理器；`job` 为每个独立线程处理器生成一份持有克隆参数的回调。
+    fn broadcast<C, J>(&self, call: C, job: J)
+    where
+        C: Fn(&dyn EventHandler),
+        J: Fn() -> Job,
+    {
+        for entry in self.snapshot().iter() {
+            match &entry.target {
+                Target::Inline(handler) => {
+                    invoke(entry.id, entry.stats.as_deref(), || call(handler.as_ref()));
+                }
+                Target::Isolated { jobs, dropped } => match jobs.try_send(job()) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        if dropped.fetch_add(1, Ordering::Relaxed) == 0 {
+                            debug!("处理器 {:?} 的队列已满，开始丢弃回调", entry.id);
+                        }
+                    }
+                    // 线程已退出（不会发生，除非线程被外部终止）
+                    Err(TrySendError::Disconnected(_)) => {}
+                },
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Arc<Vec<Entry>> {
+        Arc::clone(&self.entries.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Arc<Vec<Entry>>> {
+        self.entries.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 生成把回调转发给所有处理器的 `EventHandler` 方法，参数均按引用传入并在需要时克隆
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method(&self, $($arg: &$ty),*) {
+                self.broadcast(
+                    |handler| handler.$method($($arg),*),
+                    || {
+                        $(let $arg = $arg.clone();)*
+                        Box::new(move |handler: &dyn EventHandler| handler.$method($(&$arg),*))
+                    },
+                );
+            }
+        )*
+    };
+}
+
+impl EventHandler for HandlerRegistry {
+    // 事件经 `dispatch` 整体转发；单独的逐类型回调和 `on_event` 只在注册表被其他包装器包裹时调用
+    forward! {
+        on_create_event(event: CreateEvent, ctx: EventContext);
+        on_create_v2_event(event: CreateV2Event, ctx: EventContext);
+        on_complete_event(event: CompleteEvent, ctx: EventContext);
+        on_trade_event(event: TradeEvent, ctx: EventContext);
+        on_buy_event(event: BuyEvent, ctx: EventContext);
+        on_sell_event(event: SellEvent, ctx: EventContext);
+        on_create_pool_event(event: CreatePoolEvent, ctx: EventContext);
+        on_set_params_event(event: SetParamsEvent, ctx: EventContext);
+        on_update_global_authority_event(event: UpdateGlobalAuthorityEvent, ctx: EventContext);
+        on_set_creator_event(event: SetCreatorEvent, ctx: EventContext);
+        on_set_metaplex_creator_event(event: SetMetaplexCreatorEvent, ctx: EventContext);
+        on_deposit_event(event: DepositEvent, ctx: EventContext);
+        on_withdraw_event(event: WithdrawEvent, ctx: EventContext);
+        on_collect_creator_fee_event(event: CollectCreatorFeeEvent, ctx: EventContext);
+        on_collect_coin_creator_fee_event(event: CollectCoinCreatorFeeEvent, ctx: EventContext);
+        on_migrate_event(event: MigrateEvent, ctx: EventContext);
+        on_event(event: PumpEvent, ctx: EventContext);
+        dispatch(event: PumpEvent, ctx: EventContext);
+        on_new_token(token: NewToken, ctx: EventContext);
+        on_bonding_curve_update(mint: Pubkey, curve: BondingCurveAccount, ctx: AccountContext);
+        on_pool_reserves(reserves: PoolReserves, ctx: AccountContext);
+        on_account_update(account: AccountUpdate, ctx: AccountContext);
+        on_program_account(account: ProgramAccount, ctx: AccountContext);
+        on_slot_update(update: SlotUpdate);
+        on_block_meta(meta: BlockMetaUpdate);
+        on_entry(entry: EntryUpdate);
+        on_lag(lag: StreamLag);
+        on_latency_report(report: LatencyReport);
+        on_stats(stats: StreamStats);
+        on_raw_update(update: SubscribeUpdate);
+    }
+
+    fn on_slot_complete(&self, slot: u64, events: &[(PumpEvent, EventContext)]) {
+        self.broadcast(
+            |handler| handler.on_slot_complete(slot, events),
+            || {
+                let events = events.to_vec();
+                Box::new(move |handler: &dyn EventHandler| handler.on_slot_complete(slot, &events))
+            },
+        );
+    }
+
+    fn on_transaction(&self, tx: &TransactionUpdate, events: &[PumpEvent], ctx: &EventContext) {
+        self.broadcast(
+            |handler| handler.on_transaction(tx, events, ctx),
+            || {
+                let (tx, events, ctx) = (tx.clone(), events.to_vec(), ctx.clone());
+                Box::new(move |handler: &dyn EventHandler| {
+                    handler.on_transaction(&tx, &events, &ctx)
+                })
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::handler::dispatch_event;
+    use solana_sdk::signature::Signature;
+    use std::time::SystemTime;
+    use yellowstone_grpc_proto::geyser::SubscribeUpdateSlot;
+
+    struct PanicOnTrade;
+
+    impl EventHandler for PanicOnTrade {
+        fn on_trade_event(&self, _event: &TradeEvent, _ctx: &EventContext) {
+            panic!("trade");
+        }
+    }
+
+    struct SlowTrade;
+
+    impl EventHandler for SlowTrade {
+        fn on_trade_event(&self, _event: &TradeEvent, _ctx: &EventContext) {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        trades: AtomicU64,
+        events: AtomicU64,
+    }
+
+    impl EventHandler for Arc<Counter> {
+        fn on_trade_event(&self, _event: &TradeEvent, _ctx: &EventContext) {
+            self.trades.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_event(&self, _event: &PumpEvent, _ctx: &EventContext) {
+            self.events.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn ctx() -> EventContext {
+        EventContext {
+            slot: 1,
+            tx_index: 0,
+            signature: Signature::default(),
+            timestamp: Instant::now(),
+            elapsed: Default::default(),
+            received_at: SystemTime::now(),
+            block_time: None,
+            filters: Arc::from(Vec::new()),
+            unknown_tail: None,
+        }
+    }
+
+    #[test]
+    fn each_event_counts_as_one_invocation() {
+        let registry = HandlerRegistry::new().with_metrics(true);
+        registry.add_named("noop", ());
+        registry.add_named("panicky", PanicOnTrade);
+
+        let trade = PumpEvent::Trade(TradeEvent::default());
+        dispatch_event(&registry, &trade, &ctx());
+        dispatch_event(&registry, &PumpEvent::Buy(BuyEvent::default()), &ctx());
+        registry.on_slot_update(&SlotUpdate::from(SubscribeUpdateSlot::default()));
+
+        let metrics = registry.metrics();
+        assert_eq!(metrics.len(), 2);
+        assert_eq!((metrics[0].invocations, metrics[0].panics), (3, 0));
+        assert_eq!((metrics[1].invocations, metrics[1].panics), (3, 1));
+
+        registry.reset_metrics();
+        assert_eq!(registry.metrics()[1].invocations, 0);
+    }
+
+    #[test]
+    fn typed_callback_time_is_measured() {
+        let registry = HandlerRegistry::new().with_metrics(true);
+        registry.add(SlowTrade);
+        dispatch_event(&registry, &PumpEvent::Trade(TradeEvent::default()), &ctx());
+
+        let metrics = registry.metrics();
+        assert_eq!(metrics[0].invocations, 1);
+        assert!(metrics[0].duration.max() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn isolated_handlers_receive_both_callbacks_from_one_job() {
+        let registry = HandlerRegistry::new().with_metrics(true);
+        let counter = Arc::new(Counter::default());
+        registry.add_isolated(Arc::clone(&counter), 16).unwrap();
+        dispatch_event(&registry, &PumpEvent::Trade(TradeEvent::default()), &ctx());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while registry.metrics()[0].invocations == 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(registry.metrics()[0].invocations, 1);
+        assert_eq!(counter.trades.load(Ordering::Relaxed), 1);
+        assert_eq!(counter.events.load(Ordering::Relaxed), 1);
+    }
+}
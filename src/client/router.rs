@@ -0,0 +1,277 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::{
+    handler::{AccountContext, EventContext, EventHandler},
+    pools::PoolReserves,
+};
+use crate::models::*;
+
+/// 默认的不活跃时长，超过后释放该 mint 的处理器
+const DEFAULT_INACTIVE_AFTER: Duration = Duration::from_secs(1800);
+
+/// 两次清理不活跃处理器之间的最短间隔（链上秒）
+const SWEEP_INTERVAL: i64 = 60;
+
+struct Route<H> {
+    handler: Arc<H>,
+    /// 最近一次事件的链上时间
+    last_event: i64,
+}
+
+struct State<H> {
+    routes: HashMap<Pubkey, Route<H>>,
+    /// PumpAmm 池 -> base mint
+    pools: HashMap<Pubkey, Pubkey>,
+    /// 已观察到的最新链上时间
+    now: i64,
+    last_sweep: i64,
+}
+
+/// 按 mint 路由事件的处理器容器
+///
+/// 每个 mint 拥有一个独立的处理器实例，在该 mint 的第一个事件到达时由工厂函数创建，
+/// 超过不活跃时长（默认 30 分钟，按事件中的链上时间计算）没有新事件时释放。
+/// 维护单个代币状态的策略可以直接写成普通的 `EventHandler`，不必在一个处理器里按 mint 分发。
+///
+/// PumpAmm 的 Buy / Sell 事件只有池地址，池到 mint 的对应关系从 CreatePool 事件中学习，
+/// 订阅开始前已存在的池需要通过 [`map_pool`](Self::map_pool) 预先登记，否则这些池的交易会被忽略。
+///
+/// 处理器在释放锁之后调用，可以安全地访问路由器自身。克隆后的实例共享同一份状态。
+pub struct EventRouter<F, H> {
+    factory: Arc<F>,
+    inactive_after: Duration,
+    state: Arc<Mutex<State<H>>>,
+}
+
+impl<F, H> Clone for EventRouter<F, H> {
+    fn clone(&self) -> Self {
+        Self {
+            factory: Arc::clone(&self.factory),
+            inactive_after: self.inactive_after,
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<F, H> EventRouter<F, H>
+where
+    F: Fn(&Pubkey) -> H + Send + Sync,
+    H: EventHandler,
+{
+    /// 创建路由器，`factory` 为新出现的 mint 创建处理器
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory: Arc::new(factory),
+            inactive_after: DEFAULT_INACTIVE_AFTER,
+            state: Arc::new(Mutex::new(State {
+                routes: HashMap::new(),
+                pools: HashMap::new(),
+                now: 0,
+                last_sweep: 0,
+            })),
+        }
+    }
+
+    /// 设置不活跃时长，超过该时长没有事件的 mint 的处理器会被释放
+    pub fn with_inactive_after(mut self, duration: Duration) -> Self {
+        self.inactive_after = duration;
+        self
+    }
+
+    /// 登记 PumpAmm 池对应的 mint
+    pub fn map_pool(&self, pool: Pubkey, mint: Pubkey) {
+        self.lock().pools.insert(pool, mint);
+    }
+
+    /// mint 当前的处理器实例
+    pub fn handler(&self, mint: &Pubkey) -> Option<Arc<H>> {
+        self.lock()
+            .routes
+            .get(mint)
+            .map(|route| Arc::clone(&route.handler))
+    }
+
+    /// 立即释放 mint 的处理器，之后的事件会创建新的实例
+    pub fn remove(&self, mint: &Pubkey) -> Option<Arc<H>> {
+        self.lock().routes.remove(mint).map(|route| route.handler)
+    }
+
+    /// 当前持有处理器的 mint 数量
+    pub fn len(&self) -> usize {
+        self.lock().routes.len()
+    }
+
+    /// 是否没有持有任何处理器
+    pub fn is_empty(&self) -> bool {
+        self.lock().routes.is_empty()
+    }
+
+    /// 取得（必要时创建）mint 的处理器，`timestamp` 为事件的链上时间，账户更新没有链上时间时为 None
+    fn route(&self, mint: &Pubkey, timestamp: Option<i64>) -> Arc<H> {
+        let mut state = self.lock();
+        if let Some(timestamp) = timestamp {
+            state.now = state.now.max(timestamp);
+        }
+        let now = state.now;
+        let released = self.sweep(&mut state);
+        let route = state.routes.entry(*mint).or_insert_with(|| Route {
+            handler: Arc::new((self.factory)(mint)),
+            last_event: now,
+        });
+        route.last_event = route.last_event.max(timestamp.unwrap_or(now));
+        let handler = Arc::clone(&route.handler);
+        drop(state);
+        // 释放的处理器在锁外析构，处理器的 Drop 可以安全地访问路由器
+        drop(released);
+        handler
+    }
+
+    /// 池事件对应的处理器，池未登记时返回 None
+    fn route_pool(&self, pool: &Pubkey, timestamp: Option<i64>) -> Option<Arc<H>> {
+        let mint = *self.lock().pools.get(pool)?;
+        Some(self.route(&mint, timestamp))
+    }
+
+    /// 移除不活跃的 mint，返回被移除的处理器
+    fn sweep(&self, state: &mut State<H>) -> Vec<Route<H>> {
+        let now = state.now;
+        if now - state.last_sweep < SWEEP_INTERVAL {
+            return Vec::new();
+        }
+        state.last_sweep = now;
+        let cutoff = now - self.inactive_after.as_secs() as i64;
+        let expired: Vec<Pubkey> = state
+            .routes
+            .iter()
+            .filter(|(_, route)| route.last_event < cutoff)
+            .map(|(mint, _)| *mint)
+            .collect();
+        expired
+            .iter()
+            .filter_map(|mint| state.routes.remove(mint))
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State<H>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<F, H> EventHandler for EventRouter<F, H>
+where
+    F: Fn(&Pubkey) -> H + Send + Sync,
+    H: EventHandler,
+{
+    fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
+        self.route(&event.mint, Some(event.timestamp))
+            .on_create_event(event, ctx);
+    }
+
+    fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
+        self.route(&event.mint, Some(event.timestamp))
+            .on_create_v2_event(event, ctx);
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
+        self.route(&event.mint, Some(event.timestamp))
+            .on_complete_event(event, ctx);
+    }
+
+    fn on_trade_event(&self, event: &TradeEvent, ctx: &EventContext) {
+        self.route(&event.mint, Some(event.timestamp))
+            .on_trade_event(event, ctx);
+    }
+
+    fn on_set_creator_event(&self, event: &SetCreatorEvent, ctx: &EventContext) {
+        self.route(&event.mint, Some(event.timestamp))
+            .on_set_creator_event(event, ctx);
+    }
+
+    fn on_set_metaplex_creator_event(&self, event: &SetMetaplexCreatorEvent, ctx: &EventContext) {
+        self.route(&event.mint, Some(event.timestamp))
+            .on_set_metaplex_creator_event(event, ctx);
+    }
+
+    fn on_new_token(&self, token: &NewToken, ctx: &EventContext) {
+        self.route(&token.mint, ctx.block_time)
+            .on_new_token(token, ctx);
+    }
+
+    fn on_create_pool_event(&self, event: &CreatePoolEvent, ctx: &EventContext) {
+        self.map_pool(event.pool, event.base_mint);
+        self.route(&event.base_mint, Some(event.timestamp))
+            .on_create_pool_event(event, ctx);
+    }
+
+    fn on_migrate_event(&self, event: &MigrateEvent, ctx: &EventContext) {
+        self.map_pool(event.pool, event.mint);
+        self.route(&event.mint, Some(event.timestamp))
+            .on_migrate_event(event, ctx);
+    }
+
+    fn on_buy_event(&self, event: &BuyEvent, ctx: &EventContext) {
+        if let Some(handler) = self.route_pool(&event.pool, Some(event.timestamp)) {
+            handler.on_buy_event(event, ctx);
+        }
+    }
+
+    fn on_sell_event(&self, event: &SellEvent, ctx: &EventContext) {
+        if let Some(handler) = self.route_pool(&event.pool, Some(event.timestamp)) {
+            handler.on_sell_event(event, ctx);
+        }
+    }
+
+    fn on_deposit_event(&self, event: &DepositEvent, ctx: &EventContext) {
+        if let Some(handler) = self.route_pool(&event.pool, Some(event.timestamp)) {
+            handler.on_deposit_event(event, ctx);
+        }
+    }
+
+    fn on_withdraw_event(&self, event: &WithdrawEvent, ctx: &EventContext) {
+        if let Some(handler) = self.route_pool(&event.pool, Some(event.timestamp)) {
+            handler.on_withdraw_event(event, ctx);
+        }
+    }
+
+    fn on_event(&self, event: &PumpEvent, ctx: &EventContext) {
+        let timestamp = Some(event.timestamp());
+        let handler = match (event.pool(), event.mint()) {
+            (Some(pool), _) => self.route_pool(&pool, timestamp),
+            (None, Some(mint)) => Some(self.route(&mint, timestamp)),
+            (None, None) => None,
+        };
+        if let Some(handler) = handler {
+            handler.on_event(event, ctx);
+        }
+    }
+
+    fn on_bonding_curve_update(
+        &self,
+        mint: &Pubkey,
+        curve: &BondingCurveAccount,
+        ctx: &AccountContext,
+    ) {
+        self.route(mint, None)
+            .on_bonding_curve_update(mint, curve, ctx);
+    }
+
+    fn on_pool_reserves(&self, reserves: &PoolReserves, ctx: &AccountContext) {
+        self.map_pool(reserves.pool, reserves.base_mint);
+        self.route(&reserves.base_mint, None)
+            .on_pool_reserves(reserves, ctx);
+    }
+
+    // 池账户按 base mint 路由，bonding curve 账户不包含 mint，与其他账户一样不分发
+    fn on_program_account(&self, account: &ProgramAccount, ctx: &AccountContext) {
+        if let ProgramAccount::Pool(pool) = account {
+            self.map_pool(ctx.pubkey, pool.base_mint);
+            self.route(&pool.base_mint, None)
+                .on_program_account(account, ctx);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+use yellowstone_grpc_proto::geyser::{SubscribeRequest, SubscribeRequestPing};
+
+/// 一个统计周期内的订阅流统计
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// 订阅使用的 gRPC 端点
+    pub endpoint: String,
+    /// 统计周期长度
+    pub interval: Duration,
+    /// 收到的消息数
+    pub messages: u64,
+    /// 收到的消息字节数（按 protobuf 编码长度计算，不含 gRPC 帧头和压缩）
+    pub bytes_received: u64,
+    /// 解码失败的事件数
+    pub decode_failures: u64,
+    /// 最近一次 ping 的往返时间，周期内没有收到 pong 时为 None
+    pub ping_rtt: Option<Duration>,
+}
+
+impl StreamStats {
+    /// 每秒消息数
+    pub fn messages_per_sec(&self) -> f64 {
+        per_sec(self.messages, self.interval)
+    }
+
+    /// 每秒字节数
+    pub fn bytes_per_sec(&self) -> f64 {
+        per_sec(self.bytes_received, self.interval)
+    }
+}
+
+fn per_sec(count: u64, interval: Duration) -> f64 {
+    let secs = interval.as_secs_f64();
+    if secs > 0.0 {
+        count as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// 单个订阅的流统计记录
+///
+/// 只在订阅循环中使用，不跨线程共享。
+pub(crate) struct StatsRecorder {
+    report_interval: Duration,
+    started: Instant,
+    stats: StreamStats,
+    next_ping_id: i32,
+    /// 等待 pong 的 ping（id, 发送时间）
+    pending_ping: Option<(i32, Instant)>,
+}
+
+impl StatsRecorder {
+    pub(crate) fn new(endpoint: &str, report_interval: Duration) -> Self {
+        Self {
+            report_interval,
+            started: Instant::now(),
+            stats: StreamStats {
+                endpoint: endpoint.to_string(),
+                ..Default::default()
+            },
+            next_ping_id: 1,
+            pending_ping: None,
+        }
+    }
+
+    /// 记录收到一条消息
+    #[inline]
+    pub(crate) fn on_message(&mut self, bytes: usize) {
+        self.stats.messages += 1;
+        self.stats.bytes_received += bytes as u64;
+    }
+
+    /// 记录解码失败的事件
+    #[inline]
+    pub(crate) fn on_decode_failures(&mut self, count: usize) {
+        self.stats.decode_failures += count as u64;
+    }
+
+    /// 生成一个带新 id 的 ping 请求并记录发送时间，上一个 ping 未收到 pong 时不再计时
+    pub(crate) fn ping_request(&mut self) -> SubscribeRequest {
+        let id = self.next_ping_id;
+        self.next_ping_id = self.next_ping_id.wrapping_add(1).max(1);
+        self.pending_ping = Some((id, Instant::now()));
+        SubscribeRequest {
+            ping: Some(SubscribeRequestPing { id }),
+            ..Default::default()
+        }
+    }
+
+    /// 收到 pong 时计算往返时间
+    pub(crate) fn on_pong(&mut self, id: i32) {
+        if let Some((pending, sent_at)) = self.pending_ping {
+            if pending == id {
+                self.stats.ping_rtt = Some(sent_at.elapsed());
+                self.pending_ping = None;
+            }
+        }
+    }
+
+    /// 周期已结束时取出统计并开始新的周期
+    pub(crate) fn take_due(&mut self) -> Option<StreamStats> {
+        let interval = self.started.elapsed();
+        if interval < self.report_interval {
+            return None;
+        }
+        self.started = Instant::now();
+        let next = StreamStats {
+            endpoint: self.stats.endpoint.clone(),
+            ..Default::default()
+        };
+        let stats = std::mem::replace(&mut self.stats, next);
+        Some(StreamStats { interval, ..stats })
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// 连接状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// 至少有一个订阅处于连接中
+    Connected,
+    /// 没有活跃的订阅
+    Disconnected,
+}
+
+/// 事件流相对链上最新槽位的延迟
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamLag {
+    /// 已观察到的最高槽位与事件所在槽位之差
+    pub slots: u64,
+    /// 分发时的系统时间与事件链上时间之差（毫秒，链上时间精度为秒）
+    pub millis: u64,
+}
+
+/// 客户端状态快照，可用于服务的健康检查接口
+#[derive(Clone, Debug)]
+pub struct ClientStatus {
+    /// 连接状态
+    pub state: ConnectionState,
+    /// 使用的 gRPC 端点
+    pub endpoint: String,
+    /// 活跃的订阅数量
+    pub active_subscriptions: usize,
+    /// 最近一次收到消息的系统时间
+    pub last_message_at: Option<SystemTime>,
+    /// 已观察到的最高槽位
+    pub last_slot: Option<u64>,
+    /// 客户端（包括其克隆）的所有订阅累计成功建立的连接数
+    ///
+    /// 每个订阅建立连接时加一，同时运行的多个订阅各计一次，不代表重连次数。
+    pub connects: u64,
+    /// 最近一次分发事件时的延迟，尚未分发事件时为 None
+    pub lag: Option<StreamLag>,
+}
+
+/// 客户端内部状态记录，所有克隆的客户端共享同一份
+#[derive(Default)]
+pub(crate) struct StatusTracker {
+    active: AtomicUsize,
+    connects: AtomicU64,
+    last_slot: AtomicU64,
+    /// 最近一次收到消息的 Unix 毫秒时间，0 表示尚未收到
+    last_message_ms: AtomicU64,
+    lag_slots: AtomicU64,
+    lag_ms: AtomicU64,
+    /// 是否已记录过延迟
+    lag_recorded: AtomicBool,
+    /// 延迟是否处于超过阈值的状态
+    lagging: AtomicBool,
+}
+
+impl StatusTracker {
+    /// 记录一次成功连接，返回的守卫在订阅结束（drop）时把连接标记为断开
+    pub(crate) fn connected(&self) -> ConnectionGuard<'_> {
+        self.connects.fetch_add(1, Ordering::Relaxed);
+        self.active.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard { tracker: self }
+    }
+
+    /// 记录收到一条消息
+    #[inline]
+    pub(crate) fn on_message(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        self.last_message_ms.store(now, Ordering::Relaxed);
+    }
+
+    /// 记录观察到的槽位
+    #[inline]
+    pub(crate) fn on_slot(&self, slot: u64) {
+        self.last_slot.fetch_max(slot, Ordering::Relaxed);
+    }
+
+    /// 已观察到的最高槽位，尚未观察到时为 0
+    #[inline]
+    pub(crate) fn last_slot(&self) -> u64 {
+        self.last_slot.load(Ordering::Relaxed)
+    }
+
+    /// 记录分发事件时的延迟
+    ///
+    /// 延迟从阈值以下变为超过阈值时返回 `Some`，在恢复到阈值以下之前不会再次返回。
+    pub(crate) fn on_dispatch(
+        &self,
+        slot: u64,
+        block_time: i64,
+        threshold: Option<u64>,
+    ) -> Option<StreamLag> {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let lag = StreamLag {
+            slots: self.last_slot.load(Ordering::Relaxed).saturating_sub(slot),
+            millis: now_ms.saturating_sub(block_time.max(0) as u64 * 1000),
+        };
+        self.lag_slots.store(lag.slots, Ordering::Relaxed);
+        self.lag_ms.store(lag.millis, Ordering::Relaxed);
+        self.lag_recorded.store(true, Ordering::Relaxed);
+
+        let threshold = threshold?;
+        if lag.slots > threshold {
+            (!self.lagging.swap(true, Ordering::Relaxed)).then_some(lag)
+        } else {
+            self.lagging.store(false, Ordering::Relaxed);
+            None
+        }
+    }
+
+    pub(crate) fn snapshot(&self, endpoint: &str) -> ClientStatus {
+        let active = self.active.load(Ordering::Relaxed);
+        let last_message_ms = self.last_message_ms.load(Ordering::Relaxed);
+        let last_slot = self.last_slot.load(Ordering::Relaxed);
+        ClientStatus {
+            state: if active > 0 {
+                ConnectionState::Connected
+            } else {
+                ConnectionState::Disconnected
+            },
+            endpoint: endpoint.to_string(),
+            active_subscriptions: active,
+            last_message_at: (last_message_ms > 0)
+                .then(|| UNIX_EPOCH + Duration::from_millis(last_message_ms)),
+            last_slot: (last_slot > 0).then_some(last_slot),
+            connects: self.connects.load(Ordering::Relaxed),
+            lag: self
+                .lag_recorded
+                .load(Ordering::Relaxed)
+                .then(|| StreamLag {
+                    slots: self.lag_slots.load(Ordering::Relaxed),
+                    millis: self.lag_ms.load(Ordering::Relaxed),
+                }),
+        }
+    }
+}
+
+/// 连接守卫，drop 时减少活跃订阅计数
+pub(crate) struct ConnectionGuard<'a> {
+    tracker: &'a StatusTracker,
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.tracker.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,101 @@
+use futures_util::Stream;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc;
+
+use super::{
+    handler::{EventContext, EventHandler},
+    subscription::Subscription,
+};
+use crate::{error::Result, models::PumpEvent};
+
+/// `GrpcClient::subscribe_stream` 使用的默认队列长度
+pub const DEFAULT_STREAM_CAPACITY: usize = 10_000;
+
+/// 把事件转交给 [`EventStream`] 的处理器
+pub(crate) struct ChannelHandler {
+    events: mpsc::Sender<(PumpEvent, EventContext)>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl ChannelHandler {
+    /// 创建队列长度为 `capacity`（至少为 1）的处理器，返回处理器和队列的接收端
+    pub(crate) fn new(capacity: usize) -> (Self, mpsc::Receiver<(PumpEvent, EventContext)>) {
+        let (events, rx) = mpsc::channel(capacity.max(1));
+        let handler = Self {
+            events,
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        (handler, rx)
+    }
+
+    /// 丢弃计数，处理器交给订阅后用于构建 [`EventStream`]
+    pub(crate) fn dropped(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.dropped)
+    }
+}
+
+impl EventHandler for ChannelHandler {
+    fn on_event(&self, event: &PumpEvent, ctx: &EventContext) {
+        if self.events.try_send((event.clone(), ctx.clone())).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// 以 `Stream` 形式交付事件的订阅
+///
+/// 由 `GrpcClient::subscribe_stream*` 返回，可以在 `select!` 循环中与其他 future 组合，
+/// 不需要实现 `EventHandler`。事件按分发顺序经由有界队列交付，队列已满时丢弃新事件
+/// （可通过 [`dropped`](Self::dropped) 查询），不会阻塞订阅的接收循环。
+///
+/// 订阅结束后流在交付完已入队的事件后结束，[`finish`](Self::finish) 得到订阅的最终结果。
+/// 流被丢弃时订阅随之中止。
+pub struct EventStream {
+    events: mpsc::Receiver<(PumpEvent, EventContext)>,
+    subscription: Subscription,
+    dropped: Arc<AtomicU64>,
+}
+
+impl EventStream {
+    pub(crate) fn new(
+        events: mpsc::Receiver<(PumpEvent, EventContext)>,
+        subscription: Subscription,
+        dropped: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            events,
+            subscription,
+            dropped,
+        }
+    }
+
+    /// 订阅句柄，可用于查询状态、查看生效的过滤器或中止订阅
+    pub fn subscription(&self) -> &Subscription {
+        &self.subscription
+    }
+
+    /// 因队列已满丢弃的事件数
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// 等待订阅结束并得到结果，尚未读取的事件被丢弃
+    pub async fn finish(self) -> Result<()> {
+        self.subscription.await
+    }
+}
+
+impl Stream for EventStream {
+    type Item = (PumpEvent, EventContext);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_recv(cx)
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{sync::watch, task::JoinHandle};
+use yellowstone_grpc_proto::geyser::SubscribeRequest;
+
+use crate::error::{Error, Result};
+
+/// 运行中的订阅
+///
+/// 由 `GrpcClient::subscribe*` 返回，订阅在后台任务中运行。句柄本身是一个 future，
+/// `.await` 等待订阅结束并得到结果（流正常结束为 `Ok(())`，连接或订阅失败为错误）；
+/// 也可以通过 [`abort`](Self::abort) 主动结束订阅。
+///
+/// 句柄被丢弃时订阅随之中止，与直接丢弃订阅 future 的行为一致。
+pub struct Subscription {
+    task: JoinHandle<Result<()>>,
+    filter: watch::Receiver<SubscribeRequest>,
+}
+
+impl Subscription {
+    pub(crate) fn new(
+        task: JoinHandle<Result<()>>,
+        filter: watch::Receiver<SubscribeRequest>,
+    ) -> Self {
+        Self { task, filter }
+    }
+
+    /// 中止订阅，之后 `.await` 返回 `Ok(())`
+    pub fn abort(&self) {
+        self.task.abort();
+    }
+
+    /// 订阅是否仍在运行
+    pub fn is_active(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// 当前生效的订阅请求
+    ///
+    /// 包括默认的承诺级别以及观察列表、池储备跟踪、账户缓存合并进来的过滤器，
+    /// 这些组件变化后重新发送请求时同步更新。
+    pub fn filter(&self) -> SubscribeRequest {
+        self.filter.borrow().clone()
+    }
+}
+
+impl Future for Subscription {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.task).poll(cx).map(|joined| match joined {
+            Ok(result) => result,
+            Err(e) if e.is_cancelled() => Ok(()),
+            Err(e) => Err(Error::SubscribeError(format!("订阅任务异常退出: {e}"))),
+        })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
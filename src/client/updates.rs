@@ -0,0 +1,275 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::TransactionError};
+use std::str::FromStr;
+use yellowstone_grpc_proto::{
+    convert_from::create_tx_error,
+    geyser::{
+        SlotStatus, SubscribeUpdateAccountInfo, SubscribeUpdateBlockMeta, SubscribeUpdateEntry,
+        SubscribeUpdateSlot,
+    },
+    prelude::{Message, TokenBalance as ProtoTokenBalance, TransactionStatusMeta},
+};
+
+/// 账户更新
+#[derive(Clone, Debug)]
+pub struct AccountUpdate {
+    /// 账户地址
+    pub pubkey: Pubkey,
+    /// 账户所属程序
+    pub owner: Pubkey,
+    /// 账户余额（lamports）
+    pub lamports: u64,
+    /// 账户数据
+    pub data: Vec<u8>,
+    /// 是否为可执行账户
+    pub executable: bool,
+    /// 租金纪元
+    pub rent_epoch: u64,
+    /// 是否为订阅建立时推送的初始快照
+    pub is_startup: bool,
+}
+
+impl AccountUpdate {
+    pub(crate) fn from_proto(info: SubscribeUpdateAccountInfo, is_startup: bool) -> Option<Self> {
+        Some(Self {
+            pubkey: Pubkey::try_from(info.pubkey.as_slice()).ok()?,
+            owner: Pubkey::try_from(info.owner.as_slice()).ok()?,
+            lamports: info.lamports,
+            data: info.data,
+            executable: info.executable,
+            rent_epoch: info.rent_epoch,
+            is_startup,
+        })
+    }
+}
+
+/// 槽位状态更新
+#[derive(Clone, Debug)]
+pub struct SlotUpdate {
+    /// 槽位
+    pub slot: u64,
+    /// 父槽位
+    pub parent: Option<u64>,
+    /// 槽位状态
+    pub status: SlotStatus,
+    /// 槽位被标记为 dead 时的错误信息
+    pub dead_error: Option<String>,
+}
+
+impl From<SubscribeUpdateSlot> for SlotUpdate {
+    fn from(update: SubscribeUpdateSlot) -> Self {
+        Self {
+            slot: update.slot,
+            parent: update.parent,
+            status: SlotStatus::try_from(update.status).unwrap_or(SlotStatus::SlotProcessed),
+            dead_error: update.dead_error,
+        }
+    }
+}
+
+/// 区块元数据更新
+#[derive(Clone, Debug)]
+pub struct BlockMetaUpdate {
+    /// 槽位
+    pub slot: u64,
+    /// 区块哈希
+    pub blockhash: String,
+    /// 区块时间（Unix 秒）
+    pub block_time: Option<i64>,
+    /// 区块高度
+    pub block_height: Option<u64>,
+    /// 父槽位
+    pub parent_slot: u64,
+    /// 父区块哈希
+    pub parent_blockhash: String,
+    /// 区块内执行的交易数量
+    pub executed_transaction_count: u64,
+    /// 区块内 entry 数量
+    pub entries_count: u64,
+}
+
+impl From<SubscribeUpdateBlockMeta> for BlockMetaUpdate {
+    fn from(meta: SubscribeUpdateBlockMeta) -> Self {
+        Self {
+            slot: meta.slot,
+            blockhash: meta.blockhash,
+            block_time: meta.block_time.map(|t| t.timestamp),
+            block_height: meta.block_height.map(|h| h.block_height),
+            parent_slot: meta.parent_slot,
+            parent_blockhash: meta.parent_blockhash,
+            executed_transaction_count: meta.executed_transaction_count,
+            entries_count: meta.entries_count,
+        }
+    }
+}
+
+/// Entry 更新
+#[derive(Clone, Debug)]
+pub struct EntryUpdate {
+    /// 槽位
+    pub slot: u64,
+    /// entry 在槽位内的序号
+    pub index: u64,
+    /// PoH 哈希次数
+    pub num_hashes: u64,
+    /// entry 哈希
+    pub hash: Vec<u8>,
+    /// entry 内执行的交易数量
+    pub executed_transaction_count: u64,
+    /// entry 内第一笔交易在槽位内的序号
+    pub starting_transaction_index: u64,
+}
+
+impl From<SubscribeUpdateEntry> for EntryUpdate {
+    fn from(entry: SubscribeUpdateEntry) -> Self {
+        Self {
+            slot: entry.slot,
+            index: entry.index,
+            num_hashes: entry.num_hashes,
+            hash: entry.hash,
+            executed_transaction_count: entry.executed_transaction_count,
+            starting_transaction_index: entry.starting_transaction_index,
+        }
+    }
+}
+
+/// 交易执行前后的代币账户余额
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenBalance {
+    /// 代币账户在 `TransactionUpdate::account_keys` 中的下标
+    pub account_index: u32,
+    /// 代币账户地址
+    pub account: Pubkey,
+    pub mint: Pubkey,
+    /// 代币账户的所有者
+    pub owner: Option<Pubkey>,
+    /// 代币程序（SPL Token 或 Token-2022）
+    pub program_id: Option<Pubkey>,
+    /// 原始数量（未按 decimals 缩放）
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+impl TokenBalance {
+    fn from_proto(balance: ProtoTokenBalance, account_keys: &[Pubkey]) -> Option<Self> {
+        let amount = balance.ui_token_amount?;
+        Some(Self {
+            account_index: balance.account_index,
+            account: *account_keys.get(balance.account_index as usize)?,
+            mint: Pubkey::from_str(&balance.mint).ok()?,
+            owner: Pubkey::from_str(&balance.owner).ok(),
+            program_id: Pubkey::from_str(&balance.program_id).ok(),
+            amount: amount.amount.parse().ok()?,
+            decimals: amount.decimals as u8,
+        })
+    }
+}
+
+/// 交易的完整元数据
+///
+/// 包含交易前后的 SOL 和代币余额，可以直接算出实际成交数量、成交价格和支付的手续费，
+/// 不需要再通过 RPC 调用 getTransaction。
+#[derive(Clone, Debug)]
+pub struct TransactionUpdate {
+    pub signature: Signature,
+    pub slot: u64,
+    /// 交易在槽位内的序号
+    pub index: u64,
+    pub is_vote: bool,
+    /// 执行失败时的错误，成功时为 None
+    pub err: Option<TransactionError>,
+    /// 交易手续费（lamports，含优先费）
+    pub fee: u64,
+    /// 消耗的计算单元
+    pub compute_units_consumed: Option<u64>,
+    /// 交易涉及的账户：消息中的静态账户，其后依次为地址查找表加载的可写、只读账户，
+    /// 与余额数组的下标一一对应
+    pub account_keys: Vec<Pubkey>,
+    /// 交易执行前各账户的 SOL 余额（lamports）
+    pub pre_balances: Vec<u64>,
+    /// 交易执行后各账户的 SOL 余额（lamports）
+    pub post_balances: Vec<u64>,
+    /// 交易执行前的代币余额
+    pub pre_token_balances: Vec<TokenBalance>,
+    /// 交易执行后的代币余额
+    pub post_token_balances: Vec<TokenBalance>,
+}
+
+impl TransactionUpdate {
+    /// 由交易更新构建，`meta` 中的日志不会被使用（调用方可以事先取走）
+    pub(crate) fn from_proto(
+        signature: Signature,
+        slot: u64,
+        index: u64,
+        is_vote: bool,
+        message: Option<Message>,
+        meta: TransactionStatusMeta,
+    ) -> Self {
+        let account_keys: Vec<Pubkey> = message
+            .map(|message| message.account_keys)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(meta.loaded_writable_addresses)
+            .chain(meta.loaded_readonly_addresses)
+            .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+            .collect();
+        let token_balances = |balances: Vec<ProtoTokenBalance>| {
+            balances
+                .into_iter()
+                .filter_map(|balance| TokenBalance::from_proto(balance, &account_keys))
+                .collect()
+        };
+        Self {
+            signature,
+            slot,
+            index,
+            is_vote,
+            err: create_tx_error(meta.err.as_ref()).ok().flatten(),
+            fee: meta.fee,
+            compute_units_consumed: meta.compute_units_consumed,
+            pre_token_balances: token_balances(meta.pre_token_balances),
+            post_token_balances: token_balances(meta.post_token_balances),
+            account_keys,
+            pre_balances: meta.pre_balances,
+            post_balances: meta.post_balances,
+        }
+    }
+
+    /// 交易是否执行成功
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+
+    /// 手续费支付者（第一个签名者）
+    pub fn fee_payer(&self) -> Option<&Pubkey> {
+        self.account_keys.first()
+    }
+
+    /// 账户的 SOL 余额变化（lamports），账户不在交易中时返回 None
+    ///
+    /// 对手续费支付者而言，变化中包含了交易手续费。
+    pub fn sol_change(&self, account: &Pubkey) -> Option<i64> {
+        let index = self.account_keys.iter().position(|key| key == account)?;
+        let pre = *self.pre_balances.get(index)?;
+        let post = *self.post_balances.get(index)?;
+        Some(post as i64 - pre as i64)
+    }
+
+    /// `owner` 持有的 `mint` 代币的余额变化（原始数量，按所有者名下的全部代币账户合计），
+    /// 交易前后都没有对应的代币账户时返回 None
+    pub fn token_change(&self, owner: &Pubkey, mint: &Pubkey) -> Option<i128> {
+        let total = |balances: &[TokenBalance]| {
+            balances
+                .iter()
+                .filter(|b| b.mint == *mint && b.owner.as_ref() == Some(owner))
+                .fold(None, |sum: Option<i128>, b| {
+                    Some(sum.unwrap_or(0) + b.amount as i128)
+                })
+        };
+        let pre = total(&self.pre_token_balances);
+        let post = total(&self.post_token_balances);
+        if pre.is_none() && post.is_none() {
+            return None;
+        }
+        Some(post.unwrap_or(0) - pre.unwrap_or(0))
+    }
+}
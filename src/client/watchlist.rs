@@ -0,0 +1,84 @@
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::watch;
+use yellowstone_grpc_proto::geyser::{SubscribeRequest, SubscribeRequestFilterAccounts};
+
+use crate::pda;
+
+/// 观察列表在订阅请求中使用的账户过滤器名称
+pub const WATCHLIST_FILTER: &str = "watchlist";
+
+/// 代币观察列表
+///
+/// 通过 `GrpcClient::with_watchlist` 挂到客户端上后，加入列表的 mint 会自动为其
+/// bonding curve 账户添加账户订阅（运行中的订阅也会即时更新），
+/// 解码后的储备变化通过 `EventHandler::on_bonding_curve_update` 分发。
+///
+/// 克隆后的实例共享同一份列表。
+#[derive(Clone)]
+pub struct Watchlist {
+    /// bonding curve 地址 -> mint
+    curves: Arc<watch::Sender<HashMap<Pubkey, Pubkey>>>,
+}
+
+impl Watchlist {
+    /// 创建空的观察列表
+    pub fn new() -> Self {
+        Self {
+            curves: Arc::new(watch::Sender::new(HashMap::new())),
+        }
+    }
+
+    /// 加入 mint，新加入时返回 `true`
+    pub fn add(&self, mint: Pubkey) -> bool {
+        let curve = pda::bonding_curve(&mint);
+        self.curves
+            .send_if_modified(|curves| curves.insert(curve, mint).is_none())
+    }
+
+    /// 移除 mint，存在时返回 `true`
+    pub fn remove(&self, mint: &Pubkey) -> bool {
+        let curve = pda::bonding_curve(mint);
+        self.curves
+            .send_if_modified(|curves| curves.remove(&curve).is_some())
+    }
+
+    /// 是否包含指定 mint
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.curves.borrow().values().any(|m| m == mint)
+    }
+
+    /// 当前观察的所有 mint
+    pub fn mints(&self) -> Vec<Pubkey> {
+        self.curves.borrow().values().copied().collect()
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<HashMap<Pubkey, Pubkey>> {
+        self.curves.subscribe()
+    }
+}
+
+impl Default for Watchlist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 将观察列表中的 bonding curve 写入订阅请求的账户过滤器
+///
+/// 列表为空时移除该过滤器（空的账户过滤器会匹配所有账户）。
+pub(crate) fn apply_watchlist(curves: &HashMap<Pubkey, Pubkey>, request: &mut SubscribeRequest) {
+    if curves.is_empty() {
+        request.accounts.remove(WATCHLIST_FILTER);
+        return;
+    }
+    request.accounts.insert(
+        WATCHLIST_FILTER.to_string(),
+        SubscribeRequestFilterAccounts {
+            account: curves.keys().map(|curve| curve.to_string()).collect(),
+            owner: vec![],
+            filters: vec![],
+            nonempty_txn_signature: None,
+        },
+    );
+}
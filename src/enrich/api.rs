@@ -0,0 +1,312 @@
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use std::{str::FromStr, time::Duration};
+
+use crate::error::{Error, Result};
+
+/// pump.fun 前端 API 地址
+const DEFAULT_BASE_URL: &str = "https://frontend-api-v3.pump.fun";
+
+/// 默认请求超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// pump.fun 上的代币信息
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoinInfo {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub description: Option<String>,
+    /// 图片地址
+    pub image_uri: Option<String>,
+    /// 元数据 JSON 地址
+    pub metadata_uri: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+    pub creator: Option<Pubkey>,
+    pub bonding_curve: Option<Pubkey>,
+    /// 毕业后的 PumpAmm 池
+    pub pump_swap_pool: Option<Pubkey>,
+    /// 创建时间（Unix 毫秒）
+    pub created_timestamp: i64,
+    /// bonding curve 是否已完成
+    pub complete: bool,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub total_supply: u64,
+    /// 以 SOL 计的市值
+    pub market_cap: f64,
+    /// 以 USD 计的市值
+    pub usd_market_cap: f64,
+    /// 评论数
+    pub reply_count: u64,
+    /// 成为 king of the hill 的时间（Unix 毫秒）
+    pub king_of_the_hill_timestamp: Option<i64>,
+    pub nsfw: bool,
+}
+
+/// K 线
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Candlestick {
+    /// 起始时间（Unix 秒）
+    pub timestamp: i64,
+    /// 以 SOL 计的单价
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// 成交量（lamports）
+    pub volume: u64,
+    pub slot: u64,
+}
+
+/// 代币页面下的评论
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Reply {
+    pub id: u64,
+    pub mint: Pubkey,
+    /// 评论者钱包
+    pub user: Option<Pubkey>,
+    pub text: String,
+    pub image_uri: Option<String>,
+    /// 评论时间（Unix 毫秒）
+    pub timestamp: i64,
+    pub total_likes: u64,
+}
+
+/// pump.fun 前端 API 客户端（需要启用 `api` feature）
+///
+/// 提供代币信息、king of the hill、K 线和评论等链下数据，可用于补全链上事件。
+/// 这是非官方文档的前端接口，字段可能随前端更新变化：可选字段缺失时为 None，数值字段缺失时为默认值，
+/// 只有 mint 等必需字段无法解析时才返回错误。
+///
+/// 克隆后的实例共享同一个 HTTP 客户端。
+#[derive(Clone)]
+pub struct PumpFunApi {
+    client: reqwest::Client,
+    base_url: String,
+    timeout: Duration,
+}
+
+impl Default for PumpFunApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PumpFunApi {
+    /// 使用默认地址和超时创建
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 设置 API 地址（例如经过代理时）
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// 设置单次请求的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 代币信息，pump.fun 上不存在该代币时返回 None
+    pub async fn coin(&self, mint: &Pubkey) -> Result<Option<CoinInfo>> {
+        let json = self.get(&format!("/coins/{mint}"), &[]).await?;
+        if json.is_null() {
+            return Ok(None);
+        }
+        parse_coin(&json).map(Some)
+    }
+
+    /// 当前的 king of the hill
+    pub async fn king_of_the_hill(&self, include_nsfw: bool) -> Result<Option<CoinInfo>> {
+        let json = self
+            .get(
+                "/coins/king-of-the-hill",
+                &[("includeNsfw", include_nsfw.to_string())],
+            )
+            .await?;
+        if json.is_null() {
+            return Ok(None);
+        }
+        parse_coin(&json).map(Some)
+    }
+
+    /// K 线，`timeframe_minutes` 为每根 K 线的分钟数，按时间升序返回
+    pub async fn candlesticks(
+        &self,
+        mint: &Pubkey,
+        timeframe_minutes: u32,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Candlestick>> {
+        let json = self
+            .get(
+                &format!("/candlesticks/{mint}"),
+                &[
+                    ("timeframe", timeframe_minutes.to_string()),
+                    ("offset", offset.to_string()),
+                    ("limit", limit.to_string()),
+                ],
+            )
+            .await?;
+        let mut candles: Vec<Candlestick> = array(&json, "candlesticks")?
+            .iter()
+            .map(parse_candlestick)
+            .collect();
+        candles.sort_by_key(|candle| candle.timestamp);
+        Ok(candles)
+    }
+
+    /// 代币评论，按时间倒序分页
+    pub async fn replies(&self, mint: &Pubkey, offset: usize, limit: usize) -> Result<Vec<Reply>> {
+        let json = self
+            .get(
+                &format!("/replies/{mint}"),
+                &[
+                    ("offset", offset.to_string()),
+                    ("limit", limit.to_string()),
+                    ("reverseOrder", "true".to_string()),
+                ],
+            )
+            .await?;
+        // 新版接口返回 {"replies": [...], "hasMore": ...}，旧版直接返回数组
+        let replies = if json["replies"].is_array() {
+            &json["replies"]
+        } else {
+            &json
+        };
+        Ok(array(replies, "replies")?
+            .iter()
+            .map(|reply| parse_reply(reply, mint))
+            .collect())
+    }
+
+    /// 发送 GET 请求，404 和空响应返回 `Value::Null`
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> Result<Value> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .query(query)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Value::Null);
+        }
+        let body = response.error_for_status()?.bytes().await?;
+        if body.is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_slice(&body)
+            .map_err(|e| Error::ParseError(format!("pump.fun API 响应不是 JSON: {e}")))
+    }
+}
+
+fn array<'a>(json: &'a Value, what: &str) -> Result<&'a Vec<Value>> {
+    json.as_array()
+        .ok_or_else(|| Error::ParseError(format!("pump.fun API {what} 返回格式错误: {json}")))
+}
+
+fn parse_coin(json: &Value) -> Result<CoinInfo> {
+    Ok(CoinInfo {
+        mint: required_pubkey(json, "mint")?,
+        name: string(json, "name").unwrap_or_default(),
+        symbol: string(json, "symbol").unwrap_or_default(),
+        description: string(json, "description"),
+        image_uri: string(json, "image_uri"),
+        metadata_uri: string(json, "metadata_uri"),
+        twitter: string(json, "twitter"),
+        telegram: string(json, "telegram"),
+        website: string(json, "website"),
+        creator: pubkey(json, "creator"),
+        bonding_curve: pubkey(json, "bonding_curve"),
+        pump_swap_pool: pubkey(json, "pump_swap_pool"),
+        created_timestamp: int(json, "created_timestamp").unwrap_or_default(),
+        complete: json["complete"].as_bool().unwrap_or_default(),
+        virtual_sol_reserves: uint(json, "virtual_sol_reserves"),
+        virtual_token_reserves: uint(json, "virtual_token_reserves"),
+        total_supply: uint(json, "total_supply"),
+        market_cap: float(json, "market_cap"),
+        usd_market_cap: float(json, "usd_market_cap"),
+        reply_count: uint(json, "reply_count"),
+        king_of_the_hill_timestamp: int(json, "king_of_the_hill_timestamp"),
+        nsfw: json["nsfw"].as_bool().unwrap_or_default(),
+    })
+}
+
+fn parse_candlestick(json: &Value) -> Candlestick {
+    Candlestick {
+        timestamp: int(json, "timestamp").unwrap_or_default(),
+        open: float(json, "open"),
+        high: float(json, "high"),
+        low: float(json, "low"),
+        close: float(json, "close"),
+        volume: uint(json, "volume"),
+        slot: uint(json, "slot"),
+    }
+}
+
+fn parse_reply(json: &Value, mint: &Pubkey) -> Reply {
+    Reply {
+        id: uint(json, "id"),
+        mint: pubkey(json, "mint").unwrap_or(*mint),
+        user: pubkey(json, "user"),
+        text: string(json, "text").unwrap_or_default(),
+        image_uri: string(json, "file_uri").or_else(|| string(json, "image_uri")),
+        timestamp: int(json, "timestamp").unwrap_or_default(),
+        total_likes: uint(json, "total_likes"),
+    }
+}
+
+/// 非空字符串字段
+fn string(json: &Value, field: &str) -> Option<String> {
+    json[field]
+        .as_str()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn pubkey(json: &Value, field: &str) -> Option<Pubkey> {
+    json[field].as_str().and_then(|s| Pubkey::from_str(s).ok())
+}
+
+fn required_pubkey(json: &Value, field: &str) -> Result<Pubkey> {
+    pubkey(json, field)
+        .ok_or_else(|| Error::ParseError(format!("pump.fun API 响应缺少有效的 {field}")))
+}
+
+/// 整数字段，兼容数字和字符串两种写法
+fn int(json: &Value, field: &str) -> Option<i64> {
+    let value = &json[field];
+    value
+        .as_i64()
+        .or_else(|| value.as_f64().map(|v| v as i64))
+        .or_else(|| value.as_str()?.parse().ok())
+}
+
+fn uint(json: &Value, field: &str) -> u64 {
+    let value = &json[field];
+    value
+        .as_u64()
+        .or_else(|| value.as_f64().map(|v| v as u64))
+        .or_else(|| value.as_str()?.parse().ok())
+        .unwrap_or_default()
+}
+
+fn float(json: &Value, field: &str) -> f64 {
+    let value = &json[field];
+    value
+        .as_f64()
+        .or_else(|| value.as_str()?.parse().ok())
+        .unwrap_or_default()
+}
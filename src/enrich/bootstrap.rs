@@ -0,0 +1,172 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use super::RpcFetcher;
+use crate::{
+    client::{AccountCache, GraduatedTokens, PoolKeys},
+    error::Result,
+    models::{BondingCurveAccount, PoolAccount, ProgramAccount},
+    parser::accounts::{BONDING_CURVE_ACCOUNT_DISCRIMINATOR, POOL_ACCOUNT_DISCRIMINATOR},
+    pda::{PUMP_AMM_PROGRAM_ID, PUMP_PROGRAM_ID},
+};
+
+/// `getMultipleAccounts` 单次请求的账户上限
+const MAX_PAGE_SIZE: usize = 100;
+
+/// bonding curve 账户中 `complete` 字段的偏移（discriminator + 5 个 u64）
+const COMPLETE_OFFSET: usize = 8 + 5 * 8;
+
+/// 启动时的市场快照
+#[derive(Clone, Debug, Default)]
+pub struct MarketSnapshot {
+    /// 快照中最早一页数据所在的槽位，所有账户至少新于该槽位
+    pub slot: u64,
+    /// 未完成的 bonding curve（bonding curve 地址, 账户）
+    pub bonding_curves: Vec<(Pubkey, BondingCurveAccount)>,
+    /// PumpAmm 池（池地址, 账户）
+    pub pools: Vec<(Pubkey, PoolAccount)>,
+}
+
+impl MarketSnapshot {
+    /// 把快照中的账户写入缓存（不逐个订阅），订阅流中更新的槽位更高，会覆盖快照数据
+    pub fn seed_cache(&self, cache: &AccountCache) {
+        for (address, curve) in &self.bonding_curves {
+            cache.seed(
+                *address,
+                ProgramAccount::BondingCurve(curve.clone()),
+                self.slot,
+            );
+        }
+        for (address, pool) in &self.pools {
+            cache.seed(*address, ProgramAccount::Pool(pool.clone()), self.slot);
+        }
+    }
+
+    /// 把快照中的池登记到已毕业代币注册表
+    pub fn seed_graduated(&self, registry: &GraduatedTokens) {
+        for (address, pool) in &self.pools {
+            registry.insert_pool_account(*address, pool);
+        }
+    }
+
+    /// 快照中所有池的地址信息，可按需交给 `PoolTracker::track` 跟踪储备
+    pub fn pool_keys(&self) -> impl Iterator<Item = PoolKeys> + '_ {
+        self.pools
+            .iter()
+            .map(|(address, pool)| PoolKeys::from_pool_account(*address, pool))
+    }
+}
+
+/// 启动快照（需要启用 `rpc` feature）
+///
+/// 通过 `getProgramAccounts` 列出所有未完成的 bonding curve 和 PumpAmm 池，
+/// 让分析组件和缓存在订阅开始前就有完整的市场视图，而不是从空状态冷启动。
+///
+/// 列表请求只取账户地址，账户数据再按页（默认每页 100 个）通过 `getMultipleAccounts` 获取，
+/// 单个响应不会过大；所有请求经由 [`RpcFetcher`] 限流。
+#[derive(Clone)]
+pub struct MarketBootstrap {
+    rpc: RpcFetcher,
+    bonding_curves: bool,
+    pools: bool,
+    page_size: usize,
+}
+
+impl MarketBootstrap {
+    /// 默认同时获取 bonding curve 和池
+    pub fn new(rpc: RpcFetcher) -> Self {
+        Self {
+            rpc,
+            bonding_curves: true,
+            pools: true,
+            page_size: MAX_PAGE_SIZE,
+        }
+    }
+
+    /// 设置是否获取未完成的 bonding curve
+    pub fn with_bonding_curves(mut self, enabled: bool) -> Self {
+        self.bonding_curves = enabled;
+        self
+    }
+
+    /// 设置是否获取 PumpAmm 池
+    pub fn with_pools(mut self, enabled: bool) -> Self {
+        self.pools = enabled;
+        self
+    }
+
+    /// 设置每页获取的账户数量，取值范围 1 ~ 100
+    pub fn with_page_size(mut self, size: usize) -> Self {
+        self.page_size = size.clamp(1, MAX_PAGE_SIZE);
+        self
+    }
+
+    /// 获取快照
+    pub async fn fetch(&self) -> Result<MarketSnapshot> {
+        let mut snapshot = MarketSnapshot {
+            slot: u64::MAX,
+            ..Default::default()
+        };
+        if self.bonding_curves {
+            let filters = vec![
+                memcmp(0, BONDING_CURVE_ACCOUNT_DISCRIMINATOR),
+                memcmp(COMPLETE_OFFSET, &[0]),
+            ];
+            let keys = self
+                .rpc
+                .program_account_keys(&PUMP_PROGRAM_ID, filters)
+                .await?;
+            for (address, account) in self.fetch_accounts(&keys, &mut snapshot.slot).await? {
+                match account {
+                    ProgramAccount::BondingCurve(curve) if !curve.complete => {
+                        snapshot.bonding_curves.push((address, curve))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if self.pools {
+            let filters = vec![memcmp(0, POOL_ACCOUNT_DISCRIMINATOR)];
+            let keys = self
+                .rpc
+                .program_account_keys(&PUMP_AMM_PROGRAM_ID, filters)
+                .await?;
+            for (address, account) in self.fetch_accounts(&keys, &mut snapshot.slot).await? {
+                if let ProgramAccount::Pool(pool) = account {
+                    snapshot.pools.push((address, pool));
+                }
+            }
+        }
+        if snapshot.slot == u64::MAX {
+            snapshot.slot = 0;
+        }
+        Ok(snapshot)
+    }
+
+    /// 分页获取并解码账户，列表与获取之间被关闭或无法解码的账户会被跳过
+    async fn fetch_accounts(
+        &self,
+        keys: &[Pubkey],
+        min_slot: &mut u64,
+    ) -> Result<Vec<(Pubkey, ProgramAccount)>> {
+        let mut accounts = Vec::with_capacity(keys.len());
+        for page in keys.chunks(self.page_size) {
+            let (slot, data) = self.rpc.multiple_account_data(page).await?;
+            *min_slot = (*min_slot).min(slot);
+            for (address, data) in page.iter().zip(data) {
+                let Some(data) = data else { continue };
+                match ProgramAccount::from_account_data(&data) {
+                    Ok(account) => accounts.push((*address, account)),
+                    Err(e) => log::debug!("账户 {} 解码失败: {}", address, e),
+                }
+            }
+        }
+        Ok(accounts)
+    }
+}
+
+/// 以 base64 编码的 `memcmp` 过滤器
+fn memcmp(offset: usize, bytes: &[u8]) -> Value {
+    json!({ "memcmp": { "offset": offset, "bytes": STANDARD.encode(bytes), "encoding": "base64" } })
+}
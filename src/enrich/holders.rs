@@ -0,0 +1,226 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use lru::LruCache;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    num::NonZeroUsize,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::rpc::{parse_amount, RpcFetcher};
+use crate::{
+    error::{Error, Result},
+    pda::{associated_bonding_curve, TOKEN_PROGRAM_ID},
+};
+
+/// 默认缓存有效期
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// 默认缓存的 mint 数量
+const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// SPL Token 账户的数据长度，Token-2022 账户带扩展，长度不固定
+const TOKEN_ACCOUNT_LEN: u64 = 165;
+
+/// 代币账户中 amount 字段的偏移
+const AMOUNT_OFFSET: usize = 64;
+
+/// 集中度统计的持仓账户数量
+const TOP_HOLDERS: usize = 10;
+
+/// mint -> (获取时间, 统计结果)
+type StatsCache = LruCache<Pubkey, (Instant, Arc<HolderStats>)>;
+
+/// 单个持仓账户
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenHolding {
+    /// 代币账户地址（不是持有者钱包）
+    pub account: Pubkey,
+    pub amount: u64,
+}
+
+/// 代币的持仓分布估计
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HolderStats {
+    pub mint: Pubkey,
+    /// 总供应量
+    pub supply: u64,
+    /// 余额不为零的代币账户数量；未启用程序账户扫描时为 None
+    pub holders: Option<u64>,
+    /// 除 bonding curve 外最大的持仓账户（最多 20 个，RPC `getTokenLargestAccounts` 的上限）
+    pub largest: Vec<TokenHolding>,
+    /// 前 10 大持仓合计
+    pub top10_amount: u64,
+    /// 前 10 大持仓占总供应量的万分比
+    pub top10_bps: u64,
+}
+
+/// 持仓统计服务（需要启用 `holders` feature）
+///
+/// 通过 Solana JSON-RPC 估算代币的持仓人数和前 10 大持仓集中度：
+/// 集中度来自 `getTokenLargestAccounts`，持仓人数来自按 mint 过滤的 `getProgramAccounts` 扫描
+/// （只取 amount 字段）。bonding curve 持有的未售出代币不计入集中度。
+///
+/// 结果按 mint 缓存 `ttl`，请求通过 [`RpcFetcher`] 发送，按其最小间隔限流，避免触发 RPC 节点限流。
+/// 克隆后的实例共享缓存和限流状态。
+#[derive(Clone)]
+pub struct HolderService {
+    rpc: RpcFetcher,
+    ttl: Duration,
+    program_scan: bool,
+    cache: Arc<Mutex<StatsCache>>,
+}
+
+impl HolderService {
+    /// 使用默认的缓存有效期和请求间隔创建服务，默认启用程序账户扫描
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self::with_fetcher(RpcFetcher::new(rpc_url))
+    }
+
+    /// 使用已有的 RPC 客户端创建服务，与其他组件共享限流和缓存
+    pub fn with_fetcher(rpc: RpcFetcher) -> Self {
+        Self {
+            rpc,
+            ttl: DEFAULT_TTL,
+            program_scan: true,
+            cache: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN),
+            ))),
+        }
+    }
+
+    /// 设置缓存有效期
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 设置两次 RPC 请求的最小间隔
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.rpc = self.rpc.with_min_interval(interval);
+        self
+    }
+
+    /// 设置是否扫描程序账户统计持仓人数
+    ///
+    /// 扫描开销较大，部分 RPC 节点不支持对 Token 程序调用 `getProgramAccounts`，
+    /// 关闭后 `HolderStats::holders` 为 None。
+    pub fn with_program_scan(mut self, enabled: bool) -> Self {
+        self.program_scan = enabled;
+        self
+    }
+
+    /// 设置缓存的 mint 数量，容量为 0 时按 1 处理
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.cache = Arc::new(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    /// 缓存中未过期的统计结果，不发起请求
+    pub fn cached(&self, mint: &Pubkey) -> Option<Arc<HolderStats>> {
+        let mut cache = self.lock();
+        let (fetched_at, stats) = cache.get(mint)?;
+        (fetched_at.elapsed() < self.ttl).then(|| Arc::clone(stats))
+    }
+
+    /// 获取代币的持仓统计，缓存未过期时直接返回缓存结果
+    ///
+    /// `token_program` 为代币所属的 Token 程序（`CreateEvent` 为 SPL Token，`CreateV2Event` 为 Token-2022）。
+    pub async fn stats(&self, mint: &Pubkey, token_program: &Pubkey) -> Result<Arc<HolderStats>> {
+        if let Some(stats) = self.cached(mint) {
+            return Ok(stats);
+        }
+
+        let supply = self.rpc.token_supply(mint).await?;
+        let curve_account = associated_bonding_curve(mint, token_program);
+        let largest: Vec<TokenHolding> = self
+            .largest_accounts(mint)
+            .await?
+            .into_iter()
+            .filter(|holding| holding.account != curve_account)
+            .collect();
+        let holders = if self.program_scan {
+            Some(self.count_holders(mint, token_program).await?)
+        } else {
+            None
+        };
+
+        let top10_amount = largest
+            .iter()
+            .take(TOP_HOLDERS)
+            .map(|holding| holding.amount)
+            .sum::<u64>();
+        let top10_bps = if supply == 0 {
+            0
+        } else {
+            (top10_amount as u128 * 10_000 / supply as u128) as u64
+        };
+        let stats = Arc::new(HolderStats {
+            mint: *mint,
+            supply,
+            holders,
+            largest,
+            top10_amount,
+            top10_bps,
+        });
+        self.lock().put(*mint, (Instant::now(), Arc::clone(&stats)));
+        Ok(stats)
+    }
+
+    async fn largest_accounts(&self, mint: &Pubkey) -> Result<Vec<TokenHolding>> {
+        let result = self
+            .rpc
+            .call("getTokenLargestAccounts", json!([mint.to_string()]))
+            .await?;
+        let Some(accounts) = result["value"].as_array() else {
+            return Err(Error::ParseError(
+                "getTokenLargestAccounts 返回格式错误".into(),
+            ));
+        };
+        accounts
+            .iter()
+            .map(|account| {
+                let address = account["address"].as_str().unwrap_or_default();
+                Ok(TokenHolding {
+                    account: Pubkey::from_str(address)
+                        .map_err(|e| Error::ParseError(format!("代币账户地址无效: {e}")))?,
+                    amount: parse_amount(&account["amount"])?,
+                })
+            })
+            .collect()
+    }
+
+    /// 扫描 mint 下的所有代币账户，统计余额不为零的数量
+    async fn count_holders(&self, mint: &Pubkey, token_program: &Pubkey) -> Result<u64> {
+        let mut filters = vec![json!({ "memcmp": { "offset": 0, "bytes": mint.to_string() } })];
+        if *token_program == TOKEN_PROGRAM_ID {
+            filters.push(json!({ "dataSize": TOKEN_ACCOUNT_LEN }));
+        }
+        let params = json!([
+            token_program.to_string(),
+            {
+                "encoding": "base64",
+                "dataSlice": { "offset": AMOUNT_OFFSET, "length": 8 },
+                "filters": filters,
+            }
+        ]);
+        let result = self.rpc.call("getProgramAccounts", params).await?;
+        let Some(accounts) = result.as_array() else {
+            return Err(Error::ParseError("getProgramAccounts 返回格式错误".into()));
+        };
+        let holders = accounts
+            .iter()
+            .filter_map(|account| account["account"]["data"][0].as_str())
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .filter(|amount| amount.iter().any(|byte| *byte != 0))
+            .count();
+        Ok(holders as u64)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StatsCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,199 @@
+use log::debug;
+use lru::LruCache;
+use serde_json::Value;
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{mpsc, Semaphore};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    error::Result,
+    models::{NewToken, OffchainMetadata},
+};
+
+/// 默认 IPFS 网关
+const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Arweave 网关
+const ARWEAVE_GATEWAY: &str = "https://arweave.net/";
+
+/// 默认请求超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 默认缓存的元数据条数
+const DEFAULT_CACHE_CAPACITY: usize = 4096;
+
+/// 待补全的新币队列长度，队列满时直接分发未补全的事件
+const QUEUE_CAPACITY: usize = 1024;
+
+/// 链下元数据获取器
+///
+/// 请求元数据 URI 指向的 JSON 并提取常用字段，`ipfs://` 与 `ar://` 地址通过网关访问。
+/// 成功的结果按 URI 缓存在有界 LRU 中，克隆后的实例共享同一个 HTTP 客户端和缓存。
+#[derive(Clone)]
+pub struct MetadataFetcher {
+    client: reqwest::Client,
+    ipfs_gateway: String,
+    timeout: Duration,
+    cache: Arc<Mutex<LruCache<String, OffchainMetadata>>>,
+}
+
+impl Default for MetadataFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetadataFetcher {
+    /// 使用默认网关、超时和缓存容量创建获取器
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            cache: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN),
+            ))),
+        }
+    }
+
+    /// 设置 IPFS 网关，例如 `https://cloudflare-ipfs.com/ipfs/`
+    pub fn with_ipfs_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = gateway.into();
+        self
+    }
+
+    /// 设置单次请求的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 设置缓存容量，容量为 0 时按 1 处理
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.cache = Arc::new(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    /// 把 `ipfs://` / `ar://` 地址转换为网关地址，其他地址原样返回
+    pub fn resolve_uri(&self, uri: &str) -> String {
+        if let Some(cid) = uri.strip_prefix("ipfs://") {
+            format!("{}{}", self.ipfs_gateway, cid.trim_start_matches("ipfs/"))
+        } else if let Some(id) = uri.strip_prefix("ar://") {
+            format!("{ARWEAVE_GATEWAY}{id}")
+        } else {
+            uri.to_string()
+        }
+    }
+
+    /// 获取并解析元数据，命中缓存时不发起请求
+    pub async fn fetch(&self, uri: &str) -> Result<OffchainMetadata> {
+        if let Some(metadata) = self.lock().get(uri) {
+            return Ok(metadata.clone());
+        }
+        let json: Value = self
+            .client
+            .get(self.resolve_uri(uri))
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let metadata = self.parse(&json);
+        self.lock().put(uri.to_string(), metadata.clone());
+        Ok(metadata)
+    }
+
+    /// 从元数据 JSON 中提取字段，社交链接兼容顶层和 `extensions` 两种写法
+    fn parse(&self, json: &Value) -> OffchainMetadata {
+        let field = |name: &str| {
+            json[name]
+                .as_str()
+                .or_else(|| json["extensions"][name].as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        OffchainMetadata {
+            name: field("name"),
+            symbol: field("symbol"),
+            description: field("description"),
+            image: field("image").map(|image| self.resolve_uri(&image)),
+            twitter: field("twitter"),
+            telegram: field("telegram"),
+            website: field("website"),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<String, OffchainMetadata>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 补全完成后调用的回调
+type NewTokenCallback = Arc<dyn Fn(&NewToken, &EventContext) + Send + Sync>;
+
+/// 新币事件的链下元数据补全（需要启用 `metadata` feature）
+///
+/// 作为 `EventHandler` 订阅后，收到的 [`NewToken`] 在后台任务中获取元数据并填入 `offchain`，
+/// 再交给回调。最多同时进行 `concurrency` 个请求，因此回调顺序不保证与事件顺序一致；
+/// 获取失败或队列已满时 `offchain` 为 None，事件仍会交给回调。
+#[derive(Clone)]
+pub struct NewTokenEnricher {
+    tx: mpsc::Sender<(NewToken, EventContext)>,
+    callback: NewTokenCallback,
+}
+
+impl NewTokenEnricher {
+    /// 启动后台补全任务，必须在 Tokio 运行时中调用
+    pub fn spawn<F>(fetcher: MetadataFetcher, concurrency: usize, callback: F) -> Self
+    where
+        F: Fn(&NewToken, &EventContext) + Send + Sync + 'static,
+    {
+        let callback: NewTokenCallback = Arc::new(callback);
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(run(fetcher, concurrency.max(1), Arc::clone(&callback), rx));
+        Self { tx, callback }
+    }
+}
+
+/// 后台补全循环
+async fn run(
+    fetcher: MetadataFetcher,
+    concurrency: usize,
+    callback: NewTokenCallback,
+    mut rx: mpsc::Receiver<(NewToken, EventContext)>,
+) {
+    let permits = Arc::new(Semaphore::new(concurrency));
+    while let Some((mut token, ctx)) = rx.recv().await {
+        let Ok(permit) = Arc::clone(&permits).acquire_owned().await else {
+            break;
+        };
+        let fetcher = fetcher.clone();
+        let callback = Arc::clone(&callback);
+        tokio::spawn(async move {
+            if let Some(uri) = token.metadata.uri.as_deref() {
+                match fetcher.fetch(uri).await {
+                    Ok(metadata) => token.offchain = Some(metadata),
+                    Err(e) => debug!("获取 {} 的元数据失败: {}", token.mint, e),
+                }
+            }
+            drop(permit);
+            callback(&token, &ctx);
+        });
+    }
+}
+
+impl EventHandler for NewTokenEnricher {
+    fn on_new_token(&self, token: &NewToken, ctx: &EventContext) {
+        if self.tx.try_send((token.clone(), ctx.clone())).is_err() {
+            debug!("元数据补全队列已满，跳过 {} 的补全", token.mint);
+            (self.callback)(token, ctx);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! 事件数据补全
+//!
+//! 链上事件之外的数据（链下元数据、持仓分布等）需要额外的网络请求获取，这里的组件都在后台任务中执行请求，
+//! 不会阻塞事件分发。
+
+#[cfg(feature = "api")]
+pub mod api;
+#[cfg(feature = "rpc")]
+pub mod bootstrap;
+#[cfg(feature = "holders")]
+pub mod holders;
+#[cfg(feature = "metadata")]
+pub mod metadata;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+#[cfg(feature = "upload")]
+pub mod upload;
+
+#[cfg(feature = "api")]
+pub use api::{Candlestick, CoinInfo, PumpFunApi, Reply};
+#[cfg(feature = "rpc")]
+pub use bootstrap::{MarketBootstrap, MarketSnapshot};
+#[cfg(feature = "holders")]
+pub use holders::{HolderService, HolderStats, TokenHolding};
+#[cfg(feature = "metadata")]
+pub use metadata::{MetadataFetcher, NewTokenEnricher};
+#[cfg(feature = "rpc")]
+pub use rpc::RpcFetcher;
+#[cfg(feature = "upload")]
+pub use upload::{
+    KuboBackend, LaunchMetadata, MetadataUploader, PinataBackend, PinningBackend, TokenImage,
+    UploadedMetadata,
+};
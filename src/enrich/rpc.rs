@@ -0,0 +1,352 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use lru::LruCache;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::OnceCell;
+
+use crate::{
+    client::{PoolKeys, PoolReserves},
+    error::{Error, Result},
+    models::BondingCurveAccount,
+    pda,
+};
+
+/// 默认的两次请求最小间隔
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 默认的响应缓存有效期，足以合并同一突发中的重复查询
+const DEFAULT_TTL: Duration = Duration::from_secs(1);
+
+/// 默认缓存的响应数量
+const DEFAULT_CACHE_CAPACITY: usize = 4096;
+
+/// 代币账户中 amount 字段的偏移
+const AMOUNT_OFFSET: usize = 64;
+
+/// 同一请求的共享结果，错误以 `Arc` 共享给所有等待者
+type SharedResult = std::result::Result<Value, Arc<Error>>;
+
+/// 请求键（方法名与参数）-> 进行中的请求
+type InFlight = HashMap<String, Arc<OnceCell<SharedResult>>>;
+
+/// 请求键 -> (获取时间, 响应)
+type ResponseCache = LruCache<String, (Instant, Value)>;
+
+struct State {
+    in_flight: InFlight,
+    cache: ResponseCache,
+}
+
+/// 限流、合并并缓存的 Solana JSON-RPC 客户端（需要启用 `rpc` feature）
+///
+/// 所有依赖 RPC 的组件（如 [`HolderService`](super::HolderService)）都通过它发送请求：
+///
+/// - 限流：任意两次请求之间至少间隔 `min_interval`，突发查询不会触发节点封禁
+/// - 合并：方法名和参数相同的并发请求只发送一次，所有调用方共享结果
+/// - 缓存：成功的响应缓存 `ttl`，有效期内的相同查询直接返回缓存
+///
+/// 克隆后的实例共享限流、合并和缓存状态，多个组件共用同一个 RPC 密钥时应共用同一个实例。
+#[derive(Clone)]
+pub struct RpcFetcher {
+    client: reqwest::Client,
+    rpc_url: Arc<str>,
+    min_interval: Duration,
+    ttl: Duration,
+    state: Arc<Mutex<State>>,
+    last_request: Arc<tokio::sync::Mutex<Option<Instant>>>,
+}
+
+impl RpcFetcher {
+    /// 使用默认的请求间隔和缓存有效期创建
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            rpc_url: rpc_url.into().into(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+            ttl: DEFAULT_TTL,
+            state: Arc::new(Mutex::new(State {
+                in_flight: HashMap::new(),
+                cache: LruCache::new(
+                    NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN),
+                ),
+            })),
+            last_request: Arc::new(tokio::sync::Mutex::new(None)),
+        }
+    }
+
+    /// 设置两次请求的最小间隔
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// 按每秒请求数设置限流，0 表示不限流
+    pub fn with_requests_per_second(self, requests: u32) -> Self {
+        let interval = Duration::from_secs(1)
+            .checked_div(requests)
+            .unwrap_or_default();
+        self.with_min_interval(interval)
+    }
+
+    /// 设置响应缓存有效期，`Duration::ZERO` 表示不缓存（并发的相同请求仍会合并）
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 设置缓存的响应数量，容量为 0 时按 1 处理
+    pub fn with_cache_capacity(self, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.lock().cache.resize(capacity);
+        self
+    }
+
+    /// RPC 节点地址
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// 清空响应缓存
+    pub fn clear_cache(&self) {
+        self.lock().cache.clear();
+    }
+
+    /// 发送 JSON-RPC 请求，返回 `result` 字段
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let key = format!("{method}:{params}");
+        let cell = {
+            let mut state = self.lock();
+            if let Some((fetched_at, value)) = state.cache.get(&key) {
+                if fetched_at.elapsed() < self.ttl {
+                    return Ok(value.clone());
+                }
+            }
+            Arc::clone(state.in_flight.entry(key.clone()).or_default())
+        };
+
+        // 第一个调用方发送请求，其余调用方等待同一个结果；
+        // 发送方被取消时由下一个等待者接手
+        let result = cell
+            .get_or_init(|| async { self.send(method, &params).await.map_err(Arc::new) })
+            .await
+            .clone();
+
+        {
+            let mut state = self.lock();
+            if state
+                .in_flight
+                .get(&key)
+                .is_some_and(|current| Arc::ptr_eq(current, &cell))
+            {
+                state.in_flight.remove(&key);
+                if let (Ok(value), false) = (&result, self.ttl.is_zero()) {
+                    state.cache.put(key, (Instant::now(), value.clone()));
+                }
+            }
+        }
+        result.map_err(|e| shared_error(&e))
+    }
+
+    /// 账户数据，账户不存在时返回 None
+    pub async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self
+            .call(
+                "getAccountInfo",
+                json!([address.to_string(), { "encoding": "base64" }]),
+            )
+            .await?;
+        decode_account_data(&result["value"])
+    }
+
+    /// 多个账户的数据，顺序与 `addresses` 一致，同时返回响应所在的槽位
+    pub async fn multiple_account_data(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<(u64, Vec<Option<Vec<u8>>>)> {
+        let keys: Vec<String> = addresses.iter().map(Pubkey::to_string).collect();
+        let result = self
+            .call(
+                "getMultipleAccounts",
+                json!([keys, { "encoding": "base64" }]),
+            )
+            .await?;
+        let Some(accounts) = result["value"].as_array() else {
+            return Err(Error::ParseError("getMultipleAccounts 返回格式错误".into()));
+        };
+        let data = accounts
+            .iter()
+            .map(decode_account_data)
+            .collect::<Result<_>>()?;
+        Ok((result["context"]["slot"].as_u64().unwrap_or_default(), data))
+    }
+
+    /// 程序下满足 `filters` 的所有账户地址，不下载账户数据
+    ///
+    /// `filters` 为 `getProgramAccounts` 的 `memcmp` / `dataSize` 过滤器，
+    /// 账户数据可再通过 [`multiple_account_data`](Self::multiple_account_data) 分批获取。
+    pub async fn program_account_keys(
+        &self,
+        program: &Pubkey,
+        filters: Vec<Value>,
+    ) -> Result<Vec<Pubkey>> {
+        let result = self
+            .call(
+                "getProgramAccounts",
+                json!([
+                    program.to_string(),
+                    {
+                        "encoding": "base64",
+                        "dataSlice": { "offset": 0, "length": 0 },
+                        "filters": filters,
+                    }
+                ]),
+            )
+            .await?;
+        let Some(accounts) = result.as_array() else {
+            return Err(Error::ParseError("getProgramAccounts 返回格式错误".into()));
+        };
+        accounts
+            .iter()
+            .map(|account| {
+                let pubkey = account["pubkey"].as_str().unwrap_or_default();
+                Pubkey::from_str(pubkey)
+                    .map_err(|e| Error::ParseError(format!("账户地址无效: {e}")))
+            })
+            .collect()
+    }
+
+    /// 账户的 SOL 余额（lamports）
+    pub async fn balance(&self, address: &Pubkey) -> Result<u64> {
+        let result = self
+            .call("getBalance", json!([address.to_string()]))
+            .await?;
+        result["value"]
+            .as_u64()
+            .ok_or_else(|| Error::ParseError(format!("getBalance 返回格式错误: {result}")))
+    }
+
+    /// 代币账户的余额（最小单位）
+    pub async fn token_balance(&self, token_account: &Pubkey) -> Result<u64> {
+        let result = self
+            .call("getTokenAccountBalance", json!([token_account.to_string()]))
+            .await?;
+        parse_amount(&result["value"]["amount"])
+    }
+
+    /// 代币的总供应量（最小单位）
+    pub async fn token_supply(&self, mint: &Pubkey) -> Result<u64> {
+        let result = self
+            .call("getTokenSupply", json!([mint.to_string()]))
+            .await?;
+        parse_amount(&result["value"]["amount"])
+    }
+
+    /// 代币的 bonding curve 账户，账户不存在时返回 None
+    pub async fn bonding_curve(&self, mint: &Pubkey) -> Result<Option<BondingCurveAccount>> {
+        self.account_data(&pda::bonding_curve(mint))
+            .await?
+            .map(|data| BondingCurveAccount::from_account_data(&data))
+            .transpose()
+    }
+
+    /// 从池的两个代币账户读取 PumpAmm 池的当前储备，代币账户不存在时返回 None
+    pub async fn pool_reserves(&self, keys: &PoolKeys) -> Result<Option<PoolReserves>> {
+        let (slot, accounts) = self
+            .multiple_account_data(&[keys.base_vault, keys.quote_vault])
+            .await?;
+        let amount = |index: usize| token_amount(accounts.get(index)?.as_deref()?);
+        let (Some(base_reserves), Some(quote_reserves)) = (amount(0), amount(1)) else {
+            return Ok(None);
+        };
+        Ok(Some(PoolReserves {
+            pool: keys.pool,
+            base_mint: keys.base_mint,
+            quote_mint: keys.quote_mint,
+            base_reserves,
+            quote_reserves,
+            slot,
+        }))
+    }
+
+    /// 发送请求，按最小间隔限流
+    async fn send(&self, method: &str, params: &Value) -> Result<Value> {
+        {
+            let mut last = self.last_request.lock().await;
+            if let Some(last) = *last {
+                let next = last + self.min_interval;
+                if next > Instant::now() {
+                    tokio::time::sleep_until(next.into()).await;
+                }
+            }
+            *last = Some(Instant::now());
+        }
+
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .client
+            .post(&*self.rpc_url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            return Err(Error::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+        Ok(response["result"].take())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 把合并请求的共享错误转换为调用方自己的错误
+fn shared_error(error: &Error) -> Error {
+    match error {
+        Error::Rpc { code, message } => Error::Rpc {
+            code: *code,
+            message: message.clone(),
+        },
+        e => Error::Unknown(format!("RPC 请求失败: {e}")),
+    }
+}
+
+/// 解码 base64 编码的账户数据，账户不存在（null）时返回 None
+fn decode_account_data(account: &Value) -> Result<Option<Vec<u8>>> {
+    if account.is_null() {
+        return Ok(None);
+    }
+    let Some(data) = account["data"][0].as_str() else {
+        return Err(Error::ParseError(format!("账户数据格式错误: {account}")));
+    };
+    STANDARD
+        .decode(data)
+        .map(Some)
+        .map_err(|e| Error::ParseError(format!("账户数据 base64 解码失败: {e}")))
+}
+
+/// 读取代币账户的 amount 字段
+fn token_amount(data: &[u8]) -> Option<u64> {
+    let bytes = data.get(AMOUNT_OFFSET..AMOUNT_OFFSET + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// 解析 RPC 返回的字符串格式代币数量
+pub(crate) fn parse_amount(value: &Value) -> Result<u64> {
+    value
+        .as_str()
+        .and_then(|amount| amount.parse().ok())
+        .ok_or_else(|| Error::ParseError(format!("代币数量格式错误: {value}")))
+}
@@ -0,0 +1,299 @@
+use serde_json::{json, Map, Value};
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use crate::{
+    error::{Error, Result},
+    models::{MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN},
+};
+
+/// 默认用于拼接元数据 URI 的 IPFS 网关
+const DEFAULT_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// 默认上传超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Pinata 文件上传接口
+const PINATA_PIN_FILE_URL: &str = "https://api.pinata.cloud/pinning/pinFileToIPFS";
+
+/// IPFS pinning 后端
+///
+/// 上传一个文件并固定（pin），返回内容的 CID。内置 [`PinataBackend`] 和 [`KuboBackend`]，
+/// 其他服务实现该 trait 即可接入 [`MetadataUploader`]。
+pub trait PinningBackend: Send + Sync {
+    /// 上传文件，返回 CID
+    fn pin_file(
+        &self,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// Pinata pinning 服务
+#[derive(Clone)]
+pub struct PinataBackend {
+    client: reqwest::Client,
+    jwt: String,
+    timeout: Duration,
+}
+
+impl PinataBackend {
+    /// 使用 Pinata API JWT 创建后端
+    pub fn new(jwt: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            jwt: jwt.into(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 设置单次上传的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl PinningBackend for PinataBackend {
+    async fn pin_file(&self, file_name: &str, content_type: &str, data: Vec<u8>) -> Result<String> {
+        let (boundary, body) = multipart_file("file", file_name, content_type, &data);
+        let response: Value = self
+            .client
+            .post(PINATA_PIN_FILE_URL)
+            .bearer_auth(&self.jwt)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(body)
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        cid_of(&response, "IpfsHash")
+    }
+}
+
+/// 自建 IPFS 节点（Kubo RPC API）
+#[derive(Clone)]
+pub struct KuboBackend {
+    client: reqwest::Client,
+    api_url: String,
+    timeout: Duration,
+}
+
+impl KuboBackend {
+    /// 使用节点 RPC 地址创建后端，例如 `http://127.0.0.1:5001`
+    pub fn new(api_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 设置单次上传的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl PinningBackend for KuboBackend {
+    async fn pin_file(&self, file_name: &str, content_type: &str, data: Vec<u8>) -> Result<String> {
+        let (boundary, body) = multipart_file("file", file_name, content_type, &data);
+        let response: Value = self
+            .client
+            .post(format!(
+                "{}/api/v0/add?pin=true&cid-version=1",
+                self.api_url
+            ))
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(body)
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        cid_of(&response, "Hash")
+    }
+}
+
+fn cid_of(response: &Value, field: &str) -> Result<String> {
+    response[field]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| Error::Upload(format!("响应中没有 {field}: {response}")))
+}
+
+/// 代币图片
+#[derive(Clone, Debug)]
+pub struct TokenImage {
+    /// 文件名，例如 `logo.png`
+    pub file_name: String,
+    /// MIME 类型，例如 `image/png`
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+/// 发币所需的元数据，字段与 pump.fun 前端生成的元数据 JSON 一致
+#[derive(Clone, Debug, Default)]
+pub struct LaunchMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub image: Option<TokenImage>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+/// 上传结果
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadedMetadata {
+    /// 图片 URI，没有图片时为 None
+    pub image_uri: Option<String>,
+    /// 元数据 JSON 的 URI，即创建代币指令中的 `uri` 参数
+    pub metadata_uri: String,
+}
+
+/// 发币元数据上传（需要启用 `upload` feature）
+///
+/// 先上传图片，再把图片 URI 写入元数据 JSON 并上传，返回的 `metadata_uri` 即创建代币时填写的 `uri`。
+/// URI 由网关前缀和 CID 拼接，默认使用 `https://ipfs.io/ipfs/`，也可以设置为 `ipfs://`。
+///
+/// 上传前检查 name / symbol 的长度和生成的 URI 长度，避免链上创建指令因超长失败。
+#[derive(Clone)]
+pub struct MetadataUploader<B> {
+    backend: B,
+    gateway: String,
+}
+
+impl<B: PinningBackend> MetadataUploader<B> {
+    /// 使用指定的 pinning 后端创建上传器
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            gateway: DEFAULT_GATEWAY.to_string(),
+        }
+    }
+
+    /// 设置拼接 URI 的网关前缀，例如 `https://gateway.pinata.cloud/ipfs/` 或 `ipfs://`
+    pub fn with_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.gateway = gateway.into();
+        self
+    }
+
+    /// 上传图片和元数据 JSON
+    pub async fn upload(&self, metadata: &LaunchMetadata) -> Result<UploadedMetadata> {
+        check_len("name", &metadata.name, MAX_NAME_LEN)?;
+        check_len("symbol", &metadata.symbol, MAX_SYMBOL_LEN)?;
+
+        let image_uri = match &metadata.image {
+            Some(image) => {
+                let cid = self
+                    .backend
+                    .pin_file(&image.file_name, &image.content_type, image.data.clone())
+                    .await?;
+                Some(self.uri(&cid))
+            }
+            None => None,
+        };
+
+        let json = metadata_json(metadata, image_uri.as_deref());
+        let file_name = format!("{}.json", metadata.symbol);
+        let cid = self
+            .backend
+            .pin_file(
+                &file_name,
+                "application/json",
+                json.to_string().into_bytes(),
+            )
+            .await?;
+        let metadata_uri = self.uri(&cid);
+        check_len("uri", &metadata_uri, MAX_URI_LEN)?;
+
+        Ok(UploadedMetadata {
+            image_uri,
+            metadata_uri,
+        })
+    }
+
+    fn uri(&self, cid: &str) -> String {
+        format!("{}{}", self.gateway, cid)
+    }
+}
+
+fn check_len(field: &str, value: &str, max: usize) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(Error::Upload(format!("{field} 为空")));
+    }
+    if value.len() > max {
+        return Err(Error::Upload(format!(
+            "{field} 长度 {} 超过上限 {max}",
+            value.len()
+        )));
+    }
+    Ok(())
+}
+
+/// 生成元数据 JSON，未设置的社交链接不写入
+fn metadata_json(metadata: &LaunchMetadata, image_uri: Option<&str>) -> Value {
+    let mut json = Map::new();
+    json.insert("name".to_string(), json!(metadata.name));
+    json.insert("symbol".to_string(), json!(metadata.symbol));
+    json.insert("description".to_string(), json!(metadata.description));
+    if let Some(image) = image_uri {
+        json.insert("image".to_string(), json!(image));
+    }
+    json.insert("showName".to_string(), json!(true));
+    for (key, value) in [
+        ("twitter", &metadata.twitter),
+        ("telegram", &metadata.telegram),
+        ("website", &metadata.website),
+    ] {
+        if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            json.insert(key.to_string(), json!(value));
+        }
+    }
+    Value::Object(json)
+}
+
+/// 编码只含一个文件字段的 multipart/form-data 请求体，返回 (boundary, body)
+fn multipart_file(
+    field: &str,
+    file_name: &str,
+    content_type: &str,
+    data: &[u8],
+) -> (String, Vec<u8>) {
+    // RandomState 每次使用随机密钥，足以生成不会出现在文件内容中的分隔符
+    let random = || RandomState::new().build_hasher().finish();
+    let boundary = format!("----pump-sdk-{:016x}{:016x}", random(), random());
+    // 文件名中的引号和换行会破坏 Content-Disposition 头
+    let file_name: String = file_name
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\r' | '\n'))
+        .collect();
+
+    let mut body = Vec::with_capacity(data.len() + 256);
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\nContent-Type: {content_type}\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    (boundary, body)
+}
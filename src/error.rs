@@ -3,6 +3,7 @@ use thiserror::Error;
 /// SDK错误类型
 #[derive(Error, Debug)]
 pub enum Error {
+    #[cfg(feature = "client")]
     #[error("gRPC客户端错误: {0}")]
     GrpcClient(#[from] yellowstone_grpc_client::GeyserGrpcClientError),
 
@@ -27,6 +28,37 @@ pub enum Error {
     #[error("Borsh反序列化错误: {0}")]
     BorshDeserialize(#[from] std::io::Error),
 
+    #[error("{type_name} 字段 {field}（偏移 {offset}）解码失败: {source}")]
+    FieldDecode {
+        type_name: &'static str,
+        field: &'static str,
+        offset: usize,
+        source: std::io::Error,
+    },
+
+    #[cfg(feature = "arrow")]
+    #[error("Arrow错误: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    #[cfg(any(feature = "metadata", feature = "rpc", feature = "api"))]
+    #[error("HTTP请求错误: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[cfg(feature = "rpc")]
+    #[error("RPC错误 {code}: {message}")]
+    Rpc { code: i64, message: String },
+
+    #[error("靓号后缀无效: {0}")]
+    VanityPattern(String),
+
+    #[cfg(feature = "upload")]
+    #[error("元数据上传错误: {0}")]
+    Upload(String),
+
+    #[cfg(feature = "journal")]
+    #[error("事件日志错误: {0}")]
+    Journal(String),
+
     #[error("签名解析错误")]
     SignatureParse,
 
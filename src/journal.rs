@@ -0,0 +1,474 @@
+//! zstd 压缩的二进制事件日志（需要启用 `journal` feature）
+//!
+//! 用于低成本归档 Pump 事件流并在之后重放。整个文件是一个 zstd 流，解压后的内容为：
+//!
+//! - 文件头：8 字节魔数 `PUMPJRNL` 和 1 字节版本号
+//! - 任意数量的帧：4 字节小端帧长度，随后是帧内容——
+//!   槽位（u64 小端）、交易索引（u64 小端）、64 字节签名和事件数据
+//!   （discriminator 加 Borsh 编码，格式与 `Program data:` 日志 base64 解码后的内容相同）
+//!
+//! 事件数据由解码后的模型重新序列化得到，而不是日志中的原始字节。Borsh 编码是确定的，
+//! 宽容解码模式下未知的末尾字节也会附加在后面，因此写入的数据与链上发出的内容一致；
+//! 严格模式下程序升级新增了字段的事件解码失败、不会分发，也就不会写入日志，需要完整归档时使用宽容模式。
+
+use solana_sdk::signature::Signature;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    client::{EventContext, EventHandler},
+    error::{Error, Result},
+    models::*,
+    parser::events::{decode_event_with, encode_program_data, try_decode_event, DecodeMode},
+};
+
+/// 文件头魔数
+const MAGIC: &[u8; 8] = b"PUMPJRNL";
+
+/// 当前格式版本
+const VERSION: u8 = 1;
+
+/// 帧内容中原始数据之前的固定长度：槽位、交易索引和签名
+const FRAME_HEADER_LEN: usize = 8 + 8 + 64;
+
+/// 单帧允许的最大长度，超过时视为文件损坏
+const MAX_FRAME_LEN: usize = 1 << 20;
+
+/// 默认压缩级别
+pub const DEFAULT_LEVEL: i32 = 3;
+
+/// 日志中的一条事件记录
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub slot: u64,
+    pub tx_index: u64,
+    pub signature: Signature,
+    /// 事件数据（包含 8 字节 discriminator）
+    pub data: Vec<u8>,
+}
+
+impl JournalEntry {
+    /// 由事件及其上下文构建
+    pub fn new(event: &PumpEvent, ctx: &EventContext) -> Self {
+        let mut data = encode_program_data(event);
+        // 宽容解码模式下保留未知的末尾字节，重放时不丢失程序升级新增的字段
+        if let Some(tail) = &ctx.unknown_tail {
+            data.extend_from_slice(tail);
+        }
+        Self {
+            slot: ctx.slot,
+            tx_index: ctx.tx_index,
+            signature: ctx.signature,
+            data,
+        }
+    }
+
+    /// 事件的 discriminator，数据不足 8 字节时为 None
+    pub fn discriminator(&self) -> Option<&[u8]> {
+        self.data.get(..8)
+    }
+
+    /// 解码事件，数据末尾有模型之外的字节（宽容解码模式下记录的事件）时忽略这些字节
+    pub fn decode(&self) -> Result<PumpEvent> {
+        if self.data.len() < 8 {
+            return Err(Error::Journal("事件数据不足 8 字节".to_string()));
+        }
+        let (discriminator, data) = self.data.split_at(8);
+        try_decode_event(discriminator, data).or_else(|e| {
+            decode_event_with(discriminator, data, DecodeMode::Tolerant)
+                .map(|(event, _)| event)
+                .ok_or(e)
+        })
+    }
+}
+
+fn io_error(context: &str, e: io::Error) -> Error {
+    Error::Journal(format!("{context}: {e}"))
+}
+
+/// 事件日志写入器
+///
+/// 被丢弃时自动结束 zstd 流；需要处理结束时的错误或取回底层写入器时使用 [`finish`](Self::finish)。
+pub struct JournalWriter<W: Write> {
+    encoder: Option<zstd::Encoder<'static, W>>,
+    entries: u64,
+}
+
+impl JournalWriter<BufWriter<File>> {
+    /// 创建（或覆盖）日志文件，使用默认压缩级别
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path).map_err(|e| io_error("创建事件日志失败", e))?;
+        Self::new(BufWriter::new(file), DEFAULT_LEVEL)
+    }
+}
+
+impl<W: Write> JournalWriter<W> {
+    /// 在底层写入器上开始新的日志，`level` 为 zstd 压缩级别（1 ~ 22，0 表示 zstd 默认级别）
+    pub fn new(writer: W, level: i32) -> Result<Self> {
+        let mut encoder =
+            zstd::Encoder::new(writer, level).map_err(|e| io_error("初始化压缩器失败", e))?;
+        encoder
+            .write_all(MAGIC)
+            .and_then(|_| encoder.write_all(&[VERSION]))
+            .map_err(|e| io_error("写入事件日志失败", e))?;
+        Ok(Self {
+            encoder: Some(encoder),
+            entries: 0,
+        })
+    }
+
+    /// 追加一条记录
+    pub fn append(&mut self, entry: &JournalEntry) -> Result<()> {
+        let len = FRAME_HEADER_LEN + entry.data.len();
+        if len > MAX_FRAME_LEN {
+            return Err(Error::Journal(format!(
+                "事件数据过大: {} 字节",
+                entry.data.len()
+            )));
+        }
+        let encoder = self
+            .encoder
+            .as_mut()
+            .expect("encoder is present until finish");
+        let mut frame = Vec::with_capacity(4 + len);
+        frame.extend_from_slice(&(len as u32).to_le_bytes());
+        frame.extend_from_slice(&entry.slot.to_le_bytes());
+        frame.extend_from_slice(&entry.tx_index.to_le_bytes());
+        frame.extend_from_slice(entry.signature.as_ref());
+        frame.extend_from_slice(&entry.data);
+        encoder
+            .write_all(&frame)
+            .map_err(|e| io_error("写入事件日志失败", e))?;
+        self.entries += 1;
+        Ok(())
+    }
+
+    /// 追加一个事件
+    pub fn append_event(&mut self, event: &PumpEvent, ctx: &EventContext) -> Result<()> {
+        self.append(&JournalEntry::new(event, ctx))
+    }
+
+    /// 已写入的记录数
+    pub fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    /// 把压缩器中的数据写出到底层写入器
+    ///
+    /// zstd 帧尚未结束，进程崩溃时最后一段数据可能无法解压；需要持久化时使用 [`finish`](Self::finish)。
+    pub fn flush(&mut self) -> Result<()> {
+        let encoder = self
+            .encoder
+            .as_mut()
+            .expect("encoder is present until finish");
+        encoder.flush().map_err(|e| io_error("刷新事件日志失败", e))
+    }
+
+    /// 结束 zstd 流并返回底层写入器
+    pub fn finish(mut self) -> Result<W> {
+        let encoder = self
+            .encoder
+            .take()
+            .expect("encoder is present until finish");
+        let mut writer = encoder
+            .finish()
+            .map_err(|e| io_error("结束事件日志失败", e))?;
+        writer
+            .flush()
+            .map_err(|e| io_error("结束事件日志失败", e))?;
+        Ok(writer)
+    }
+}
+
+impl<W: Write> Drop for JournalWriter<W> {
+    fn drop(&mut self) {
+        if let Some(mut encoder) = self.encoder.take() {
+            if let Err(e) = encoder.do_finish() {
+                log::warn!("结束事件日志失败: {}", e);
+            }
+        }
+    }
+}
+
+/// 事件日志读取器，按写入顺序迭代记录
+///
+/// 文件末尾不完整的帧（例如写入过程中进程崩溃）产生一个错误后结束迭代。
+pub struct JournalReader<R: Read> {
+    decoder: zstd::Decoder<'static, BufReader<R>>,
+    done: bool,
+}
+
+impl JournalReader<File> {
+    /// 打开日志文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path).map_err(|e| io_error("打开事件日志失败", e))?;
+        Self::new(file)
+    }
+}
+
+impl<R: Read> JournalReader<R> {
+    /// 从底层读取器读取日志，校验文件头
+    pub fn new(reader: R) -> Result<Self> {
+        let mut decoder =
+            zstd::Decoder::new(reader).map_err(|e| io_error("初始化解压器失败", e))?;
+        let mut header = [0u8; 9];
+        decoder
+            .read_exact(&mut header)
+            .map_err(|e| io_error("读取事件日志文件头失败", e))?;
+        if &header[..8] != MAGIC {
+            return Err(Error::Journal("不是事件日志文件".to_string()));
+        }
+        if header[8] != VERSION {
+            return Err(Error::Journal(format!(
+                "不支持的事件日志版本: {}",
+                header[8]
+            )));
+        }
+        Ok(Self {
+            decoder,
+            done: false,
+        })
+    }
+
+    /// 把日志中的事件依次分发给处理器，返回分发的事件数
+    ///
+    /// 用于回测：把归档的事件流重放给分析组件或策略处理器。上下文中的 `received_at` 为重放时的时间，
+    /// `block_time` 取自事件中记录的链上时钟。无法解码的记录记录日志后跳过。
+    pub fn replay<H: EventHandler + ?Sized>(self, handler: &H) -> Result<u64> {
+        let filters: Arc<[String]> = Arc::from(Vec::new());
+        let mut dispatched = 0;
+        for entry in self {
+            let entry = entry?;
+            let event = match entry.decode() {
+                Ok(event) => event,
+                Err(e) => {
+                    log::warn!("跳过无法解码的记录 {}: {}", entry.signature, e);
+                    continue;
+                }
+            };
+            let ctx = EventContext {
+                slot: entry.slot,
+                tx_index: entry.tx_index,
+                signature: entry.signature,
+                timestamp: Instant::now(),
+                elapsed: Duration::ZERO,
+                received_at: SystemTime::now(),
+                block_time: Some(event.timestamp()),
+                filters: Arc::clone(&filters),
+                unknown_tail: None,
+            };
+            crate::client::handler::dispatch_event(handler, &event, &ctx);
+            dispatched += 1;
+        }
+        Ok(dispatched)
+    }
+
+    fn read_entry(&mut self) -> Result<Option<JournalEntry>> {
+        let mut len = [0u8; 4];
+        // 在帧边界上结束属于正常结束
+        match self.decoder.read(&mut len[..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(e) => return Err(io_error("读取事件日志失败", e)),
+        }
+        self.decoder
+            .read_exact(&mut len[1..])
+            .map_err(|e| io_error("事件日志帧不完整", e))?;
+        let len = u32::from_le_bytes(len) as usize;
+        if !(FRAME_HEADER_LEN..=MAX_FRAME_LEN).contains(&len) {
+            return Err(Error::Journal(format!("事件日志帧长度无效: {len}")));
+        }
+        let mut frame = vec![0u8; len];
+        self.decoder
+            .read_exact(&mut frame)
+            .map_err(|e| io_error("事件日志帧不完整", e))?;
+        let (header, data) = frame.split_at(FRAME_HEADER_LEN);
+        let slot = u64::from_le_bytes(header[..8].try_into().expect("8 bytes"));
+        let tx_index = u64::from_le_bytes(header[8..16].try_into().expect("8 bytes"));
+        let signature = Signature::try_from(&header[16..]).map_err(|_| Error::SignatureParse)?;
+        Ok(Some(JournalEntry {
+            slot,
+            tx_index,
+            signature,
+            data: data.to_vec(),
+        }))
+    }
+}
+
+impl<R: Read> Iterator for JournalReader<R> {
+    type Item = Result<JournalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_entry() {
+            Ok(Some(entry)) => Some(Ok(entry)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// 把收到的事件写入事件日志的处理器
+///
+/// 作为订阅的处理器（或注册到 `HandlerRegistry`）即可归档事件流。写入失败时记录日志并丢弃该事件。
+/// 克隆后的实例写入同一个日志，所有克隆被丢弃后结束 zstd 流。
+#[derive(Clone)]
+pub struct JournalRecorder {
+    writer: Arc<Mutex<JournalWriter<BufWriter<File>>>>,
+}
+
+impl JournalRecorder {
+    /// 创建（或覆盖）日志文件，`level` 为 zstd 压缩级别
+    pub fn create(path: impl AsRef<Path>, level: i32) -> Result<Self> {
+        let file = File::create(path).map_err(|e| io_error("创建事件日志失败", e))?;
+        let writer = JournalWriter::new(BufWriter::new(file), level)?;
+        Ok(Self {
+            writer: Arc::new(Mutex::new(writer)),
+        })
+    }
+
+    /// 已写入的记录数
+    pub fn entries(&self) -> u64 {
+        self.lock().entries()
+    }
+
+    /// 把缓冲的数据写出到文件
+    pub fn flush(&self) -> Result<()> {
+        let mut writer = self.lock();
+        writer.flush()?;
+        writer
+            .encoder
+            .as_mut()
+            .expect("encoder is present until finish")
+            .get_mut()
+            .flush()
+            .map_err(|e| io_error("刷新事件日志失败", e))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JournalWriter<BufWriter<File>>> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl EventHandler for JournalRecorder {
+    fn on_event(&self, event: &PumpEvent, ctx: &EventContext) {
+        if let Err(e) = self.lock().append_event(event, ctx) {
+            log::warn!("写入事件日志失败: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn ctx(slot: u64, unknown_tail: Option<Arc<[u8]>>) -> EventContext {
+        EventContext {
+            slot,
+            tx_index: 7,
+            signature: Signature::new_unique(),
+            timestamp: Instant::now(),
+            elapsed: Duration::ZERO,
+            received_at: SystemTime::now(),
+            block_time: None,
+            filters: Arc::from(Vec::new()),
+            unknown_tail,
+        }
+    }
+
+    fn trade() -> PumpEvent {
+        PumpEvent::Trade(TradeEvent {
+            mint: Pubkey::new_unique(),
+            sol_amount: 1_000,
+            is_buy: true,
+            ix_name: "buy".to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn round_trips_entries_in_order() {
+        let events = [trade(), PumpEvent::Complete(CompleteEvent::default())];
+        let mut writer = JournalWriter::new(Vec::new(), DEFAULT_LEVEL).unwrap();
+        let entries: Vec<_> = events
+            .iter()
+            .enumerate()
+            .map(|(slot, event)| JournalEntry::new(event, &ctx(slot as u64, None)))
+            .collect();
+        for entry in &entries {
+            writer.append(entry).unwrap();
+        }
+        assert_eq!(writer.entries(), 2);
+        let bytes = writer.finish().unwrap();
+
+        let read: Vec<_> = JournalReader::new(bytes.as_slice())
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(read, entries);
+        let decoded: Vec<_> = read.iter().map(|entry| entry.decode().unwrap()).collect();
+        assert_eq!(decoded, events);
+    }
+
+    #[test]
+    fn recorder_writes_one_entry_per_event() {
+        let path = std::env::temp_dir().join(format!("pump-journal-{}.journal", Signature::new_unique()));
+        let recorder = JournalRecorder::create(&path, DEFAULT_LEVEL).unwrap();
+        let event = trade();
+        crate::client::handler::dispatch_event(&recorder, &event, &ctx(1, None));
+        assert_eq!(recorder.entries(), 1);
+        drop(recorder);
+
+        let entries: Vec<_> = JournalReader::open(&path)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].decode().unwrap(), event);
+    }
+
+    #[test]
+    fn keeps_and_tolerates_unknown_tail() {
+        let event = trade();
+        let entry = JournalEntry::new(&event, &ctx(1, Some(Arc::from(&[1u8, 2, 3][..]))));
+        assert!(entry.data.ends_with(&[1, 2, 3]));
+        assert_eq!(entry.decode().unwrap(), event);
+        assert!(JournalEntry { data: vec![0; 4], ..entry }.decode().is_err());
+    }
+
+    #[test]
+    fn rejects_foreign_files() {
+        let bytes = zstd::encode_all(&b"NOTAJRNL\x01"[..], DEFAULT_LEVEL).unwrap();
+        assert!(matches!(JournalReader::new(bytes.as_slice()), Err(Error::Journal(_))));
+        let bytes = zstd::encode_all(&b"PUMPJRNL\x02"[..], DEFAULT_LEVEL).unwrap();
+        assert!(matches!(JournalReader::new(bytes.as_slice()), Err(Error::Journal(_))));
+    }
+
+    #[test]
+    fn truncated_frame_ends_with_error() {
+        let mut raw = MAGIC.to_vec();
+        raw.push(VERSION);
+        raw.extend_from_slice(&((FRAME_HEADER_LEN + 16) as u32).to_le_bytes());
+        raw.extend_from_slice(&[0; FRAME_HEADER_LEN]);
+        let bytes = zstd::encode_all(raw.as_slice(), DEFAULT_LEVEL).unwrap();
+
+        let mut reader = JournalReader::new(bytes.as_slice()).unwrap();
+        assert!(matches!(reader.next(), Some(Err(Error::Journal(_)))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -1,15 +1,44 @@
+#[cfg(feature = "client")]
+pub mod analytics;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "client")]
 pub mod client;
+#[cfg(any(feature = "metadata", feature = "rpc", feature = "api"))]
+pub mod enrich;
 pub mod error;
+#[cfg(feature = "journal")]
+pub mod journal;
 pub mod models;
+#[cfg(feature = "client")]
+pub mod notify;
 pub mod parser;
+pub mod pda;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+pub mod vanity;
 
 // 重新导出公共API
+#[cfg(feature = "client")]
 pub use client::{
-    Config, EventContext, EventFilter, EventHandler, FilteredLoggingEventHandler, GrpcClient,
-    LoggingEventHandler,
+    AccountCache, AccountContext, AccountUpdate, BlockMetaUpdate, ChannelDeadLetterSink,
+    CheckpointStore, ClientStatus, Config, ConnectionState, CustomEvents, DeadLetter,
+    DeadLetterReason, DeadLetterSink, DispatchOrder, EntryUpdate, EventContext, EventFilter,
+    EventHandler, EventLanes, EventRouter, FeeParams, FeeWatcher, FileCheckpointStore,
+    FileDeadLetterSink, FillOutcome, FillReport, FillTracker, FilteredLoggingEventHandler,
+    GraduatedToken, GraduatedTokens, GrpcClient, HandlerId, HandlerMetrics, HandlerRegistry,
+    LaneMetrics, LatencyHistogram, LatencyProfiler, LatencyReport, LatencyStage,
+    LoggingEventHandler, MemoryCheckpointStore, PoolKeys, PoolReserves, PoolTracker,
+    SharedEventFilter, ShedPolicy, SlotUpdate, StreamLag, StreamStats, Subscription,
+    SubscriptionFilter, TokenBalance, TransactionUpdate, Watchlist,
 };
+#[cfg(feature = "sqlite")]
+pub use client::SqliteCheckpointStore;
 pub use error::{Error, Result};
 pub use models::*;
+pub use parser::errors::PumpProgramError;
+pub use parser::events::{DecodeMode, EventSource};
+pub use parser::instructions::{DecodedInstruction, PumpInstruction};
 
 /// SDK版本信息
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,141 @@
+use thiserror::Error;
+
+use super::{CreateEvent, CreateV2Event};
+
+/// 名称最大字节数（与 Metaplex 元数据限制一致）
+pub const MAX_NAME_LEN: usize = 32;
+/// 符号最大字节数
+pub const MAX_SYMBOL_LEN: usize = 10;
+/// URI 最大字节数
+pub const MAX_URI_LEN: usize = 200;
+/// 允许的 URI scheme
+pub const ALLOWED_URI_SCHEMES: &[&str] = &["https", "ipfs", "ar"];
+
+/// 元数据字段
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+}
+
+/// 元数据校验问题
+///
+/// 事件中的字符串经过 Borsh 解码时已保证是合法 UTF-8，这里只检查内容本身。
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum MetadataIssue {
+    #[error("{0:?} 为空")]
+    Empty(MetadataField),
+
+    #[error("{field:?} 长度 {len} 超过上限 {max}")]
+    TooLong {
+        field: MetadataField,
+        len: usize,
+        max: usize,
+    },
+
+    #[error("{0:?} 包含控制字符或不可见字符")]
+    ControlCharacters(MetadataField),
+
+    #[error("不支持的 URI scheme: {0}")]
+    UnsupportedUriScheme(String),
+}
+
+/// 清洗后的代币元数据，可以直接写入日志、数据库或展示在界面上
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    /// scheme 不在白名单内时为 None
+    pub uri: Option<String>,
+}
+
+/// 是否为需要移除的字符：控制字符，以及可用于视觉欺骗的零宽字符和双向文本控制符
+fn is_unsafe_char(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{FEFF}'
+        )
+}
+
+fn uri_scheme(uri: &str) -> Option<&str> {
+    uri.split_once("://").map(|(scheme, _)| scheme)
+}
+
+fn check_field(field: MetadataField, value: &str, max: usize, issues: &mut Vec<MetadataIssue>) {
+    if value.trim().is_empty() {
+        issues.push(MetadataIssue::Empty(field));
+    }
+    if value.len() > max {
+        issues.push(MetadataIssue::TooLong {
+            field,
+            len: value.len(),
+            max,
+        });
+    }
+    if value.chars().any(is_unsafe_char) {
+        issues.push(MetadataIssue::ControlCharacters(field));
+    }
+}
+
+/// 移除不安全字符、去掉首尾空白，并在字符边界处截断到 `max` 字节
+fn sanitize_field(value: &str, max: usize) -> String {
+    let cleaned: String = value.chars().filter(|c| !is_unsafe_char(*c)).collect();
+    let mut out = String::with_capacity(cleaned.len().min(max));
+    for c in cleaned.trim().chars() {
+        if out.len() + c.len_utf8() > max {
+            break;
+        }
+        out.push(c);
+    }
+    out.trim_end().to_string()
+}
+
+fn validate(name: &str, symbol: &str, uri: &str) -> Vec<MetadataIssue> {
+    let mut issues = Vec::new();
+    check_field(MetadataField::Name, name, MAX_NAME_LEN, &mut issues);
+    check_field(MetadataField::Symbol, symbol, MAX_SYMBOL_LEN, &mut issues);
+    check_field(MetadataField::Uri, uri, MAX_URI_LEN, &mut issues);
+    if !uri.trim().is_empty() {
+        let scheme = uri_scheme(uri.trim()).unwrap_or_default();
+        if !ALLOWED_URI_SCHEMES.contains(&scheme) {
+            issues.push(MetadataIssue::UnsupportedUriScheme(scheme.to_string()));
+        }
+    }
+    issues
+}
+
+fn sanitize(name: &str, symbol: &str, uri: &str) -> TokenMetadata {
+    let uri = sanitize_field(uri, MAX_URI_LEN);
+    let uri_allowed = uri_scheme(&uri).is_some_and(|scheme| ALLOWED_URI_SCHEMES.contains(&scheme));
+    TokenMetadata {
+        name: sanitize_field(name, MAX_NAME_LEN),
+        symbol: sanitize_field(symbol, MAX_SYMBOL_LEN),
+        uri: uri_allowed.then_some(uri),
+    }
+}
+
+impl CreateEvent {
+    /// 校验创建者提供的 name / symbol / uri，没有问题时返回空列表
+    pub fn validate_metadata(&self) -> Vec<MetadataIssue> {
+        validate(&self.name, &self.symbol, &self.uri)
+    }
+
+    /// 清洗后的元数据：移除控制字符与不可见字符、截断超长字段、丢弃不在白名单内的 URI
+    pub fn sanitized(&self) -> TokenMetadata {
+        sanitize(&self.name, &self.symbol, &self.uri)
+    }
+}
+
+impl CreateV2Event {
+    /// 校验创建者提供的 name / symbol / uri，没有问题时返回空列表
+    pub fn validate_metadata(&self) -> Vec<MetadataIssue> {
+        validate(&self.name, &self.symbol, &self.uri)
+    }
+
+    /// 清洗后的元数据：移除控制字符与不可见字符、截断超长字段、丢弃不在白名单内的 URI
+    pub fn sanitized(&self) -> TokenMetadata {
+        sanitize(&self.name, &self.symbol, &self.uri)
+    }
+}
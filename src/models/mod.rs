@@ -1,6 +1,14 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 
+use crate::pda::WSOL_MINT;
+
+mod metadata;
+mod new_token;
+
+pub use metadata::*;
+pub use new_token::{CurveState, DevBuy, NewToken, OffchainMetadata};
+
 #[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
 pub struct CreateEvent {
     pub name: String,
@@ -156,3 +164,442 @@ pub struct CreatePoolEvent {
     pub coin_creator: Pubkey,
     pub is_mayhem_mode: bool,
 }
+
+/// PumpAmm 添加流动性（`deposit`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct DepositEvent {
+    pub timestamp: i64,
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub lp_mint_supply: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub user_pool_token_account: Pubkey,
+}
+
+/// PumpAmm 移除流动性（`withdraw`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct WithdrawEvent {
+    pub timestamp: i64,
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub base_amount_out: u64,
+    pub quote_amount_out: u64,
+    pub lp_mint_supply: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub user_pool_token_account: Pubkey,
+}
+
+/// bonding curve 完成后迁移到 PumpAmm（`migrate`）
+///
+/// 对应链上的 `CompletePumpAmmMigrationEvent`，由 Pump 程序在迁移交易中发出。
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct MigrateEvent {
+    /// 支付迁移费用的用户
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    /// 迁移到池中的代币数量
+    pub mint_amount: u64,
+    /// 迁移到池中的 SOL（lamports）
+    pub sol_amount: u64,
+    /// 迁移手续费（lamports）
+    pub pool_migration_fee: u64,
+    pub bonding_curve: Pubkey,
+    pub timestamp: i64,
+    /// 迁移生成的 PumpAmm 池
+    pub pool: Pubkey,
+}
+
+/// Pump 创建者领取 bonding curve 交易累积的创建者手续费（`collect_creator_fee`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct CollectCreatorFeeEvent {
+    pub timestamp: i64,
+    pub creator: Pubkey,
+    /// 领取的手续费（lamports）
+    pub creator_fee: u64,
+}
+
+/// PumpAmm 代币创建者领取池交易累积的创建者手续费（`collect_coin_creator_fee`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct CollectCoinCreatorFeeEvent {
+    pub timestamp: i64,
+    pub coin_creator: Pubkey,
+    /// 领取的手续费（quote 代币最小单位，通常为 WSOL）
+    pub coin_creator_fee: u64,
+    /// 手续费转出的创建者金库 ATA
+    pub coin_creator_vault_ata: Pubkey,
+    /// 接收手续费的创建者代币账户
+    pub coin_creator_token_account: Pubkey,
+}
+
+/// Pump 全局参数修改（`set_params`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct SetParamsEvent {
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub final_real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    pub withdraw_authority: Pubkey,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: u64,
+    pub creator_fee_basis_points: u64,
+    pub fee_recipients: [Pubkey; 8],
+    pub timestamp: i64,
+    pub set_creator_authority: Pubkey,
+    pub admin_set_creator_authority: Pubkey,
+}
+
+/// Pump 全局管理员变更（`update_global_authority`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct UpdateGlobalAuthorityEvent {
+    pub global: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// bonding curve 创建者变更（`set_creator`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct SetCreatorEvent {
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+}
+
+/// 按 Metaplex 元数据设置 bonding curve 创建者（`set_metaplex_creator`）
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct SetMetaplexCreatorEvent {
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub metadata: Pubkey,
+    pub creator: Pubkey,
+}
+
+/// Pump / PumpAmm 事件
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum PumpEvent {
+    Create(CreateEvent),
+    CreateV2(CreateV2Event),
+    Complete(CompleteEvent),
+    Trade(TradeEvent),
+    Buy(BuyEvent),
+    Sell(SellEvent),
+    CreatePool(CreatePoolEvent),
+    SetParams(SetParamsEvent),
+    UpdateGlobalAuthority(UpdateGlobalAuthorityEvent),
+    SetCreator(SetCreatorEvent),
+    SetMetaplexCreator(SetMetaplexCreatorEvent),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    CollectCreatorFee(CollectCreatorFeeEvent),
+    CollectCoinCreatorFee(CollectCoinCreatorFeeEvent),
+    Migrate(MigrateEvent),
+}
+
+impl PumpEvent {
+    /// 事件中记录的链上时间戳（Unix 秒）
+    pub fn timestamp(&self) -> i64 {
+        match self {
+            PumpEvent::Create(e) => e.timestamp,
+            PumpEvent::CreateV2(e) => e.timestamp,
+            PumpEvent::Complete(e) => e.timestamp,
+            PumpEvent::Trade(e) => e.timestamp,
+            PumpEvent::Buy(e) => e.timestamp,
+            PumpEvent::Sell(e) => e.timestamp,
+            PumpEvent::CreatePool(e) => e.timestamp,
+            PumpEvent::SetParams(e) => e.timestamp,
+            PumpEvent::UpdateGlobalAuthority(e) => e.timestamp,
+            PumpEvent::SetCreator(e) => e.timestamp,
+            PumpEvent::SetMetaplexCreator(e) => e.timestamp,
+            PumpEvent::Deposit(e) => e.timestamp,
+            PumpEvent::Withdraw(e) => e.timestamp,
+            PumpEvent::CollectCreatorFee(e) => e.timestamp,
+            PumpEvent::CollectCoinCreatorFee(e) => e.timestamp,
+            PumpEvent::Migrate(e) => e.timestamp,
+        }
+    }
+
+    /// 交易类事件的方向，非交易事件返回 None
+    pub fn side(&self) -> Option<Side> {
+        match self {
+            PumpEvent::Trade(e) => Some(e.side()),
+            PumpEvent::Buy(_) => Some(Side::Buy),
+            PumpEvent::Sell(_) => Some(Side::Sell),
+            _ => None,
+        }
+    }
+
+    /// 是否为买入（Trade 买入或 PumpAmm Buy）
+    pub fn is_buy(&self) -> bool {
+        self.side() == Some(Side::Buy)
+    }
+
+    /// 是否为卖出（Trade 卖出或 PumpAmm Sell）
+    pub fn is_sell(&self) -> bool {
+        self.side() == Some(Side::Sell)
+    }
+
+    /// 事件涉及的代币
+    ///
+    /// CreatePool 事件取非 WSOL 的一侧；PumpAmm 的 Buy / Sell / Deposit / Withdraw 事件只记录池地址，
+    /// 全局参数类事件（SetParams / UpdateGlobalAuthority）和创建者手续费领取事件不涉及单个代币，均返回 None。
+    pub fn mint(&self) -> Option<Pubkey> {
+        match self {
+            PumpEvent::Create(e) => Some(e.mint),
+            PumpEvent::CreateV2(e) => Some(e.mint),
+            PumpEvent::Complete(e) => Some(e.mint),
+            PumpEvent::Trade(e) => Some(e.mint),
+            PumpEvent::CreatePool(e) if e.base_mint == WSOL_MINT => Some(e.quote_mint),
+            PumpEvent::CreatePool(e) => Some(e.base_mint),
+            PumpEvent::SetCreator(e) => Some(e.mint),
+            PumpEvent::SetMetaplexCreator(e) => Some(e.mint),
+            PumpEvent::Migrate(e) => Some(e.mint),
+            PumpEvent::Buy(_)
+            | PumpEvent::Sell(_)
+            | PumpEvent::Deposit(_)
+            | PumpEvent::Withdraw(_)
+            | PumpEvent::SetParams(_)
+            | PumpEvent::UpdateGlobalAuthority(_)
+            | PumpEvent::CollectCreatorFee(_)
+            | PumpEvent::CollectCoinCreatorFee(_) => None,
+        }
+    }
+
+    /// PumpAmm 事件的池地址，Migrate 事件为迁移生成的池，其余 Pump 事件返回 None
+    pub fn pool(&self) -> Option<Pubkey> {
+        match self {
+            PumpEvent::Buy(e) => Some(e.pool),
+            PumpEvent::Sell(e) => Some(e.pool),
+            PumpEvent::CreatePool(e) => Some(e.pool),
+            PumpEvent::Deposit(e) => Some(e.pool),
+            PumpEvent::Withdraw(e) => Some(e.pool),
+            PumpEvent::Migrate(e) => Some(e.pool),
+            _ => None,
+        }
+    }
+
+    /// 发起事件的用户（CreatePool 为池的创建者，UpdateGlobalAuthority 为原管理员，手续费领取事件为创建者）
+    ///
+    /// SetParams / SetCreator / SetMetaplexCreator 事件没有记录发起者，返回 None。
+    pub fn user(&self) -> Option<Pubkey> {
+        match self {
+            PumpEvent::Create(e) => Some(e.user),
+            PumpEvent::CreateV2(e) => Some(e.user),
+            PumpEvent::Complete(e) => Some(e.user),
+            PumpEvent::Trade(e) => Some(e.user),
+            PumpEvent::Buy(e) => Some(e.user),
+            PumpEvent::Sell(e) => Some(e.user),
+            PumpEvent::CreatePool(e) => Some(e.creator),
+            PumpEvent::Deposit(e) => Some(e.user),
+            PumpEvent::Withdraw(e) => Some(e.user),
+            PumpEvent::CollectCreatorFee(e) => Some(e.creator),
+            PumpEvent::CollectCoinCreatorFee(e) => Some(e.coin_creator),
+            PumpEvent::Migrate(e) => Some(e.user),
+            PumpEvent::UpdateGlobalAuthority(e) => Some(e.authority),
+            PumpEvent::SetParams(_)
+            | PumpEvent::SetCreator(_)
+            | PumpEvent::SetMetaplexCreator(_) => None,
+        }
+    }
+
+    /// 交易类事件的 SOL 数量（lamports，不含手续费），非交易事件返回 None
+    ///
+    /// PumpAmm 事件取 quote 数量（Buy 为 `quote_amount_in`，Sell 为 `quote_amount_out`），quote 通常为 WSOL。
+    pub fn sol_amount(&self) -> Option<u64> {
+        match self {
+            PumpEvent::Trade(e) => Some(e.sol_amount),
+            PumpEvent::Buy(e) => Some(e.quote_amount_in),
+            PumpEvent::Sell(e) => Some(e.quote_amount_out),
+            _ => None,
+        }
+    }
+
+    /// 交易类事件的代币数量（最小单位），非交易事件返回 None
+    pub fn token_amount(&self) -> Option<u64> {
+        match self {
+            PumpEvent::Trade(e) => Some(e.token_amount),
+            PumpEvent::Buy(e) => Some(e.base_amount_out),
+            PumpEvent::Sell(e) => Some(e.base_amount_in),
+            _ => None,
+        }
+    }
+}
+
+/// 交易方向
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+// 以下 delta 均从交易用户的视角计算：流入用户钱包为正，流出为负。
+
+impl TradeEvent {
+    /// 交易方向
+    pub fn side(&self) -> Side {
+        if self.is_buy {
+            Side::Buy
+        } else {
+            Side::Sell
+        }
+    }
+
+    /// 用户 SOL 变化（lamports），包含协议手续费和创建者手续费
+    ///
+    /// 买入时用户支付 `sol_amount + fee + creator_fee`，卖出时收到 `sol_amount - fee - creator_fee`。
+    pub fn sol_delta(&self) -> i128 {
+        let fees = self.fee as i128 + self.creator_fee as i128;
+        match self.side() {
+            Side::Buy => -(self.sol_amount as i128) - fees,
+            Side::Sell => self.sol_amount as i128 - fees,
+        }
+    }
+
+    /// 用户代币变化（最小单位）
+    pub fn token_delta(&self) -> i128 {
+        match self.side() {
+            Side::Buy => self.token_amount as i128,
+            Side::Sell => -(self.token_amount as i128),
+        }
+    }
+}
+
+impl BuyEvent {
+    /// 交易方向，总是 `Side::Buy`
+    pub fn side(&self) -> Side {
+        Side::Buy
+    }
+
+    /// 用户 base 代币变化（收到的 `base_amount_out`）
+    pub fn base_delta(&self) -> i128 {
+        self.base_amount_out as i128
+    }
+
+    /// 用户 quote 代币变化（支付的 `user_quote_amount_in`，含全部手续费），quote 通常为 WSOL
+    pub fn quote_delta(&self) -> i128 {
+        -(self.user_quote_amount_in as i128)
+    }
+}
+
+impl SellEvent {
+    /// 交易方向，总是 `Side::Sell`
+    pub fn side(&self) -> Side {
+        Side::Sell
+    }
+
+    /// 用户 base 代币变化（卖出的 `base_amount_in`）
+    pub fn base_delta(&self) -> i128 {
+        -(self.base_amount_in as i128)
+    }
+
+    /// 用户 quote 代币变化（收到的 `user_quote_amount_out`，已扣除手续费），quote 通常为 WSOL
+    pub fn quote_delta(&self) -> i128 {
+        self.user_quote_amount_out as i128
+    }
+}
+
+/// Pump bonding curve 账户状态
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct BondingCurveAccount {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    pub creator: Pubkey,
+    pub is_mayhem_mode: bool,
+}
+
+/// Pump 全局配置账户（`global` PDA）
+///
+/// 程序升级会在账户末尾追加字段，这里只解码已知的前导字段。
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct GlobalAccount {
+    pub initialized: bool,
+    pub authority: Pubkey,
+    /// 手续费接收账户
+    pub fee_recipient: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    /// 协议手续费（基点）
+    pub fee_basis_points: u64,
+    pub withdraw_authority: Pubkey,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: u64,
+    /// 创建者手续费（基点）
+    pub creator_fee_basis_points: u64,
+    /// 备用手续费接收账户，交易时可任选其一
+    pub fee_recipients: [Pubkey; 7],
+    pub set_creator_authority: Pubkey,
+    pub admin_set_creator_authority: Pubkey,
+}
+
+/// PumpAmm 全局配置账户（`global_config` PDA）
+///
+/// 程序升级会在账户末尾追加字段，这里只解码已知的前导字段。
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct GlobalConfigAccount {
+    pub admin: Pubkey,
+    /// LP 手续费（基点）
+    pub lp_fee_basis_points: u64,
+    /// 协议手续费（基点）
+    pub protocol_fee_basis_points: u64,
+    /// 按位禁用的指令
+    pub disable_flags: u8,
+    /// 协议手续费接收账户，交易时可任选其一
+    pub protocol_fee_recipients: [Pubkey; 8],
+    /// 代币创建者手续费（基点）
+    pub coin_creator_fee_basis_points: u64,
+    pub admin_set_coin_creator_authority: Pubkey,
+}
+
+/// PumpAmm 池账户
+///
+/// 程序升级会在账户末尾追加字段，这里只解码已知的前导字段。
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct PoolAccount {
+    pub pool_bump: u8,
+    pub index: u16,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    pub lp_supply: u64,
+    pub coin_creator: Pubkey,
+}
+
+/// 按 discriminator 识别的 Pump / PumpAmm 程序账户
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgramAccount {
+    Global(GlobalAccount),
+    GlobalConfig(GlobalConfigAccount),
+    Pool(PoolAccount),
+    BondingCurve(BondingCurveAccount),
+}
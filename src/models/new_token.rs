@@ -0,0 +1,160 @@
+use solana_sdk::pubkey::Pubkey;
+
+use super::{CreateEvent, CreateV2Event, PumpEvent, TokenMetadata, TradeEvent};
+
+/// 新币上线的组合事件
+///
+/// 由同一笔交易中的 CreateEvent / CreateV2Event、创建者的首笔买入（dev buy）
+/// 以及交易结束时的 bonding curve 状态组合而成，通过 `EventHandler::on_new_token` 分发。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewToken {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    /// 代币创建者
+    pub creator: Pubkey,
+    /// 支付创建交易的钱包
+    pub user: Pubkey,
+    /// 清洗后的链上元数据
+    pub metadata: TokenMetadata,
+    pub token_program: Pubkey,
+    pub token_total_supply: u64,
+    pub is_mayhem_mode: bool,
+    /// 是否由 CreateV2 指令创建
+    pub is_v2: bool,
+    pub timestamp: i64,
+    /// 创建者在同一笔交易中的买入，没有时为 None
+    pub dev_buy: Option<DevBuy>,
+    /// 创建交易结束时的 bonding curve 状态（包含 dev buy 的影响）
+    pub curve: CurveState,
+    /// 从元数据 URI 获取的链下元数据，由 `enrich::NewTokenEnricher` 填充（需要启用 `metadata` feature）
+    pub offchain: Option<OffchainMetadata>,
+}
+
+/// 创建者在创建交易中的买入，同一笔交易内的多次买入会合并
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DevBuy {
+    /// 买入花费（lamports，不含手续费）
+    pub sol_amount: u64,
+    /// 买入的代币数量
+    pub token_amount: u64,
+    /// 协议手续费与创建者手续费合计
+    pub fees: u64,
+}
+
+/// bonding curve 储备状态
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CurveState {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+/// 元数据 URI 指向的链下 JSON 中常用的字段
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OffchainMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    /// 图片地址，`ipfs://` 等地址已转换为 HTTP 网关地址
+    pub image: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+impl NewToken {
+    /// 由 CreateEvent 构建，尚未合并 dev buy
+    pub fn from_create(event: &CreateEvent) -> Self {
+        Self {
+            mint: event.mint,
+            bonding_curve: event.bonding_curve,
+            creator: event.creator,
+            user: event.user,
+            metadata: event.sanitized(),
+            token_program: event.token_program,
+            token_total_supply: event.token_total_supply,
+            is_mayhem_mode: event.is_mayhem_mode,
+            is_v2: false,
+            timestamp: event.timestamp,
+            dev_buy: None,
+            curve: CurveState {
+                virtual_sol_reserves: event.virtual_sol_reserves,
+                virtual_token_reserves: event.virtual_token_reserves,
+                real_sol_reserves: 0,
+                real_token_reserves: event.real_token_reserves,
+            },
+            offchain: None,
+        }
+    }
+
+    /// 由 CreateV2Event 构建，尚未合并 dev buy
+    pub fn from_create_v2(event: &CreateV2Event) -> Self {
+        Self {
+            mint: event.mint,
+            bonding_curve: event.bonding_curve,
+            creator: event.creator,
+            user: event.user,
+            metadata: event.sanitized(),
+            token_program: event.token_program,
+            token_total_supply: event.token_total_supply,
+            is_mayhem_mode: event.is_mayhem_mode,
+            is_v2: true,
+            timestamp: event.timestamp,
+            dev_buy: None,
+            curve: CurveState {
+                virtual_sol_reserves: event.virtual_sol_reserves,
+                virtual_token_reserves: event.virtual_token_reserves,
+                real_sol_reserves: 0,
+                real_token_reserves: event.real_token_reserves,
+            },
+            offchain: None,
+        }
+    }
+
+    /// 合并同一笔交易中的 TradeEvent：创建者（或付款钱包）的买入计入 dev buy，
+    /// 任何该 mint 的交易都会更新 bonding curve 状态。不属于该 mint 时返回 `false`
+    pub fn apply_trade(&mut self, trade: &TradeEvent) -> bool {
+        if trade.mint != self.mint {
+            return false;
+        }
+        if trade.is_buy && (trade.user == self.creator || trade.user == self.user) {
+            let dev_buy = self.dev_buy.get_or_insert_with(DevBuy::default);
+            dev_buy.sol_amount += trade.sol_amount;
+            dev_buy.token_amount += trade.token_amount;
+            dev_buy.fees += trade.fee + trade.creator_fee;
+        }
+        self.curve = CurveState {
+            virtual_sol_reserves: trade.virtual_sol_reserves,
+            virtual_token_reserves: trade.virtual_token_reserves,
+            real_sol_reserves: trade.real_sol_reserves,
+            real_token_reserves: trade.real_token_reserves,
+        };
+        true
+    }
+
+    /// 从一笔交易的全部事件中组合出新币事件，交易中没有创建事件时返回空列表
+    pub fn from_transaction<'a>(events: impl IntoIterator<Item = &'a PumpEvent>) -> Vec<Self> {
+        let mut tokens = Vec::new();
+        for event in events {
+            Self::observe(&mut tokens, event);
+        }
+        tokens
+    }
+
+    /// 把一个事件合并进正在组合的新币列表
+    pub(crate) fn observe(tokens: &mut Vec<Self>, event: &PumpEvent) {
+        match event {
+            PumpEvent::Create(e) => tokens.push(Self::from_create(e)),
+            PumpEvent::CreateV2(e) => tokens.push(Self::from_create_v2(e)),
+            PumpEvent::Trade(e) => {
+                for token in tokens.iter_mut() {
+                    if token.apply_trade(e) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use log::{debug, warn};
+use serde_json::{json, Value};
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::mpsc, time::Instant};
+
+use super::{format_sol, Notification};
+use crate::{
+    analytics::WhaleAlert,
+    client::{EventContext, EventHandler},
+    enrich::MetadataFetcher,
+    models::{CompleteEvent, CreateEvent, CreateV2Event, Side},
+};
+
+/// 待发送通知队列长度，队列满时丢弃新通知，不阻塞事件处理
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Discord 单条消息最多包含的 embed 数量
+const MAX_EMBEDS_PER_MESSAGE: usize = 10;
+
+/// 遇到限流时的最大重试次数
+const MAX_RETRIES: usize = 3;
+
+const COLOR_NEW_TOKEN: u32 = 0x2ecc71;
+const COLOR_WHALE: u32 = 0x3498db;
+const COLOR_WARNING: u32 = 0xe67e22;
+const COLOR_GRADUATION: u32 = 0x9b59b6;
+
+/// embed 模板：收到通知和新币图片地址（如果有），返回 embed 对象，返回 None 表示不发送
+pub type DiscordTemplate = Arc<dyn Fn(&Notification, Option<&str>) -> Option<Value> + Send + Sync>;
+
+/// 默认 embed 模板
+pub fn default_embed(notification: &Notification, image: Option<&str>) -> Option<Value> {
+    let embed = match notification {
+        Notification::NewToken {
+            mint,
+            name,
+            symbol,
+            uri,
+            creator,
+            signature,
+        } => {
+            let mut embed = json!({
+                "title": format!("🆕 {name} (${symbol})"),
+                "url": format!("https://pump.fun/coin/{mint}"),
+                "color": COLOR_NEW_TOKEN,
+                "fields": [
+                    { "name": "Mint", "value": format!("`{mint}`") },
+                    { "name": "创建者", "value": format!("`{creator}`") },
+                    { "name": "链接", "value": explorer_links(Some(mint), signature) },
+                ],
+            });
+            if let Some(uri) = uri {
+                embed["description"] = json!(uri);
+            }
+            if let Some(image) = image {
+                embed["thumbnail"] = json!({ "url": image });
+            }
+            embed
+        }
+        Notification::Whale(WhaleAlert::LargeTrade {
+            market,
+            trader,
+            side,
+            lamports,
+            signature,
+        }) => json!({
+            "title": format!(
+                "🐋 大额{} {} SOL",
+                if *side == Side::Buy { "买入" } else { "卖出" },
+                format_sol(*lamports)
+            ),
+            "color": COLOR_WHALE,
+            "fields": [
+                { "name": "市场", "value": format!("`{market}`") },
+                { "name": "钱包", "value": format!("`{trader}`") },
+                { "name": "链接", "value": explorer_links(None, signature) },
+            ],
+        }),
+        Notification::Whale(WhaleAlert::Accumulation {
+            mint,
+            wallet,
+            share_bps,
+            ..
+        }) => json!({
+            "title": format!("📈 持仓集中 {:.2}%", *share_bps as f64 / 100.0),
+            "url": format!("https://pump.fun/coin/{mint}"),
+            "color": COLOR_WARNING,
+            "fields": [
+                { "name": "Mint", "value": format!("`{mint}`") },
+                { "name": "钱包", "value": format!("[`{wallet}`](https://solscan.io/account/{wallet})") },
+            ],
+        }),
+        Notification::Whale(WhaleAlert::CreatorSell {
+            market,
+            creator,
+            lamports,
+            signature,
+            ..
+        }) => json!({
+            "title": format!("⚠️ 创建者卖出 {} SOL", format_sol(*lamports)),
+            "color": COLOR_WARNING,
+            "fields": [
+                { "name": "市场", "value": format!("`{market}`") },
+                { "name": "创建者", "value": format!("`{creator}`") },
+                { "name": "链接", "value": explorer_links(None, signature) },
+            ],
+        }),
+        Notification::Graduation {
+            mint,
+            bonding_curve,
+            signature,
+        } => json!({
+            "title": "🎓 毕业：bonding curve 已完成",
+            "url": format!("https://pump.fun/coin/{mint}"),
+            "color": COLOR_GRADUATION,
+            "fields": [
+                { "name": "Mint", "value": format!("`{mint}`") },
+                { "name": "Bonding curve", "value": format!("`{bonding_curve}`") },
+                { "name": "链接", "value": explorer_links(Some(mint), signature) },
+            ],
+        }),
+    };
+    Some(embed)
+}
+
+/// 浏览器链接
+fn explorer_links(
+    mint: Option<&solana_sdk::pubkey::Pubkey>,
+    signature: &solana_sdk::signature::Signature,
+) -> String {
+    let mut links = format!(
+        "[Solscan](https://solscan.io/tx/{signature}) · [SolanaFM](https://solana.fm/tx/{signature})"
+    );
+    if let Some(mint) = mint {
+        links.push_str(&format!(
+            " · [pump.fun](https://pump.fun/coin/{mint}) · [代币](https://solscan.io/token/{mint})"
+        ));
+    }
+    links
+}
+
+/// Discord webhook 通知配置
+#[derive(Clone)]
+pub struct DiscordConfig {
+    /// webhook 地址
+    pub webhook_url: String,
+    /// 覆盖 webhook 默认的显示名称
+    pub username: Option<String>,
+    /// 等待凑批的最长时间，期间收到的通知合并为一条消息（最多 10 个 embed）
+    pub batch_window: Duration,
+    /// 是否获取新币元数据中的图片作为缩略图
+    pub fetch_images: bool,
+    /// embed 模板
+    pub template: DiscordTemplate,
+}
+
+impl DiscordConfig {
+    /// 使用默认模板创建配置
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            webhook_url: webhook_url.into(),
+            username: None,
+            batch_window: Duration::from_secs(2),
+            fetch_images: true,
+            template: Arc::new(default_embed),
+        }
+    }
+
+    /// 设置显示名称
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// 设置凑批等待时间
+    pub fn with_batch_window(mut self, window: Duration) -> Self {
+        self.batch_window = window;
+        self
+    }
+
+    /// 设置是否获取新币图片
+    pub fn with_fetch_images(mut self, enabled: bool) -> Self {
+        self.fetch_images = enabled;
+        self
+    }
+
+    /// 设置自定义 embed 模板
+    pub fn with_template<F>(mut self, template: F) -> Self
+    where
+        F: Fn(&Notification, Option<&str>) -> Option<Value> + Send + Sync + 'static,
+    {
+        self.template = Arc::new(template);
+        self
+    }
+}
+
+/// Discord webhook 通知（需要启用 `discord` feature）
+///
+/// 与 [`TelegramNotifier`](super::TelegramNotifier) 对应：通知进入有界队列，后台任务把
+/// `batch_window` 内收到的通知合并为一条最多 10 个 embed 的消息发送，按响应中的
+/// `retry_after` 和 `X-RateLimit-*` 头处理限流；队列满时丢弃新通知。
+#[derive(Clone)]
+pub struct DiscordNotifier {
+    tx: mpsc::Sender<Notification>,
+}
+
+impl DiscordNotifier {
+    /// 启动后台发送任务，必须在 Tokio 运行时中调用
+    pub fn spawn(config: DiscordConfig) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(run(config, rx));
+        Self { tx }
+    }
+
+    /// 推送一条通知，队列已满时丢弃并返回 `false`
+    pub fn notify(&self, notification: Notification) -> bool {
+        match self.tx.try_send(notification) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Discord 通知队列已满或已关闭，丢弃通知: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// 后台发送循环
+async fn run(config: DiscordConfig, mut rx: mpsc::Receiver<Notification>) {
+    let client = reqwest::Client::new();
+    let fetcher = MetadataFetcher::new();
+
+    while let Some(first) = rx.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + config.batch_window;
+        while batch.len() < MAX_EMBEDS_PER_MESSAGE {
+            match tokio::time::timeout_at(deadline, rx.recv()).await {
+                Ok(Some(notification)) => batch.push(notification),
+                _ => break,
+            }
+        }
+
+        let mut embeds = Vec::with_capacity(batch.len());
+        for notification in &batch {
+            let image = match notification {
+                Notification::NewToken { uri: Some(uri), .. } if config.fetch_images => fetcher
+                    .fetch(uri)
+                    .await
+                    .ok()
+                    .and_then(|metadata| metadata.image),
+                _ => None,
+            };
+            if let Some(embed) = (config.template)(notification, image.as_deref()) {
+                embeds.push(embed);
+            }
+        }
+        if embeds.is_empty() {
+            continue;
+        }
+
+        let mut body = json!({ "embeds": embeds });
+        if let Some(username) = &config.username {
+            body["username"] = json!(username);
+        }
+        send(&client, &config.webhook_url, &body).await;
+    }
+}
+
+/// 发送一条消息，遇到限流时等待后重试
+async fn send(client: &reqwest::Client, url: &str, body: &Value) {
+    for _ in 0..=MAX_RETRIES {
+        let response = match client.post(url).json(body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Discord 请求失败: {}", e);
+                return;
+            }
+        };
+        let status = response.status();
+        // 当前窗口的额度已用完时，等到窗口重置再发下一条
+        let reset_after = (header(&response, "x-ratelimit-remaining") == Some(0.0))
+            .then(|| header(&response, "x-ratelimit-reset-after"))
+            .flatten();
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let payload: Value = response.json().await.unwrap_or_default();
+            let retry_after = payload["retry_after"].as_f64().unwrap_or(1.0);
+            debug!("Discord 限流，{:.2} 秒后重试", retry_after);
+            tokio::time::sleep(Duration::from_secs_f64(retry_after)).await;
+            continue;
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            warn!("Discord 返回错误 {}: {}", status, text);
+        }
+        if let Some(reset_after) = reset_after {
+            tokio::time::sleep(Duration::from_secs_f64(reset_after)).await;
+        }
+        return;
+    }
+    warn!("Discord 限流重试次数耗尽，丢弃消息");
+}
+
+fn header(response: &reqwest::Response, name: &str) -> Option<f64> {
+    response.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+impl EventHandler for DiscordNotifier {
+    fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
+        self.notify(Notification::new_token(event, ctx));
+    }
+
+    fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
+        self.notify(Notification::new_token_v2(event, ctx));
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
+        self.notify(Notification::graduation(event, ctx));
+    }
+}
@@ -0,0 +1,88 @@
+//! 通知推送
+//!
+//! [`Notification`] 是与推送渠道无关的通知内容，各渠道的 sink（需要启用对应 feature）
+//! 负责按模板渲染并推送。sink 都实现了 `EventHandler`，新币和毕业通知可以直接订阅获得，
+//! 巨鲸告警则在 `WhaleWatcher` 的回调中转交给 sink。
+
+#[cfg(feature = "discord")]
+pub mod discord;
+#[cfg(feature = "telegram")]
+pub mod telegram;
+
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use crate::{
+    analytics::WhaleAlert,
+    client::EventContext,
+    models::{CompleteEvent, CreateEvent, CreateV2Event},
+};
+
+#[cfg(feature = "discord")]
+pub use discord::{DiscordConfig, DiscordNotifier};
+#[cfg(feature = "telegram")]
+pub use telegram::{TelegramConfig, TelegramNotifier};
+
+/// 通知内容
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Notification {
+    /// 新代币创建，元数据已经过清洗
+    NewToken {
+        mint: Pubkey,
+        name: String,
+        symbol: String,
+        uri: Option<String>,
+        creator: Pubkey,
+        signature: Signature,
+    },
+    /// 巨鲸告警
+    Whale(WhaleAlert),
+    /// bonding curve 完成（毕业）
+    Graduation {
+        mint: Pubkey,
+        bonding_curve: Pubkey,
+        signature: Signature,
+    },
+}
+
+impl Notification {
+    /// 由 CreateEvent 构建新币通知
+    pub fn new_token(event: &CreateEvent, ctx: &EventContext) -> Self {
+        let metadata = event.sanitized();
+        Self::NewToken {
+            mint: event.mint,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            creator: event.creator,
+            signature: ctx.signature,
+        }
+    }
+
+    /// 由 CreateV2Event 构建新币通知
+    pub fn new_token_v2(event: &CreateV2Event, ctx: &EventContext) -> Self {
+        let metadata = event.sanitized();
+        Self::NewToken {
+            mint: event.mint,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            creator: event.creator,
+            signature: ctx.signature,
+        }
+    }
+
+    /// 由 CompleteEvent 构建毕业通知
+    pub fn graduation(event: &CompleteEvent, ctx: &EventContext) -> Self {
+        Self::Graduation {
+            mint: event.mint,
+            bonding_curve: event.bonding_curve,
+            signature: ctx.signature,
+        }
+    }
+}
+
+/// lamports 转换为 SOL 显示
+#[cfg(any(feature = "telegram", feature = "discord"))]
+pub(crate) fn format_sol(lamports: u64) -> String {
+    format!("{:.3}", lamports as f64 / 1_000_000_000.0)
+}
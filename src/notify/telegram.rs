@@ -0,0 +1,253 @@
+use log::{debug, warn};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::mpsc;
+
+use super::{format_sol, Notification};
+use crate::{
+    analytics::WhaleAlert,
+    client::{EventContext, EventHandler},
+    models::{CompleteEvent, CreateEvent, CreateV2Event, Side},
+};
+
+/// 待发送通知队列长度，队列满时丢弃新通知，不阻塞事件处理
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Telegram 对同一聊天的推荐发送间隔
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(1100);
+
+/// 消息模板：返回 MarkdownV2 文本，返回 None 表示不发送该通知
+pub type TelegramTemplate = Arc<dyn Fn(&Notification) -> Option<String> + Send + Sync>;
+
+/// Telegram MarkdownV2 的保留字符
+const MARKDOWN_RESERVED: &str = "_*[]()~`>#+-=|{}.!\\";
+
+/// 转义 Telegram MarkdownV2 的保留字符
+pub fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_RESERVED.contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// 默认消息模板
+pub fn default_template(notification: &Notification) -> Option<String> {
+    let text = match notification {
+        Notification::NewToken {
+            mint,
+            name,
+            symbol,
+            uri,
+            creator,
+            signature,
+        } => {
+            let mut text = format!(
+                "🆕 *新代币* {} \\(${}\\)\nMint: `{}`\n创建者: `{}`\n",
+                escape_markdown(name),
+                escape_markdown(symbol),
+                mint,
+                creator,
+            );
+            if let Some(uri) = uri {
+                text.push_str(&format!("元数据: {}\n", escape_markdown(uri)));
+            }
+            text.push_str(&format!("[交易](https://solscan.io/tx/{signature})"));
+            text
+        }
+        Notification::Whale(WhaleAlert::LargeTrade {
+            market,
+            trader,
+            side,
+            lamports,
+            signature,
+        }) => format!(
+            "🐋 *大额{}* {} SOL\n市场: `{}`\n钱包: `{}`\n[交易](https://solscan.io/tx/{})",
+            if *side == Side::Buy { "买入" } else { "卖出" },
+            escape_markdown(&format_sol(*lamports)),
+            market,
+            trader,
+            signature,
+        ),
+        Notification::Whale(WhaleAlert::Accumulation {
+            mint,
+            wallet,
+            share_bps,
+            ..
+        }) => format!(
+            "📈 *持仓集中* {}%\nMint: `{}`\n钱包: `{}`",
+            escape_markdown(&format!("{:.2}", *share_bps as f64 / 100.0)),
+            mint,
+            wallet,
+        ),
+        Notification::Whale(WhaleAlert::CreatorSell {
+            market,
+            creator,
+            lamports,
+            signature,
+            ..
+        }) => format!(
+            "⚠️ *创建者卖出* {} SOL\n市场: `{}`\n创建者: `{}`\n[交易](https://solscan.io/tx/{})",
+            escape_markdown(&format_sol(*lamports)),
+            market,
+            creator,
+            signature,
+        ),
+        Notification::Graduation {
+            mint,
+            bonding_curve,
+            signature,
+        } => format!(
+            "🎓 *毕业* bonding curve 已完成\nMint: `{}`\nBonding curve: `{}`\n[交易](https://solscan.io/tx/{})",
+            mint, bonding_curve, signature,
+        ),
+    };
+    Some(text)
+}
+
+/// Telegram 机器人通知（需要启用 `telegram` feature）
+///
+/// 通知进入有界队列后由后台任务按最小间隔依次发送，遇到 429 时按 `retry_after` 等待后重试一次，
+/// 队列满时丢弃新通知，保证不会阻塞事件处理。
+/// 作为 `EventHandler` 使用时推送新币和毕业通知；巨鲸告警可在 `WhaleWatcher` 回调中调用 [`notify`]。
+///
+/// [`notify`]: TelegramNotifier::notify
+#[derive(Clone)]
+pub struct TelegramNotifier {
+    tx: mpsc::Sender<Notification>,
+}
+
+/// Telegram 通知配置
+#[derive(Clone)]
+pub struct TelegramConfig {
+    /// 机器人 token
+    pub bot_token: String,
+    /// 目标聊天 ID（用户、群组或频道）
+    pub chat_id: String,
+    /// 两条消息之间的最小间隔
+    pub min_interval: Duration,
+    /// 消息模板
+    pub template: TelegramTemplate,
+}
+
+impl TelegramConfig {
+    /// 使用默认模板和发送间隔创建配置
+    pub fn new(bot_token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+            template: Arc::new(default_template),
+        }
+    }
+
+    /// 设置两条消息之间的最小间隔
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// 设置自定义消息模板
+    pub fn with_template<F>(mut self, template: F) -> Self
+    where
+        F: Fn(&Notification) -> Option<String> + Send + Sync + 'static,
+    {
+        self.template = Arc::new(template);
+        self
+    }
+}
+
+impl TelegramNotifier {
+    /// 启动后台发送任务，必须在 Tokio 运行时中调用
+    pub fn spawn(config: TelegramConfig) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(run(config, rx));
+        Self { tx }
+    }
+
+    /// 推送一条通知，队列已满时丢弃并返回 `false`
+    pub fn notify(&self, notification: Notification) -> bool {
+        match self.tx.try_send(notification) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Telegram 通知队列已满或已关闭，丢弃通知: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// 后台发送循环
+async fn run(config: TelegramConfig, mut rx: mpsc::Receiver<Notification>) {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://api.telegram.org/bot{}/sendMessage",
+        config.bot_token
+    );
+
+    while let Some(notification) = rx.recv().await {
+        let Some(text) = (config.template)(&notification) else {
+            continue;
+        };
+        let body = serde_json::json!({
+            "chat_id": config.chat_id,
+            "text": text,
+            "parse_mode": "MarkdownV2",
+            "disable_web_page_preview": true,
+        });
+
+        for attempt in 0..2 {
+            match send(&client, &url, &body).await {
+                Ok(()) => break,
+                Err(Some(retry_after)) if attempt == 0 => {
+                    debug!("Telegram 限流，{} 秒后重试", retry_after.as_secs());
+                    tokio::time::sleep(retry_after).await;
+                }
+                Err(_) => {
+                    warn!("Telegram 通知发送失败");
+                    break;
+                }
+            }
+        }
+        tokio::time::sleep(config.min_interval).await;
+    }
+}
+
+/// 发送一条消息，被限流时返回需要等待的时间
+async fn send(
+    client: &reqwest::Client,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<(), Option<Duration>> {
+    let response = client.post(url).json(body).send().await.map_err(|e| {
+        warn!("Telegram 请求失败: {}", e);
+        None
+    })?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let payload: serde_json::Value = response.json().await.unwrap_or_default();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = payload["parameters"]["retry_after"].as_u64().unwrap_or(1);
+        return Err(Some(Duration::from_secs(retry_after)));
+    }
+    warn!("Telegram 返回错误 {}: {}", status, payload["description"]);
+    Err(None)
+}
+
+impl EventHandler for TelegramNotifier {
+    fn on_create_event(&self, event: &CreateEvent, ctx: &EventContext) {
+        self.notify(Notification::new_token(event, ctx));
+    }
+
+    fn on_create_v2_event(&self, event: &CreateV2Event, ctx: &EventContext) {
+        self.notify(Notification::new_token_v2(event, ctx));
+    }
+
+    fn on_complete_event(&self, event: &CompleteEvent, ctx: &EventContext) {
+        self.notify(Notification::graduation(event, ctx));
+    }
+}
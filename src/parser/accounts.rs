@@ -0,0 +1,166 @@
+use crate::{
+    error::{Error, Result},
+    models::{
+        BondingCurveAccount, GlobalAccount, GlobalConfigAccount, PoolAccount, ProgramAccount,
+    },
+};
+use borsh::BorshDeserialize;
+
+use super::layout::FieldLayout;
+
+// 账户 discriminator 常量（sha256("account:<Name>") 前 8 字节）
+pub const BONDING_CURVE_ACCOUNT_DISCRIMINATOR: &[u8] = &[23, 183, 248, 55, 96, 216, 172, 96];
+pub const GLOBAL_ACCOUNT_DISCRIMINATOR: &[u8] = &[167, 232, 232, 177, 200, 108, 114, 127];
+pub const GLOBAL_CONFIG_ACCOUNT_DISCRIMINATOR: &[u8] = &[149, 8, 156, 202, 160, 252, 176, 217];
+pub const POOL_ACCOUNT_DISCRIMINATOR: &[u8] = &[241, 154, 109, 4, 17, 177, 109, 188];
+
+/// 校验 discriminator 后对账户数据做 Borsh 解码
+///
+/// 账户分配的空间可能大于当前结构体长度，尾部多余字节会被忽略。
+/// 解码失败时返回带有字段名和偏移的 `Error::FieldDecode`。
+fn decode_account<T: BorshDeserialize + FieldLayout>(
+    data: &[u8],
+    discriminator: &[u8],
+    name: &str,
+) -> Result<T> {
+    if data.len() < 8 || &data[..8] != discriminator {
+        return Err(Error::ParseError(format!(
+            "{name} 账户 discriminator 不匹配"
+        )));
+    }
+    let mut body = &data[8..];
+    T::deserialize(&mut body).map_err(|_| T::diagnose(&data[8..]))
+}
+
+impl BondingCurveAccount {
+    /// 从链上账户数据解码，会校验 Anchor discriminator
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_account(data, BONDING_CURVE_ACCOUNT_DISCRIMINATOR, "BondingCurve")
+    }
+}
+
+impl GlobalAccount {
+    /// 从链上账户数据解码，会校验 Anchor discriminator
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_account(data, GLOBAL_ACCOUNT_DISCRIMINATOR, "Global")
+    }
+}
+
+impl GlobalConfigAccount {
+    /// 从链上账户数据解码，会校验 Anchor discriminator
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_account(data, GLOBAL_CONFIG_ACCOUNT_DISCRIMINATOR, "GlobalConfig")
+    }
+}
+
+impl PoolAccount {
+    /// 从链上账户数据解码，会校验 Anchor discriminator
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        decode_account(data, POOL_ACCOUNT_DISCRIMINATOR, "Pool")
+    }
+}
+
+impl ProgramAccount {
+    /// 按 discriminator 识别账户类型并解码，未知类型返回 `Error::ParseError`
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let discriminator = data.get(..8).unwrap_or_default();
+        if discriminator == BONDING_CURVE_ACCOUNT_DISCRIMINATOR {
+            BondingCurveAccount::from_account_data(data).map(Self::BondingCurve)
+        } else if discriminator == POOL_ACCOUNT_DISCRIMINATOR {
+            PoolAccount::from_account_data(data).map(Self::Pool)
+        } else if discriminator == GLOBAL_ACCOUNT_DISCRIMINATOR {
+            GlobalAccount::from_account_data(data).map(Self::Global)
+        } else if discriminator == GLOBAL_CONFIG_ACCOUNT_DISCRIMINATOR {
+            GlobalConfigAccount::from_account_data(data).map(Self::GlobalConfig)
+        } else {
+            Err(Error::ParseError(format!(
+                "未知的账户 discriminator: {discriminator:?}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::to_vec;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn account_data(discriminator: &[u8], body: &impl borsh::BorshSerialize) -> Vec<u8> {
+        [discriminator, &to_vec(body).unwrap()].concat()
+    }
+
+    fn bonding_curve() -> BondingCurveAccount {
+        BondingCurveAccount {
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            creator: Pubkey::new_unique(),
+            is_mayhem_mode: true,
+        }
+    }
+
+    #[test]
+    fn decodes_bonding_curve_and_ignores_trailing_space() {
+        let curve = bonding_curve();
+        let mut data = account_data(BONDING_CURVE_ACCOUNT_DISCRIMINATOR, &curve);
+        data.extend_from_slice(&[0; 32]);
+        assert_eq!(BondingCurveAccount::from_account_data(&data).unwrap(), curve);
+        assert_eq!(
+            ProgramAccount::from_account_data(&data).unwrap(),
+            ProgramAccount::BondingCurve(curve)
+        );
+    }
+
+    #[test]
+    fn decodes_pool_by_discriminator() {
+        let pool = PoolAccount {
+            index: 3,
+            base_mint: Pubkey::new_unique(),
+            lp_supply: 42,
+            ..Default::default()
+        };
+        let data = account_data(POOL_ACCOUNT_DISCRIMINATOR, &pool);
+        assert_eq!(
+            ProgramAccount::from_account_data(&data).unwrap(),
+            ProgramAccount::Pool(pool)
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_or_short_discriminator() {
+        let data = account_data(POOL_ACCOUNT_DISCRIMINATOR, &bonding_curve());
+        assert!(matches!(
+            BondingCurveAccount::from_account_data(&data),
+            Err(Error::ParseError(_))
+        ));
+        assert!(BondingCurveAccount::from_account_data(&[23, 183]).is_err());
+        assert!(matches!(
+            ProgramAccount::from_account_data(&[0; 16]),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn truncated_account_reports_field_and_offset() {
+        let data = account_data(BONDING_CURVE_ACCOUNT_DISCRIMINATOR, &bonding_curve());
+        // 截断在 `creator` 字段中间：5 个 u64 与 1 个 bool 之后
+        let err = BondingCurveAccount::from_account_data(&data[..8 + 41 + 10]).unwrap_err();
+        match err {
+            Error::FieldDecode {
+                type_name,
+                field,
+                offset,
+                ..
+            } => {
+                assert_eq!(type_name, "BondingCurveAccount");
+                assert_eq!(field, "creator");
+                assert_eq!(offset, 41);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+use std::str::FromStr;
+
+use super::errors::PumpProgramError;
+
+/// 交易失败的原因
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FailureReason {
+    /// Pump / PumpAmm 程序返回的错误
+    Program(PumpProgramError),
+    /// 其他程序返回的自定义错误码
+    CustomProgramError { program: Pubkey, code: u32 },
+    /// 计算单元耗尽，需要提高 compute unit limit
+    ComputeBudgetExceeded,
+    /// SOL 转账余额不足（lamports）
+    InsufficientLamports { available: u64, required: u64 },
+    /// 代币余额不足（SPL Token 程序报错）
+    InsufficientTokenFunds,
+    /// 余额不足以支付交易手续费
+    InsufficientFundsForFee,
+    /// blockhash 已过期或节点尚未看到，需要用新的 blockhash 重新签名
+    BlockhashNotFound,
+    /// 相同的交易已经处理过
+    AlreadyProcessed,
+    /// 未归类的失败，保留原始描述
+    Other(String),
+}
+
+/// 失败交易的结构化诊断
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailureDiagnosis {
+    pub reason: FailureReason,
+    /// 出错的程序（最内层的失败指令），无法从日志确定时为 None
+    pub program: Option<Pubkey>,
+    /// 出错的顶层指令下标，只有从 `TransactionError` 诊断时才有
+    pub instruction_index: Option<u8>,
+    /// Anchor 程序在日志中输出的错误描述
+    pub message: Option<String>,
+}
+
+impl FailureDiagnosis {
+    /// 从交易日志（模拟结果或交易元数据中的 log messages）诊断，日志中没有失败记录时返回 None
+    ///
+    /// 识别 `Program <id> failed: ...`、系统程序的 `Transfer: insufficient lamports`、
+    /// SPL Token 的 `Error: insufficient funds` 和 Anchor 的 `AnchorError ... Error Message:` 日志。
+    pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Option<Self> {
+        let mut reason = None;
+        let mut program = None;
+        let mut message = None;
+        for line in logs.iter().map(AsRef::as_ref) {
+            if let Some(text) = line.strip_prefix("Program log: ") {
+                if text == "Error: insufficient funds" {
+                    reason.get_or_insert(FailureReason::InsufficientTokenFunds);
+                } else if let Some((_, text)) = text.split_once("Error Message: ") {
+                    message.get_or_insert_with(|| text.trim_end_matches('.').to_string());
+                }
+            } else if let Some(lamports) = line.strip_prefix("Transfer: insufficient lamports ") {
+                if let Some((available, required)) = parse_insufficient_lamports(lamports) {
+                    reason.get_or_insert(FailureReason::InsufficientLamports {
+                        available,
+                        required,
+                    });
+                }
+            } else if let Some((id, failure)) = parse_failed_line(line) {
+                // 最内层的失败指令最先出现，外层程序的失败行只是向上传递
+                if program.is_none() {
+                    program = Some(id);
+                    reason.get_or_insert_with(|| classify_failure(&id, failure));
+                }
+            }
+        }
+        if reason.is_none() && program.is_none() {
+            return None;
+        }
+        Some(Self {
+            reason: reason.unwrap_or_else(|| FailureReason::Other("未知错误".to_string())),
+            program,
+            instruction_index: None,
+            message,
+        })
+    }
+
+    /// 由交易错误和日志诊断，日志可以为空（例如 blockhash 过期的交易没有执行日志）
+    ///
+    /// 日志中有更具体的原因（如 Pump 错误码、余额不足）时优先使用日志的结果。
+    pub fn from_transaction<S: AsRef<str>>(error: &TransactionError, logs: &[S]) -> Self {
+        let from_logs = Self::from_logs(logs);
+        let (instruction_index, reason) = match error {
+            TransactionError::BlockhashNotFound => (None, FailureReason::BlockhashNotFound),
+            TransactionError::AlreadyProcessed => (None, FailureReason::AlreadyProcessed),
+            TransactionError::InsufficientFundsForFee => {
+                (None, FailureReason::InsufficientFundsForFee)
+            }
+            TransactionError::InstructionError(
+                index,
+                InstructionError::ComputationalBudgetExceeded,
+            ) => (Some(*index), FailureReason::ComputeBudgetExceeded),
+            TransactionError::InstructionError(index, e) => {
+                (Some(*index), FailureReason::Other(e.to_string()))
+            }
+            e => (None, FailureReason::Other(e.to_string())),
+        };
+        match from_logs {
+            Some(diagnosis) if !matches!(diagnosis.reason, FailureReason::Other(_)) => Self {
+                instruction_index,
+                ..diagnosis
+            },
+            diagnosis => Self {
+                reason,
+                program: diagnosis.as_ref().and_then(|d| d.program),
+                instruction_index,
+                message: diagnosis.and_then(|d| d.message),
+            },
+        }
+    }
+
+    /// Pump / PumpAmm 程序错误
+    pub fn program_error(&self) -> Option<PumpProgramError> {
+        match self.reason {
+            FailureReason::Program(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// 是否可以直接重试（重新获取 blockhash、重新报价或提高计算单元后重新发送）
+    pub fn is_retryable(&self) -> bool {
+        match &self.reason {
+            FailureReason::Program(error) => error.is_slippage(),
+            FailureReason::ComputeBudgetExceeded | FailureReason::BlockhashNotFound => true,
+            _ => false,
+        }
+    }
+}
+
+/// 解析 `Program <id> failed: <原因>`
+fn parse_failed_line(line: &str) -> Option<(Pubkey, &str)> {
+    let rest = line.strip_prefix("Program ")?;
+    let (program, rest) = rest.split_once(' ')?;
+    let failure = rest.strip_prefix("failed: ")?;
+    Some((Pubkey::from_str(program).ok()?, failure))
+}
+
+fn classify_failure(program: &Pubkey, failure: &str) -> FailureReason {
+    if let Some(code) = failure.strip_prefix("custom program error: 0x") {
+        if let Ok(code) = u32::from_str_radix(code.trim(), 16) {
+            return match PumpProgramError::from_custom(program, code) {
+                Some(error) => FailureReason::Program(error),
+                None => FailureReason::CustomProgramError {
+                    program: *program,
+                    code,
+                },
+            };
+        }
+    }
+    if failure.contains("exceeded CUs meter") || failure.contains("Computational budget exceeded") {
+        return FailureReason::ComputeBudgetExceeded;
+    }
+    FailureReason::Other(failure.to_string())
+}
+
+/// 解析 `<available>, need <required>`
+fn parse_insufficient_lamports(text: &str) -> Option<(u64, u64)> {
+    let (available, required) = text.split_once(", need ")?;
+    Some((
+        available.trim().parse().ok()?,
+        required.trim().parse().ok()?,
+    ))
+}
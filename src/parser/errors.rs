@@ -0,0 +1,293 @@
+//! Pump / PumpAmm 程序自定义错误码
+//!
+//! 交易失败时，程序返回的 Anchor 自定义错误码出现在 `TransactionError::InstructionError`
+//! 和日志的 `custom program error: 0x...` 中，这里把它们映射为类型化的错误，
+//! 方便按失败原因分支处理（例如滑点超限时重新报价、bonding curve 已完成时改走 PumpAmm）。
+
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::pda::{PUMP_AMM_PROGRAM_ID, PUMP_PROGRAM_ID};
+
+/// Anchor 自定义错误码的起始值，小于该值的是 Anchor 框架错误（约束检查、账户校验等）
+pub const ANCHOR_ERROR_CODE_OFFSET: u32 = 6000;
+
+/// 声明程序错误码枚举，错误码按声明顺序从 6000 开始递增
+macro_rules! program_errors {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $message:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Error)]
+        #[repr(u32)]
+        pub enum $name {
+            $(
+                #[error($message)]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            /// 由错误码查找，未知的错误码返回 None
+            pub fn from_code(code: u32) -> Option<Self> {
+                let index = code.checked_sub(ANCHOR_ERROR_CODE_OFFSET)?;
+                Self::ALL.get(index as usize).copied()
+            }
+
+            /// 错误码
+            pub fn code(self) -> u32 {
+                ANCHOR_ERROR_CODE_OFFSET + self as u32
+            }
+
+            /// IDL 中的错误名
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant),)*
+                }
+            }
+        }
+    };
+}
+
+program_errors!(
+    /// Pump 程序错误
+    PumpError {
+        NotAuthorized => "账户无权执行该指令",
+        AlreadyInitialized => "程序已初始化",
+        TooMuchSolRequired => "滑点超限：买入所需 SOL 超过上限",
+        TooLittleSolReceived => "滑点超限：卖出获得的 SOL 低于下限",
+        MintDoesNotMatchBondingCurve => "mint 与 bonding curve 不匹配",
+        BondingCurveComplete => "bonding curve 已完成，代币已迁移",
+        BondingCurveNotComplete => "bonding curve 尚未完成",
+        NotInitialized => "程序尚未初始化",
+        WithdrawTooFrequent => "提取过于频繁",
+        NewSizeShouldBeGreaterThanCurrentSize => "新账户大小应大于当前大小",
+        AccountTypeNotSupported => "不支持的账户类型",
+        InitialRealTokenReservesShouldBeLessThanTokenTotalSupply => "初始真实代币储备应小于总供应量",
+        InitialVirtualTokenReservesShouldBeGreaterThanRealTokenReserves => "初始虚拟代币储备应大于真实代币储备",
+        FeeBasisPointsGreaterThanMaximum => "手续费基点超过上限",
+        AllZerosWithdrawAuthority => "提取权限账户为全零地址",
+        PoolMigrationFeeShouldBeLessThanFinalRealSolReserves => "迁移手续费应小于最终真实 SOL 储备",
+        PoolMigrationFeeShouldBeGreaterThanCreatorFeePlusMaxMigrateFees => "迁移手续费应大于创建者手续费与最大迁移费用之和",
+        DisabledWithdraw => "提取已禁用",
+        DisabledMigrate => "迁移已禁用",
+        InvalidCreator => "创建者无效",
+        BuyZeroAmount => "买入数量为 0",
+        NotEnoughTokensToBuy => "bonding curve 中剩余代币不足",
+        SellZeroAmount => "卖出数量为 0",
+        NotEnoughTokensToSell => "持有的代币不足",
+        Overflow => "数值溢出",
+        Truncation => "数值截断",
+        DivisionByZero => "除以零",
+        NotEnoughRemainingAccounts => "remaining accounts 数量不足",
+        AllFeeRecipientsShouldBeNonZero => "手续费接收账户不能为全零地址",
+        UnsortedNotUniqueFeeRecipients => "手续费接收账户未排序或有重复",
+        CreatorShouldNotBeZero => "创建者不能为全零地址",
+    }
+);
+
+program_errors!(
+    /// PumpAmm 程序错误
+    PumpAmmError {
+        FeeBasisPointsExceedsMaximum => "手续费基点超过上限",
+        ZeroBaseAmount => "base 数量为 0",
+        ZeroQuoteAmount => "quote 数量为 0",
+        TooLittlePoolTokenLiquidity => "LP 代币数量过少",
+        ExceededSlippage => "滑点超限",
+        InvalidAdmin => "管理员无效",
+        UnsupportedBaseMint => "不支持的 base mint",
+        UnsupportedQuoteMint => "不支持的 quote mint",
+        InvalidBaseMint => "base mint 无效",
+        InvalidQuoteMint => "quote mint 无效",
+        InvalidLpMint => "LP mint 无效",
+        AllProtocolFeeRecipientsShouldBeNonZero => "协议手续费接收账户不能为全零地址",
+        UnsortedNotUniqueProtocolFeeRecipients => "协议手续费接收账户未排序或有重复",
+        InvalidProtocolFeeRecipient => "协议手续费接收账户无效",
+        InvalidPoolBaseTokenAccount => "池的 base 代币账户无效",
+        InvalidPoolQuoteTokenAccount => "池的 quote 代币账户无效",
+        BuyMoreBaseAmountThanPoolReserves => "买入数量超过池的 base 储备",
+        DisabledCreatePool => "创建池已禁用",
+        DisabledDeposit => "添加流动性已禁用",
+        DisabledWithdraw => "移除流动性已禁用",
+        DisabledBuy => "买入已禁用",
+        DisabledSell => "卖出已禁用",
+        SameMint => "base 与 quote 是同一个 mint",
+        Overflow => "数值溢出",
+        Truncation => "数值截断",
+        DivisionByZero => "除以零",
+        NewSizeLessThanCurrentSize => "新账户大小小于当前大小",
+        AccountTypeNotSupported => "不支持的账户类型",
+        OnlyCanonicalPumpPoolsCanHaveCoinCreator => "只有 Pump 迁移生成的池可以设置代币创建者",
+    }
+);
+
+/// Pump / PumpAmm 程序返回的错误
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Error)]
+pub enum PumpProgramError {
+    #[error("Pump 程序错误 {}（{}）: {0}", .0.code(), .0.name())]
+    Pump(PumpError),
+
+    #[error("PumpAmm 程序错误 {}（{}）: {0}", .0.code(), .0.name())]
+    PumpAmm(PumpAmmError),
+
+    /// Anchor 框架错误（账户约束、discriminator 校验等），错误码小于 6000
+    #[error("程序 {program} 的 Anchor 框架错误 {code}")]
+    Anchor { program: Pubkey, code: u32 },
+
+    /// 本 SDK 尚未收录的自定义错误码（程序升级后新增的错误）
+    #[error("程序 {program} 的未知错误码 {code}")]
+    Unknown { program: Pubkey, code: u32 },
+}
+
+impl PumpProgramError {
+    /// 由程序 ID 和自定义错误码构建，非 Pump / PumpAmm 程序返回 None
+    pub fn from_custom(program: &Pubkey, code: u32) -> Option<Self> {
+        let known = if *program == PUMP_PROGRAM_ID {
+            PumpError::from_code(code).map(Self::Pump)
+        } else if *program == PUMP_AMM_PROGRAM_ID {
+            PumpAmmError::from_code(code).map(Self::PumpAmm)
+        } else {
+            return None;
+        };
+        Some(known.unwrap_or(if code < ANCHOR_ERROR_CODE_OFFSET {
+            Self::Anchor {
+                program: *program,
+                code,
+            }
+        } else {
+            Self::Unknown {
+                program: *program,
+                code,
+            }
+        }))
+    }
+
+    /// 由交易错误构建
+    ///
+    /// `TransactionError` 只包含出错指令的下标，`instruction_programs` 为交易中各顶层指令的程序 ID，
+    /// 用于确定出错的程序。出错的不是 Pump / PumpAmm 指令、或不是自定义错误时返回 None。
+    ///
+    /// 注意：经由其他程序 CPI 调用 Pump 时，错误码属于外层程序，应改用 [`from_logs`](Self::from_logs)。
+    pub fn from_transaction_error(
+        error: &TransactionError,
+        instruction_programs: &[Pubkey],
+    ) -> Option<Self> {
+        let TransactionError::InstructionError(index, InstructionError::Custom(code)) = error
+        else {
+            return None;
+        };
+        Self::from_custom(instruction_programs.get(*index as usize)?, *code)
+    }
+
+    /// 从交易日志（模拟结果或交易元数据中的 log messages）中解析错误
+    ///
+    /// 查找 `Program <id> failed: custom program error: 0x...`，CPI 调用中内层 Pump 指令的失败
+    /// 也能识别。日志中没有 Pump / PumpAmm 的自定义错误时返回 None。
+    pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Option<Self> {
+        logs.iter()
+            .find_map(|line| parse_failed_line(line.as_ref()))
+    }
+
+    /// 错误码
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::Pump(error) => error.code(),
+            Self::PumpAmm(error) => error.code(),
+            Self::Anchor { code, .. } | Self::Unknown { code, .. } => *code,
+        }
+    }
+
+    /// 是否为滑点超限（价格在提交与执行之间变化），通常可以重新报价后重试
+    pub fn is_slippage(&self) -> bool {
+        matches!(
+            self,
+            Self::Pump(PumpError::TooMuchSolRequired | PumpError::TooLittleSolReceived)
+                | Self::PumpAmm(PumpAmmError::ExceededSlippage)
+        )
+    }
+
+    /// 是否为 bonding curve 已完成，代币需要改在 PumpAmm 上交易
+    pub fn is_bonding_curve_complete(&self) -> bool {
+        matches!(self, Self::Pump(PumpError::BondingCurveComplete))
+    }
+}
+
+/// 解析 `Program <id> failed: custom program error: 0x<code>`
+fn parse_failed_line(line: &str) -> Option<PumpProgramError> {
+    let rest = line.strip_prefix("Program ")?;
+    let (program, rest) = rest.split_once(' ')?;
+    let code = rest.strip_prefix("failed: custom program error: 0x")?;
+    let code = u32::from_str_radix(code.trim(), 16).ok()?;
+    PumpProgramError::from_custom(&Pubkey::from_str(program).ok()?, code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_in_declaration_order() {
+        for (index, error) in PumpError::ALL.iter().enumerate() {
+            assert_eq!(error.code(), ANCHOR_ERROR_CODE_OFFSET + index as u32);
+            assert_eq!(PumpError::from_code(error.code()), Some(*error));
+        }
+        for error in PumpAmmError::ALL {
+            assert_eq!(PumpAmmError::from_code(error.code()), Some(*error));
+        }
+        assert_eq!(PumpError::from_code(6002), Some(PumpError::TooMuchSolRequired));
+        assert_eq!(PumpError::from_code(6005), Some(PumpError::BondingCurveComplete));
+        assert_eq!(PumpAmmError::from_code(6004), Some(PumpAmmError::ExceededSlippage));
+        assert_eq!(PumpError::from_code(5999), None);
+        assert_eq!(PumpError::from_code(6000 + PumpError::ALL.len() as u32), None);
+    }
+
+    #[test]
+    fn classifies_custom_codes_by_program() {
+        assert_eq!(
+            PumpProgramError::from_custom(&PUMP_PROGRAM_ID, 6003),
+            Some(PumpProgramError::Pump(PumpError::TooLittleSolReceived))
+        );
+        assert_eq!(
+            PumpProgramError::from_custom(&PUMP_AMM_PROGRAM_ID, 2006),
+            Some(PumpProgramError::Anchor {
+                program: PUMP_AMM_PROGRAM_ID,
+                code: 2006
+            })
+        );
+        assert_eq!(
+            PumpProgramError::from_custom(&PUMP_PROGRAM_ID, 6999),
+            Some(PumpProgramError::Unknown {
+                program: PUMP_PROGRAM_ID,
+                code: 6999
+            })
+        );
+        assert_eq!(PumpProgramError::from_custom(&Pubkey::new_unique(), 6003), None);
+    }
+
+    #[test]
+    fn resolves_failing_instruction_program() {
+        let programs = [Pubkey::new_unique(), PUMP_AMM_PROGRAM_ID];
+        let error = TransactionError::InstructionError(1, InstructionError::Custom(6004));
+        let parsed = PumpProgramError::from_transaction_error(&error, &programs).unwrap();
+        assert!(parsed.is_slippage());
+        assert_eq!(parsed.code(), 6004);
+
+        let other = TransactionError::InstructionError(0, InstructionError::Custom(6004));
+        assert_eq!(PumpProgramError::from_transaction_error(&other, &programs), None);
+        let missing = TransactionError::InstructionError(5, InstructionError::Custom(6004));
+        assert_eq!(PumpProgramError::from_transaction_error(&missing, &programs), None);
+    }
+
+    #[test]
+    fn parses_failure_from_logs() {
+        let logs = [
+            "Program ComputeBudget111111111111111111111111111111 success".to_string(),
+            format!("Program {PUMP_PROGRAM_ID} invoke [1]"),
+            format!("Program {PUMP_PROGRAM_ID} failed: custom program error: 0x1775"),
+        ];
+        let error = PumpProgramError::from_logs(&logs).unwrap();
+        assert!(error.is_bonding_curve_complete());
+        assert_eq!(PumpProgramError::from_logs(&logs[..2]), None);
+    }
+}
@@ -1,12 +1,20 @@
 use crate::models::{
-    BuyEvent, CompleteEvent, CreateEvent, CreatePoolEvent, CreateV2Event, SellEvent, TradeEvent,
+    BuyEvent, CollectCoinCreatorFeeEvent, CollectCreatorFeeEvent, CompleteEvent, CreateEvent,
+    CreatePoolEvent, CreateV2Event, DepositEvent, MigrateEvent, PumpEvent, SellEvent,
+    SetCreatorEvent, SetMetaplexCreatorEvent, SetParamsEvent, TradeEvent,
+    UpdateGlobalAuthorityEvent, WithdrawEvent,
 };
 use base64::{engine::general_purpose, Engine};
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use std::{cell::RefCell, error::Error, ops::ControlFlow};
 
+use super::layout::FieldLayout;
+
 const PROGRAM_DATA: &str = "Program data: ";
 
+/// `emit_cpi!` 自调用指令数据的前缀（Anchor 的 `EVENT_IX_TAG`，小端序），其后为事件的 discriminator 和 Borsh 数据
+pub const EVENT_IX_TAG: &[u8] = &[0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
 // 导出所有事件类型的 discriminator 常量，供性能关键路径使用
 // 这样可以避免在运行时调用函数获取 discriminator
 pub const CREATE_DISCRIMINATOR: &[u8] = &[27, 114, 169, 77, 222, 235, 99, 118];
@@ -16,6 +24,15 @@ pub const TRADE_DISCRIMINATOR: &[u8] = &[189, 219, 127, 211, 78, 230, 97, 238];
 pub const BUY_DISCRIMINATOR: &[u8] = &[103, 244, 82, 31, 44, 245, 119, 119];
 pub const CREATE_POOL_DISCRIMINATOR: &[u8] = &[177, 49, 12, 210, 160, 118, 167, 116];
 pub const SELL_DISCRIMINATOR: &[u8] = &[62, 47, 55, 10, 165, 3, 220, 42];
+pub const SET_PARAMS_DISCRIMINATOR: &[u8] = &[223, 195, 159, 246, 62, 48, 143, 131];
+pub const UPDATE_GLOBAL_AUTHORITY_DISCRIMINATOR: &[u8] = &[182, 195, 137, 42, 35, 206, 207, 247];
+pub const SET_CREATOR_DISCRIMINATOR: &[u8] = &[237, 52, 123, 37, 245, 251, 72, 210];
+pub const SET_METAPLEX_CREATOR_DISCRIMINATOR: &[u8] = &[142, 203, 6, 32, 127, 105, 191, 162];
+pub const DEPOSIT_DISCRIMINATOR: &[u8] = &[120, 248, 61, 83, 31, 142, 107, 144];
+pub const WITHDRAW_DISCRIMINATOR: &[u8] = &[22, 9, 133, 26, 160, 44, 71, 192];
+pub const COLLECT_CREATOR_FEE_DISCRIMINATOR: &[u8] = &[122, 2, 127, 1, 14, 191, 12, 175];
+pub const COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR: &[u8] = &[232, 245, 194, 238, 234, 218, 58, 89];
+pub const MIGRATE_DISCRIMINATOR: &[u8] = &[189, 233, 93, 185, 92, 148, 234, 148];
 
 thread_local! {
     static PROGRAM_LOG_BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(1024));
@@ -55,6 +72,305 @@ where
     });
 }
 
+/// 遍历 `emit_cpi!` 自调用指令中的事件数据
+///
+/// `instructions` 为程序自调用的内部指令数据，不以 [`EVENT_IX_TAG`] 开头的指令被跳过。
+/// 与 [`visit_program_logs`] 相同按倒序遍历，`visitor` 收到事件的 discriminator 和数据。
+pub fn visit_cpi_events<F>(instructions: &[Vec<u8>], mut visitor: F)
+where
+    F: FnMut(&[u8], &[u8]) -> ControlFlow<()>,
+{
+    for data in instructions.iter().rev() {
+        let Some(payload) = data.strip_prefix(EVENT_IX_TAG) else {
+            continue;
+        };
+        if payload.len() < 8 {
+            continue;
+        }
+        let (discriminator, data) = payload.split_at(8);
+        if visitor(discriminator, data).is_break() {
+            break;
+        }
+    }
+}
+
+/// 事件来源
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventSource {
+    /// 只从 `Program data:` 日志解析
+    #[default]
+    Logs,
+    /// 只从 `emit_cpi!` 自调用的内部指令数据解析，不受日志截断影响
+    Cpi,
+    /// 先从日志解析，日志中缺失的事件类型再从内部指令数据补齐
+    Both,
+}
+
+impl EventSource {
+    /// 是否从日志解析
+    pub fn logs(self) -> bool {
+        matches!(self, Self::Logs | Self::Both)
+    }
+
+    /// 是否从内部指令数据解析
+    pub fn cpi(self) -> bool {
+        matches!(self, Self::Cpi | Self::Both)
+    }
+}
+
+/// 事件解码模式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// 严格模式：事件数据必须被完整消费，存在多余字节时解码失败
+    #[default]
+    Strict,
+    /// 宽容模式：只解码已知的前导字段，忽略并记录末尾的未知字节。
+    /// 程序升级在事件末尾追加字段后，SDK 在模型更新前仍能继续工作。
+    Tolerant,
+}
+
+/// 按解码模式解码，返回事件和未被消费的末尾字节（严格模式下总是为空）
+///
+/// 解码失败时逐字段重新解码，返回带有事件类型、字段名和字节偏移的错误。
+#[inline]
+fn decode_with<T: BorshDeserialize + FieldLayout>(
+    data: &[u8],
+    mode: DecodeMode,
+) -> crate::Result<(T, &[u8])> {
+    let decoded = match mode {
+        DecodeMode::Strict => T::try_from_slice(data).ok().map(|event| (event, &data[..0])),
+        DecodeMode::Tolerant => {
+            let mut rest = data;
+            T::deserialize(&mut rest).ok().map(|event| (event, rest))
+        }
+    };
+    decoded.ok_or_else(|| T::diagnose(data))
+}
+
+/// 事件类型数量，用于按类型记录一笔交易中已解析的事件
+const EVENT_TYPE_COUNT: usize = 16;
+
+/// 根据 discriminator 返回事件类型序号，未知类型返回 None
+///
+/// 优化：使用直接字节比较，并优先检查最常见的事件类型（Buy/Sell > Trade > 其他）
+#[inline(always)]
+fn event_index(discriminator: &[u8]) -> Option<usize> {
+    if discriminator == BUY_DISCRIMINATOR {
+        Some(0)
+    } else if discriminator == SELL_DISCRIMINATOR {
+        Some(1)
+    } else if discriminator == TRADE_DISCRIMINATOR {
+        Some(2)
+    } else if discriminator == CREATE_DISCRIMINATOR {
+        Some(3)
+    } else if discriminator == CREATE_V2_DISCRIMINATOR {
+        Some(4)
+    } else if discriminator == COMPLETE_DISCRIMINATOR {
+        Some(5)
+    } else if discriminator == CREATE_POOL_DISCRIMINATOR {
+        Some(6)
+    } else if discriminator == SET_PARAMS_DISCRIMINATOR {
+        Some(7)
+    } else if discriminator == UPDATE_GLOBAL_AUTHORITY_DISCRIMINATOR {
+        Some(8)
+    } else if discriminator == SET_CREATOR_DISCRIMINATOR {
+        Some(9)
+    } else if discriminator == SET_METAPLEX_CREATOR_DISCRIMINATOR {
+        Some(10)
+    } else if discriminator == DEPOSIT_DISCRIMINATOR {
+        Some(11)
+    } else if discriminator == WITHDRAW_DISCRIMINATOR {
+        Some(12)
+    } else if discriminator == COLLECT_CREATOR_FEE_DISCRIMINATOR {
+        Some(13)
+    } else if discriminator == COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR {
+        Some(14)
+    } else if discriminator == MIGRATE_DISCRIMINATOR {
+        Some(15)
+    } else {
+        None
+    }
+}
+
+/// 是否为 SDK 内置的事件类型
+#[inline]
+pub fn is_known_event(discriminator: &[u8]) -> bool {
+    event_index(discriminator).is_some()
+}
+
+/// 按事件类型序号解码事件数据，返回事件和未知的末尾字节
+#[inline]
+fn decode_indexed(
+    index: usize,
+    data: &[u8],
+    mode: DecodeMode,
+) -> crate::Result<(PumpEvent, &[u8])> {
+    fn wrap<T>(
+        decoded: crate::Result<(T, &[u8])>,
+        variant: fn(T) -> PumpEvent,
+    ) -> crate::Result<(PumpEvent, &[u8])> {
+        decoded.map(|(event, tail)| (variant(event), tail))
+    }
+
+    match index {
+        0 => wrap(decode_with(data, mode), PumpEvent::Buy),
+        1 => wrap(decode_with(data, mode), PumpEvent::Sell),
+        2 => wrap(decode_with(data, mode), PumpEvent::Trade),
+        3 => wrap(decode_with(data, mode), PumpEvent::Create),
+        4 => wrap(decode_with(data, mode), PumpEvent::CreateV2),
+        5 => wrap(decode_with(data, mode), PumpEvent::Complete),
+        6 => wrap(decode_with(data, mode), PumpEvent::CreatePool),
+        7 => wrap(decode_with(data, mode), PumpEvent::SetParams),
+        8 => wrap(decode_with(data, mode), PumpEvent::UpdateGlobalAuthority),
+        9 => wrap(decode_with(data, mode), PumpEvent::SetCreator),
+        10 => wrap(decode_with(data, mode), PumpEvent::SetMetaplexCreator),
+        11 => wrap(decode_with(data, mode), PumpEvent::Deposit),
+        12 => wrap(decode_with(data, mode), PumpEvent::Withdraw),
+        13 => wrap(decode_with(data, mode), PumpEvent::CollectCreatorFee),
+        14 => wrap(decode_with(data, mode), PumpEvent::CollectCoinCreatorFee),
+        15 => wrap(decode_with(data, mode), PumpEvent::Migrate),
+        _ => Err(crate::Error::ParseError(format!("未知的事件类型序号: {index}"))),
+    }
+}
+
+/// 根据 discriminator 解码单个事件，未知类型或解码失败返回 None
+pub fn decode_event(discriminator: &[u8], data: &[u8]) -> Option<PumpEvent> {
+    decode_event_with(discriminator, data, DecodeMode::Strict).map(|(event, _)| event)
+}
+
+/// 按指定模式解码单个事件，返回事件和未知的末尾字节
+pub fn decode_event_with<'a>(
+    discriminator: &[u8],
+    data: &'a [u8],
+    mode: DecodeMode,
+) -> Option<(PumpEvent, &'a [u8])> {
+    event_index(discriminator).and_then(|index| decode_indexed(index, data, mode).ok())
+}
+
+/// 严格解码单个事件，失败时返回错误
+///
+/// 未知 discriminator 返回 `Error::ParseError`；数据与模型不匹配时返回
+/// `Error::FieldDecode`，其中包含事件类型、出错字段和字节偏移，便于程序升级后排查。
+pub fn try_decode_event(discriminator: &[u8], data: &[u8]) -> crate::Result<PumpEvent> {
+    let index = event_index(discriminator).ok_or_else(|| {
+        crate::Error::ParseError(format!("未知的事件 discriminator: {discriminator:?}"))
+    })?;
+    decode_indexed(index, data, DecodeMode::Strict).map(|(event, _)| event)
+}
+
+/// 把事件编码为程序日志中的原始数据（discriminator 加 Borsh 编码），即 `Program data:` 日志 base64 解码后的内容
+pub fn encode_program_data(event: &PumpEvent) -> Vec<u8> {
+    fn encode<T: BorshSerialize>(discriminator: &[u8], event: &T) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        // 写入 Vec 不会失败
+        let _ = event.serialize(&mut data);
+        data
+    }
+
+    match event {
+        PumpEvent::Create(e) => encode(CREATE_DISCRIMINATOR, e),
+        PumpEvent::CreateV2(e) => encode(CREATE_V2_DISCRIMINATOR, e),
+        PumpEvent::Complete(e) => encode(COMPLETE_DISCRIMINATOR, e),
+        PumpEvent::Trade(e) => encode(TRADE_DISCRIMINATOR, e),
+        PumpEvent::Buy(e) => encode(BUY_DISCRIMINATOR, e),
+        PumpEvent::Sell(e) => encode(SELL_DISCRIMINATOR, e),
+        PumpEvent::CreatePool(e) => encode(CREATE_POOL_DISCRIMINATOR, e),
+        PumpEvent::SetParams(e) => encode(SET_PARAMS_DISCRIMINATOR, e),
+        PumpEvent::UpdateGlobalAuthority(e) => encode(UPDATE_GLOBAL_AUTHORITY_DISCRIMINATOR, e),
+        PumpEvent::SetCreator(e) => encode(SET_CREATOR_DISCRIMINATOR, e),
+        PumpEvent::SetMetaplexCreator(e) => encode(SET_METAPLEX_CREATOR_DISCRIMINATOR, e),
+        PumpEvent::Deposit(e) => encode(DEPOSIT_DISCRIMINATOR, e),
+        PumpEvent::Withdraw(e) => encode(WITHDRAW_DISCRIMINATOR, e),
+        PumpEvent::CollectCreatorFee(e) => encode(COLLECT_CREATOR_FEE_DISCRIMINATOR, e),
+        PumpEvent::CollectCoinCreatorFee(e) => encode(COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR, e),
+        PumpEvent::Migrate(e) => encode(MIGRATE_DISCRIMINATOR, e),
+    }
+}
+
+/// 解析一笔交易日志中的所有事件
+///
+/// 日志按倒序遍历，每种事件类型在一笔交易中只解析第一次遇到的一条，
+/// 所有类型都已找到时提前结束。`visitor` 收到事件的 discriminator 和解码后的事件。
+pub fn visit_events<F>(logs: &[String], mut visitor: F)
+where
+    F: FnMut(&[u8], PumpEvent),
+{
+    visit_events_with(logs, DecodeMode::Strict, |discriminator, event, _| {
+        visitor(discriminator, event)
+    });
+}
+
+/// 按指定解码模式解析一笔交易日志中的所有事件
+///
+/// 与 [`visit_events`] 相同，`visitor` 额外收到事件数据中未知的末尾字节（严格模式下总是为空）。
+/// 返回 discriminator 匹配但解码失败的事件数量。
+pub fn visit_events_with<F>(logs: &[String], mode: DecodeMode, visitor: F) -> usize
+where
+    F: FnMut(&[u8], PumpEvent, &[u8]),
+{
+    visit_events_reporting(logs, &[], EventSource::Logs, mode, visitor, |_, _, _| {})
+}
+
+/// 从 `emit_cpi!` 自调用的内部指令数据中解析一笔交易的所有事件
+///
+/// 与 [`visit_events_with`] 相同，每种事件类型只解析倒序遍历时遇到的第一条。
+/// 返回 discriminator 匹配但解码失败的事件数量。
+pub fn visit_cpi_events_with<F>(instructions: &[Vec<u8>], mode: DecodeMode, visitor: F) -> usize
+where
+    F: FnMut(&[u8], PumpEvent, &[u8]),
+{
+    visit_events_reporting(&[], instructions, EventSource::Cpi, mode, visitor, |_, _, _| {})
+}
+
+/// 按事件来源解析日志和内部指令数据，解码失败时额外以事件的 discriminator、数据和错误调用 `on_failure`
+///
+/// `Both` 模式下先遍历日志，同一事件类型在日志中已解析时不再从内部指令数据解析。
+pub(crate) fn visit_events_reporting<F, E>(
+    logs: &[String],
+    instructions: &[Vec<u8>],
+    source: EventSource,
+    mode: DecodeMode,
+    mut visitor: F,
+    mut on_failure: E,
+) -> usize
+where
+    F: FnMut(&[u8], PumpEvent, &[u8]),
+    E: FnMut(&[u8], &[u8], &crate::Error),
+{
+    let mut seen = [false; EVENT_TYPE_COUNT];
+    let mut failures = 0;
+
+    let mut visit = |discriminator: &[u8], data: &[u8]| {
+        let Some(index) = event_index(discriminator) else {
+            return ControlFlow::Continue(());
+        };
+        if !seen[index] {
+            match decode_indexed(index, data, mode) {
+                Ok((event, tail)) => {
+                    visitor(discriminator, event, tail);
+                    seen[index] = true;
+                }
+                Err(e) => {
+                    failures += 1;
+                    log::warn!("事件解码失败: {}", e);
+                    on_failure(discriminator, data, &e);
+                }
+            }
+        }
+        if seen.iter().all(|s| *s) {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    };
+    if source.logs() {
+        visit_program_logs(logs, &mut visit);
+    }
+    if source.cpi() {
+        visit_cpi_events(instructions, &mut visit);
+    }
+    failures
+}
+
 pub trait EventTrait: Sized + std::fmt::Debug {
     fn discriminator() -> [u8; 8];
     fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>>;
@@ -175,3 +491,129 @@ impl EventTrait for SellEvent {
         discr == SELL_DISCRIMINATOR
     }
 }
+
+impl EventTrait for SetParamsEvent {
+    fn discriminator() -> [u8; 8] {
+        SET_PARAMS_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == SET_PARAMS_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for UpdateGlobalAuthorityEvent {
+    fn discriminator() -> [u8; 8] {
+        UPDATE_GLOBAL_AUTHORITY_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == UPDATE_GLOBAL_AUTHORITY_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for SetCreatorEvent {
+    fn discriminator() -> [u8; 8] {
+        SET_CREATOR_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == SET_CREATOR_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for SetMetaplexCreatorEvent {
+    fn discriminator() -> [u8; 8] {
+        SET_METAPLEX_CREATOR_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == SET_METAPLEX_CREATOR_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for DepositEvent {
+    fn discriminator() -> [u8; 8] {
+        DEPOSIT_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == DEPOSIT_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for WithdrawEvent {
+    fn discriminator() -> [u8; 8] {
+        WITHDRAW_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == WITHDRAW_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for CollectCreatorFeeEvent {
+    fn discriminator() -> [u8; 8] {
+        COLLECT_CREATOR_FEE_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == COLLECT_CREATOR_FEE_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for CollectCoinCreatorFeeEvent {
+    fn discriminator() -> [u8; 8] {
+        COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR
+    }
+}
+
+impl EventTrait for MigrateEvent {
+    fn discriminator() -> [u8; 8] {
+        MIGRATE_DISCRIMINATOR.try_into().unwrap()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_slice(bytes).map_err(|e| Box::new(e) as Box<dyn Error>)
+    }
+
+    fn valid_discrminator(discr: &[u8]) -> bool {
+        discr == MIGRATE_DISCRIMINATOR
+    }
+}
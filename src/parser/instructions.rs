@@ -0,0 +1,274 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
+#[cfg(feature = "client")]
+use yellowstone_grpc_proto::{
+    geyser::SubscribeUpdateTransactionInfo,
+    prelude::{Message, TransactionStatusMeta},
+};
+
+use crate::error::{Error, Result};
+#[cfg(feature = "client")]
+use crate::pda::PUMP_PROGRAM_ID;
+
+// 指令 discriminator 常量（sha256("global:<name>") 前 8 字节）
+pub const BUY_INSTRUCTION_DISCRIMINATOR: &[u8] = &[102, 6, 61, 18, 1, 218, 235, 234];
+pub const BUY_EXACT_SOL_IN_INSTRUCTION_DISCRIMINATOR: &[u8] = &[56, 252, 116, 8, 158, 223, 205, 95];
+pub const SELL_INSTRUCTION_DISCRIMINATOR: &[u8] = &[51, 230, 133, 164, 1, 127, 131, 173];
+pub const CREATE_INSTRUCTION_DISCRIMINATOR: &[u8] = &[24, 30, 200, 40, 5, 28, 7, 119];
+pub const MIGRATE_INSTRUCTION_DISCRIMINATOR: &[u8] = &[155, 234, 231, 146, 236, 158, 162, 30];
+
+/// Pump buy 指令参数：买入固定数量的代币
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize)]
+pub struct BuyInstruction {
+    /// 买入的代币数量
+    pub amount: u64,
+    /// 愿意支付的最大 SOL（lamports，含手续费），即滑点上限
+    pub max_sol_cost: u64,
+}
+
+/// Pump buy_exact_sol_in 指令参数：花费固定数量的 SOL
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize)]
+pub struct BuyExactSolInInstruction {
+    /// 花费的 SOL（lamports，含手续费）
+    pub spendable_sol_in: u64,
+    /// 至少获得的代币数量，即滑点下限
+    pub min_tokens_out: u64,
+}
+
+/// Pump sell 指令参数
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize)]
+pub struct SellInstruction {
+    /// 卖出的代币数量
+    pub amount: u64,
+    /// 至少获得的 SOL（lamports，扣除手续费后），即滑点下限
+    pub min_sol_output: u64,
+}
+
+/// Pump create 指令参数
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize)]
+pub struct CreateInstruction {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: Pubkey,
+}
+
+/// 解码后的 Pump 程序指令
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PumpInstruction {
+    Buy(BuyInstruction),
+    BuyExactSolIn(BuyExactSolInInstruction),
+    Sell(SellInstruction),
+    Create(CreateInstruction),
+    /// bonding curve 完成后迁移到 PumpAmm，没有参数
+    Migrate,
+}
+
+impl PumpInstruction {
+    /// 按 discriminator 识别指令类型并解码参数
+    ///
+    /// 未知类型返回 `Error::ParseError`。参数之后的字节（例如较新版本追加的可选参数）被忽略。
+    pub fn from_data(data: &[u8]) -> Result<Self> {
+        fn args<T: BorshDeserialize>(data: &[u8], name: &str) -> Result<T> {
+            let mut rest = &data[8..];
+            T::deserialize(&mut rest)
+                .map_err(|e| Error::ParseError(format!("{name} 指令参数解码失败: {e}")))
+        }
+
+        let discriminator = data.get(..8).unwrap_or_default();
+        if discriminator == BUY_INSTRUCTION_DISCRIMINATOR {
+            args(data, "buy").map(Self::Buy)
+        } else if discriminator == SELL_INSTRUCTION_DISCRIMINATOR {
+            args(data, "sell").map(Self::Sell)
+        } else if discriminator == BUY_EXACT_SOL_IN_INSTRUCTION_DISCRIMINATOR {
+            args(data, "buy_exact_sol_in").map(Self::BuyExactSolIn)
+        } else if discriminator == CREATE_INSTRUCTION_DISCRIMINATOR {
+            args(data, "create").map(Self::Create)
+        } else if discriminator == MIGRATE_INSTRUCTION_DISCRIMINATOR {
+            Ok(Self::Migrate)
+        } else {
+            Err(Error::ParseError(format!(
+                "未知的指令 discriminator: {discriminator:?}"
+            )))
+        }
+    }
+
+    /// 是否为已收录的指令类型
+    pub fn is_known(data: &[u8]) -> bool {
+        let discriminator = data.get(..8).unwrap_or_default();
+        [
+            BUY_INSTRUCTION_DISCRIMINATOR,
+            BUY_EXACT_SOL_IN_INSTRUCTION_DISCRIMINATOR,
+            SELL_INSTRUCTION_DISCRIMINATOR,
+            CREATE_INSTRUCTION_DISCRIMINATOR,
+            MIGRATE_INSTRUCTION_DISCRIMINATOR,
+        ]
+        .contains(&discriminator)
+    }
+
+    /// (mint, bonding curve, 发起指令的用户) 在账户列表中的位置
+    fn account_layout(&self) -> [usize; 3] {
+        match self {
+            Self::Buy(_) | Self::BuyExactSolIn(_) | Self::Sell(_) => [2, 3, 6],
+            Self::Create(_) => [0, 2, 7],
+            Self::Migrate => [2, 3, 5],
+        }
+    }
+}
+
+/// 交易中的一条 Pump 指令及其账户
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedInstruction {
+    /// 所在顶层指令的下标
+    pub index: usize,
+    /// 经由其他程序 CPI 调用时在该顶层指令的内部指令中的序号，顶层指令为 None
+    pub inner_index: Option<usize>,
+    pub instruction: PumpInstruction,
+    /// 按指令顺序排列的账户，签名和可写标记按交易消息的头部解析
+    pub accounts: Vec<AccountMeta>,
+}
+
+impl DecodedInstruction {
+    /// 指令操作的代币
+    pub fn mint(&self) -> Option<Pubkey> {
+        self.account(0)
+    }
+
+    /// 代币的 bonding curve 账户
+    pub fn bonding_curve(&self) -> Option<Pubkey> {
+        self.account(1)
+    }
+
+    /// 发起指令的用户（买卖的交易者、代币创建者或迁移的付款人）
+    pub fn user(&self) -> Option<Pubkey> {
+        self.account(2)
+    }
+
+    fn account(&self, role: usize) -> Option<Pubkey> {
+        let position = self.instruction.account_layout()[role];
+        self.accounts.get(position).map(|meta| meta.pubkey)
+    }
+}
+
+/// 解码 gRPC 交易更新中的 Pump 指令（需要启用 `client` feature）
+///
+/// 见 [`decode_message`]，交易没有消息时返回空列表。
+#[cfg(feature = "client")]
+pub fn decode_transaction(tx: &SubscribeUpdateTransactionInfo) -> Vec<DecodedInstruction> {
+    tx.transaction
+        .as_ref()
+        .and_then(|tx| tx.message.as_ref())
+        .map(|message| decode_message(message, tx.meta.as_ref()))
+        .unwrap_or_default()
+}
+
+/// 解码交易消息中的 Pump 指令，按执行顺序返回
+///
+/// 提供 `meta` 时同时解码经由其他程序（交易机器人、聚合器）CPI 调用的内部指令，
+/// 并解析地址查找表加载的账户；未收录的指令类型被跳过，参数解码失败的指令记录一条警告后跳过。
+#[cfg(feature = "client")]
+pub fn decode_message(
+    message: &Message,
+    meta: Option<&TransactionStatusMeta>,
+) -> Vec<DecodedInstruction> {
+    let keys = AccountKeys::new(message, meta);
+    let mut decoded = Vec::new();
+    let mut push =
+        |index: usize, inner_index: Option<usize>, program: u32, accounts: &[u8], data: &[u8]| {
+            if keys.pubkey(program as usize) != Some(PUMP_PROGRAM_ID)
+                || !PumpInstruction::is_known(data)
+            {
+                return;
+            }
+            match PumpInstruction::from_data(data) {
+                Ok(instruction) => decoded.push(DecodedInstruction {
+                    index,
+                    inner_index,
+                    instruction,
+                    accounts: accounts
+                        .iter()
+                        .filter_map(|account| keys.meta(*account as usize))
+                        .collect(),
+                }),
+                Err(e) => log::warn!("指令解码失败: {}", e),
+            }
+        };
+
+    for (index, ix) in message.instructions.iter().enumerate() {
+        push(index, None, ix.program_id_index, &ix.accounts, &ix.data);
+        let inner = meta
+            .into_iter()
+            .flat_map(|meta| &meta.inner_instructions)
+            .filter(|inner| inner.index as usize == index)
+            .flat_map(|inner| inner.instructions.iter().enumerate());
+        for (inner_index, ix) in inner {
+            push(
+                index,
+                Some(inner_index),
+                ix.program_id_index,
+                &ix.accounts,
+                &ix.data,
+            );
+        }
+    }
+    decoded
+}
+
+/// 交易的完整账户列表：消息中的静态账户，其后依次为地址查找表加载的可写、只读账户
+#[cfg(feature = "client")]
+struct AccountKeys {
+    keys: Vec<Pubkey>,
+    num_static: usize,
+    num_signers: usize,
+    num_writable_signers: usize,
+    num_writable_unsigned_end: usize,
+    num_loaded_writable: usize,
+}
+
+#[cfg(feature = "client")]
+impl AccountKeys {
+    fn new(message: &Message, meta: Option<&TransactionStatusMeta>) -> Self {
+        let loaded_writable = meta.map(|meta| meta.loaded_writable_addresses.as_slice());
+        let loaded_readonly = meta.map(|meta| meta.loaded_readonly_addresses.as_slice());
+        let keys: Vec<Pubkey> = message
+            .account_keys
+            .iter()
+            .chain(loaded_writable.unwrap_or_default())
+            .chain(loaded_readonly.unwrap_or_default())
+            .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+            .collect();
+        let header = message.header.unwrap_or_default();
+        let num_static = message.account_keys.len();
+        let num_signers = header.num_required_signatures as usize;
+        Self {
+            keys,
+            num_static,
+            num_signers,
+            num_writable_signers: num_signers
+                .saturating_sub(header.num_readonly_signed_accounts as usize),
+            num_writable_unsigned_end: num_static
+                .saturating_sub(header.num_readonly_unsigned_accounts as usize),
+            num_loaded_writable: loaded_writable.map_or(0, <[_]>::len),
+        }
+    }
+
+    fn pubkey(&self, index: usize) -> Option<Pubkey> {
+        self.keys.get(index).copied()
+    }
+
+    fn meta(&self, index: usize) -> Option<AccountMeta> {
+        let pubkey = self.pubkey(index)?;
+        let is_writable = if index < self.num_signers {
+            index < self.num_writable_signers
+        } else if index < self.num_static {
+            index < self.num_writable_unsigned_end
+        } else {
+            index < self.num_static + self.num_loaded_writable
+        };
+        Some(AccountMeta {
+            pubkey,
+            is_signer: index < self.num_signers,
+            is_writable,
+        })
+    }
+}
@@ -0,0 +1,462 @@
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::Error,
+    models::{
+        BondingCurveAccount, BuyEvent, CollectCoinCreatorFeeEvent, CollectCreatorFeeEvent,
+        CompleteEvent, CreateEvent, CreatePoolEvent, CreateV2Event, DepositEvent, GlobalAccount,
+        GlobalConfigAccount, MigrateEvent, PoolAccount, SellEvent, SetCreatorEvent,
+        SetMetaplexCreatorEvent, SetParamsEvent, TradeEvent, UpdateGlobalAuthorityEvent,
+        WithdrawEvent,
+    },
+};
+
+/// 末尾存在多余字节时报告的字段名
+pub const TRAILING_FIELD: &str = "<trailing>";
+
+/// 按字段顺序描述 Borsh 布局的类型
+///
+/// 整体解码失败后用于定位出错的字段：按声明顺序逐个字段重新解码，
+/// 返回带有类型名、字段名和字节偏移的 [`Error::FieldDecode`]。
+pub trait FieldLayout {
+    /// 类型名称
+    const NAME: &'static str;
+
+    /// 定位 `data` 解码失败的位置
+    fn diagnose(data: &[u8]) -> Error;
+}
+
+/// 逐字段读取并记录偏移
+struct FieldReader<'a> {
+    name: &'static str,
+    data: &'a [u8],
+    rest: &'a [u8],
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(name: &'static str, data: &'a [u8]) -> Self {
+        Self {
+            name,
+            data,
+            rest: data,
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.data.len() - self.rest.len()
+    }
+
+    fn read<T: BorshDeserialize>(&mut self, field: &'static str) -> Result<(), Error> {
+        let offset = self.offset();
+        T::deserialize(&mut self.rest)
+            .map(drop)
+            .map_err(|source| Error::FieldDecode {
+                type_name: self.name,
+                field,
+                offset,
+                source,
+            })
+    }
+
+    /// 所有字段都解码成功时，报告末尾多余的字节
+    fn finish(self) -> Error {
+        let offset = self.offset();
+        Error::FieldDecode {
+            type_name: self.name,
+            field: TRAILING_FIELD,
+            offset,
+            source: std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} 个未知的末尾字节", self.rest.len()),
+            ),
+        }
+    }
+}
+
+/// 为模型声明字段布局
+///
+/// 字段列表必须与结构体定义一致：解构模式保证字段名完整，类型断言保证字段类型匹配，
+/// 模型新增或修改字段而这里没有同步时会编译失败（字段顺序需人工保持一致）。
+macro_rules! field_layout {
+    ($ty:ident { $($field:ident: $fty:ty),* $(,)? }) => {
+        impl FieldLayout for $ty {
+            const NAME: &'static str = stringify!($ty);
+
+            fn diagnose(data: &[u8]) -> Error {
+                let _check = |value: &$ty| {
+                    let $ty { $($field),* } = value;
+                    $(let _: &$fty = $field;)*
+                };
+                let mut reader = FieldReader::new(Self::NAME, data);
+                $(
+                    if let Err(e) = reader.read::<$fty>(stringify!($field)) {
+                        return e;
+                    }
+                )*
+                reader.finish()
+            }
+        }
+    };
+}
+
+field_layout!(CreateEvent {
+    name: String,
+    symbol: String,
+    uri: String,
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    user: Pubkey,
+    creator: Pubkey,
+    timestamp: i64,
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    token_total_supply: u64,
+    token_program: Pubkey,
+    is_mayhem_mode: bool,
+});
+
+field_layout!(CreateV2Event {
+    name: String,
+    symbol: String,
+    uri: String,
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    user: Pubkey,
+    creator: Pubkey,
+    timestamp: i64,
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    token_total_supply: u64,
+    token_program: Pubkey,
+    is_mayhem_mode: bool,
+});
+
+field_layout!(CompleteEvent {
+    user: Pubkey,
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    timestamp: i64,
+});
+
+field_layout!(TradeEvent {
+    mint: Pubkey,
+    sol_amount: u64,
+    token_amount: u64,
+    is_buy: bool,
+    user: Pubkey,
+    timestamp: i64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    real_sol_reserves: u64,
+    real_token_reserves: u64,
+    fee_recipient: Pubkey,
+    fee_basis_points: u64,
+    fee: u64,
+    creator: Pubkey,
+    creator_fee_basis_points: u64,
+    creator_fee: u64,
+    track_volume: bool,
+    total_unclaimed_tokens: u64,
+    total_claimed_tokens: u64,
+    current_sol_volume: u64,
+    last_update_timestamp: i64,
+    ix_name: String,
+});
+
+field_layout!(BuyEvent {
+    timestamp: i64,
+    base_amount_out: u64,
+    max_quote_amount_in: u64,
+    user_base_token_reserves: u64,
+    user_quote_token_reserves: u64,
+    pool_base_token_reserves: u64,
+    pool_quote_token_reserves: u64,
+    quote_amount_in: u64,
+    lp_fee_basis_points: u64,
+    lp_fee: u64,
+    protocol_fee_basis_points: u64,
+    protocol_fee: u64,
+    quote_amount_in_with_lp_fee: u64,
+    user_quote_amount_in: u64,
+    pool: Pubkey,
+    user: Pubkey,
+    user_base_token_account: Pubkey,
+    user_quote_token_account: Pubkey,
+    protocol_fee_recipient: Pubkey,
+    protocol_fee_recipient_token_account: Pubkey,
+    coin_creator: Pubkey,
+    coin_creator_fee_basis_points: u64,
+    coin_creator_fee: u64,
+    track_volume: bool,
+    total_unclaimed_tokens: u64,
+    total_claimed_tokens: u64,
+    current_sol_volume: u64,
+    last_update_timestamp: i64,
+    min_base_amount_out: u64,
+    ix_name: String,
+});
+
+field_layout!(SellEvent {
+    timestamp: i64,
+    base_amount_in: u64,
+    min_quote_amount_out: u64,
+    user_base_token_reserves: u64,
+    user_quote_token_reserves: u64,
+    pool_base_token_reserves: u64,
+    pool_quote_token_reserves: u64,
+    quote_amount_out: u64,
+    lp_fee_basis_points: u64,
+    lp_fee: u64,
+    protocol_fee_basis_points: u64,
+    protocol_fee: u64,
+    quote_amount_out_without_lp_fee: u64,
+    user_quote_amount_out: u64,
+    pool: Pubkey,
+    user: Pubkey,
+    user_base_token_account: Pubkey,
+    user_quote_token_account: Pubkey,
+    protocol_fee_recipient: Pubkey,
+    protocol_fee_recipient_token_account: Pubkey,
+    coin_creator: Pubkey,
+    coin_creator_fee_basis_points: u64,
+    coin_creator_fee: u64,
+});
+
+field_layout!(CreatePoolEvent {
+    timestamp: i64,
+    index: u16,
+    creator: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    base_mint_decimals: u8,
+    quote_mint_decimals: u8,
+    base_amount_in: u64,
+    quote_amount_in: u64,
+    pool_base_amount: u64,
+    pool_quote_amount: u64,
+    minimum_liquidity: u64,
+    initial_liquidity: u64,
+    lp_token_amount_out: u64,
+    pool_bump: u8,
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    user_base_token_account: Pubkey,
+    user_quote_token_account: Pubkey,
+    coin_creator: Pubkey,
+    is_mayhem_mode: bool,
+});
+
+field_layout!(SetParamsEvent {
+    initial_virtual_token_reserves: u64,
+    initial_virtual_sol_reserves: u64,
+    initial_real_token_reserves: u64,
+    final_real_sol_reserves: u64,
+    token_total_supply: u64,
+    fee_basis_points: u64,
+    withdraw_authority: Pubkey,
+    enable_migrate: bool,
+    pool_migration_fee: u64,
+    creator_fee_basis_points: u64,
+    fee_recipients: [Pubkey; 8],
+    timestamp: i64,
+    set_creator_authority: Pubkey,
+    admin_set_creator_authority: Pubkey,
+});
+
+field_layout!(UpdateGlobalAuthorityEvent {
+    global: Pubkey,
+    authority: Pubkey,
+    new_authority: Pubkey,
+    timestamp: i64,
+});
+
+field_layout!(SetCreatorEvent {
+    timestamp: i64,
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    creator: Pubkey,
+});
+
+field_layout!(SetMetaplexCreatorEvent {
+    timestamp: i64,
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    metadata: Pubkey,
+    creator: Pubkey,
+});
+
+field_layout!(DepositEvent {
+    timestamp: i64,
+    lp_token_amount_out: u64,
+    max_base_amount_in: u64,
+    max_quote_amount_in: u64,
+    user_base_token_reserves: u64,
+    user_quote_token_reserves: u64,
+    pool_base_token_reserves: u64,
+    pool_quote_token_reserves: u64,
+    base_amount_in: u64,
+    quote_amount_in: u64,
+    lp_mint_supply: u64,
+    pool: Pubkey,
+    user: Pubkey,
+    user_base_token_account: Pubkey,
+    user_quote_token_account: Pubkey,
+    user_pool_token_account: Pubkey,
+});
+
+field_layout!(WithdrawEvent {
+    timestamp: i64,
+    lp_token_amount_in: u64,
+    min_base_amount_out: u64,
+    min_quote_amount_out: u64,
+    user_base_token_reserves: u64,
+    user_quote_token_reserves: u64,
+    pool_base_token_reserves: u64,
+    pool_quote_token_reserves: u64,
+    base_amount_out: u64,
+    quote_amount_out: u64,
+    lp_mint_supply: u64,
+    pool: Pubkey,
+    user: Pubkey,
+    user_base_token_account: Pubkey,
+    user_quote_token_account: Pubkey,
+    user_pool_token_account: Pubkey,
+});
+
+field_layout!(CollectCreatorFeeEvent {
+    timestamp: i64,
+    creator: Pubkey,
+    creator_fee: u64,
+});
+
+field_layout!(CollectCoinCreatorFeeEvent {
+    timestamp: i64,
+    coin_creator: Pubkey,
+    coin_creator_fee: u64,
+    coin_creator_vault_ata: Pubkey,
+    coin_creator_token_account: Pubkey,
+});
+
+field_layout!(MigrateEvent {
+    user: Pubkey,
+    mint: Pubkey,
+    mint_amount: u64,
+    sol_amount: u64,
+    pool_migration_fee: u64,
+    bonding_curve: Pubkey,
+    timestamp: i64,
+    pool: Pubkey,
+});
+
+field_layout!(BondingCurveAccount {
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    real_sol_reserves: u64,
+    token_total_supply: u64,
+    complete: bool,
+    creator: Pubkey,
+    is_mayhem_mode: bool,
+});
+
+field_layout!(GlobalAccount {
+    initialized: bool,
+    authority: Pubkey,
+    fee_recipient: Pubkey,
+    initial_virtual_token_reserves: u64,
+    initial_virtual_sol_reserves: u64,
+    initial_real_token_reserves: u64,
+    token_total_supply: u64,
+    fee_basis_points: u64,
+    withdraw_authority: Pubkey,
+    enable_migrate: bool,
+    pool_migration_fee: u64,
+    creator_fee_basis_points: u64,
+    fee_recipients: [Pubkey; 7],
+    set_creator_authority: Pubkey,
+    admin_set_creator_authority: Pubkey,
+});
+
+field_layout!(GlobalConfigAccount {
+    admin: Pubkey,
+    lp_fee_basis_points: u64,
+    protocol_fee_basis_points: u64,
+    disable_flags: u8,
+    protocol_fee_recipients: [Pubkey; 8],
+    coin_creator_fee_basis_points: u64,
+    admin_set_coin_creator_authority: Pubkey,
+});
+
+field_layout!(PoolAccount {
+    pool_bump: u8,
+    index: u16,
+    creator: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    lp_mint: Pubkey,
+    pool_base_token_account: Pubkey,
+    pool_quote_token_account: Pubkey,
+    lp_supply: u64,
+    coin_creator: Pubkey,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::to_vec;
+
+    fn field_error(error: Error) -> (&'static str, &'static str, usize) {
+        match error {
+            Error::FieldDecode {
+                type_name,
+                field,
+                offset,
+                ..
+            } => (type_name, field, offset),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn locates_truncated_field() {
+        let data = to_vec(&CompleteEvent::default()).unwrap();
+        assert_eq!(field_error(CompleteEvent::diagnose(&[])), ("CompleteEvent", "user", 0));
+        assert_eq!(
+            field_error(CompleteEvent::diagnose(&data[..40])),
+            ("CompleteEvent", "mint", 32)
+        );
+        assert_eq!(
+            field_error(CompleteEvent::diagnose(&data[..100])),
+            ("CompleteEvent", "timestamp", 96)
+        );
+    }
+
+    #[test]
+    fn reports_trailing_bytes_after_last_field() {
+        let mut data = to_vec(&CollectCreatorFeeEvent::default()).unwrap();
+        let len = data.len();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            field_error(CollectCreatorFeeEvent::diagnose(&data)),
+            ("CollectCreatorFeeEvent", TRAILING_FIELD, len)
+        );
+    }
+
+    #[test]
+    fn locates_invalid_string() {
+        let event = TradeEvent {
+            ix_name: "buy".to_string(),
+            ..Default::default()
+        };
+        let mut data = to_vec(&event).unwrap();
+        // `ix_name` 的内容改为非法 UTF-8
+        let last = data.len() - 1;
+        data[last] = 0xff;
+        let (type_name, field, offset) = field_error(TradeEvent::diagnose(&data));
+        assert_eq!((type_name, field), ("TradeEvent", "ix_name"));
+        assert_eq!(offset, data.len() - 4 - 3);
+    }
+}
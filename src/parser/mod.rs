@@ -1 +1,6 @@
+pub mod accounts;
+pub mod diagnosis;
+pub mod errors;
 pub mod events;
+pub mod instructions;
+pub mod layout;
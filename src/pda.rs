@@ -0,0 +1,69 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+/// Pump 程序ID
+pub const PUMP_PROGRAM_ID: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+/// PumpAmm 程序ID
+pub const PUMP_AMM_PROGRAM_ID: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+/// SPL Token 程序ID
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+/// SPL Token-2022 程序ID
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+/// Associated Token Account 程序ID
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+/// Wrapped SOL mint
+pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// Pump 全局配置账户地址
+pub fn global() -> Pubkey {
+    Pubkey::find_program_address(&[b"global"], &PUMP_PROGRAM_ID).0
+}
+
+/// PumpAmm 全局配置账户地址
+pub fn global_config() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_config"], &PUMP_AMM_PROGRAM_ID).0
+}
+
+/// 推导 mint 对应的 bonding curve 地址
+pub fn bonding_curve(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+/// 推导关联代币账户（ATA）地址
+pub fn associated_token_account(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// 推导 bonding curve 持有代币的关联账户地址
+pub fn associated_bonding_curve(mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    associated_token_account(&bonding_curve(mint), mint, token_program)
+}
+
+/// 推导迁移池的创建者（Pump 程序的 pool-authority PDA）
+pub fn pool_authority(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pool-authority", mint.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+/// 推导 PumpAmm 池地址
+pub fn pool(index: u16, creator: &Pubkey, base_mint: &Pubkey, quote_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"pool",
+            &index.to_le_bytes(),
+            creator.as_ref(),
+            base_mint.as_ref(),
+            quote_mint.as_ref(),
+        ],
+        &PUMP_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// 推导曲线完成后迁移生成的标准 PumpAmm 池地址（mint/WSOL，index 为 0）
+pub fn canonical_pump_pool(mint: &Pubkey) -> Pubkey {
+    pool(0, &pool_authority(mint), mint, &WSOL_MINT)
+}
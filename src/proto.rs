@@ -0,0 +1,910 @@
+//! Protobuf 编码（需要启用 `protobuf` feature）
+//!
+//! 这里的消息类型与 `proto/pump_events.proto` 一一对应，手写 prost 定义以避免构建时依赖 protoc。
+//! 其他语言的服务可以直接用该 proto 文件生成代码，消费基于 SDK 的生产者写入 Kafka / NATS 的事件。
+//! 修改时必须同步更新 proto 文件，且只能追加新的字段编号。
+
+use std::time::UNIX_EPOCH;
+
+use prost::Message;
+
+use crate::{client::EventContext, models};
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateEvent {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub symbol: String,
+    #[prost(string, tag = "3")]
+    pub uri: String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub bonding_curve: Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub creator: Vec<u8>,
+    #[prost(int64, tag = "8")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "9")]
+    pub virtual_token_reserves: u64,
+    #[prost(uint64, tag = "10")]
+    pub virtual_sol_reserves: u64,
+    #[prost(uint64, tag = "11")]
+    pub real_token_reserves: u64,
+    #[prost(uint64, tag = "12")]
+    pub token_total_supply: u64,
+    #[prost(bytes = "vec", tag = "13")]
+    pub token_program: Vec<u8>,
+    #[prost(bool, tag = "14")]
+    pub is_mayhem_mode: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateV2Event {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub symbol: String,
+    #[prost(string, tag = "3")]
+    pub uri: String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub bonding_curve: Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub creator: Vec<u8>,
+    #[prost(int64, tag = "8")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "9")]
+    pub virtual_token_reserves: u64,
+    #[prost(uint64, tag = "10")]
+    pub virtual_sol_reserves: u64,
+    #[prost(uint64, tag = "11")]
+    pub real_token_reserves: u64,
+    #[prost(uint64, tag = "12")]
+    pub token_total_supply: u64,
+    #[prost(bytes = "vec", tag = "13")]
+    pub token_program: Vec<u8>,
+    #[prost(bool, tag = "14")]
+    pub is_mayhem_mode: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompleteEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub bonding_curve: Vec<u8>,
+    #[prost(int64, tag = "4")]
+    pub timestamp: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TradeEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub sol_amount: u64,
+    #[prost(uint64, tag = "3")]
+    pub token_amount: u64,
+    #[prost(bool, tag = "4")]
+    pub is_buy: bool,
+    #[prost(bytes = "vec", tag = "5")]
+    pub user: Vec<u8>,
+    #[prost(int64, tag = "6")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "7")]
+    pub virtual_sol_reserves: u64,
+    #[prost(uint64, tag = "8")]
+    pub virtual_token_reserves: u64,
+    #[prost(uint64, tag = "9")]
+    pub real_sol_reserves: u64,
+    #[prost(uint64, tag = "10")]
+    pub real_token_reserves: u64,
+    #[prost(bytes = "vec", tag = "11")]
+    pub fee_recipient: Vec<u8>,
+    #[prost(uint64, tag = "12")]
+    pub fee_basis_points: u64,
+    #[prost(uint64, tag = "13")]
+    pub fee: u64,
+    #[prost(bytes = "vec", tag = "14")]
+    pub creator: Vec<u8>,
+    #[prost(uint64, tag = "15")]
+    pub creator_fee_basis_points: u64,
+    #[prost(uint64, tag = "16")]
+    pub creator_fee: u64,
+    #[prost(bool, tag = "17")]
+    pub track_volume: bool,
+    #[prost(uint64, tag = "18")]
+    pub total_unclaimed_tokens: u64,
+    #[prost(uint64, tag = "19")]
+    pub total_claimed_tokens: u64,
+    #[prost(uint64, tag = "20")]
+    pub current_sol_volume: u64,
+    #[prost(int64, tag = "21")]
+    pub last_update_timestamp: i64,
+    #[prost(string, tag = "22")]
+    pub ix_name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BuyEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "2")]
+    pub base_amount_out: u64,
+    #[prost(uint64, tag = "3")]
+    pub max_quote_amount_in: u64,
+    #[prost(uint64, tag = "4")]
+    pub user_base_token_reserves: u64,
+    #[prost(uint64, tag = "5")]
+    pub user_quote_token_reserves: u64,
+    #[prost(uint64, tag = "6")]
+    pub pool_base_token_reserves: u64,
+    #[prost(uint64, tag = "7")]
+    pub pool_quote_token_reserves: u64,
+    #[prost(uint64, tag = "8")]
+    pub quote_amount_in: u64,
+    #[prost(uint64, tag = "9")]
+    pub lp_fee_basis_points: u64,
+    #[prost(uint64, tag = "10")]
+    pub lp_fee: u64,
+    #[prost(uint64, tag = "11")]
+    pub protocol_fee_basis_points: u64,
+    #[prost(uint64, tag = "12")]
+    pub protocol_fee: u64,
+    #[prost(uint64, tag = "13")]
+    pub quote_amount_in_with_lp_fee: u64,
+    #[prost(uint64, tag = "14")]
+    pub user_quote_amount_in: u64,
+    #[prost(bytes = "vec", tag = "15")]
+    pub pool: Vec<u8>,
+    #[prost(bytes = "vec", tag = "16")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "17")]
+    pub user_base_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "18")]
+    pub user_quote_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "19")]
+    pub protocol_fee_recipient: Vec<u8>,
+    #[prost(bytes = "vec", tag = "20")]
+    pub protocol_fee_recipient_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "21")]
+    pub coin_creator: Vec<u8>,
+    #[prost(uint64, tag = "22")]
+    pub coin_creator_fee_basis_points: u64,
+    #[prost(uint64, tag = "23")]
+    pub coin_creator_fee: u64,
+    #[prost(bool, tag = "24")]
+    pub track_volume: bool,
+    #[prost(uint64, tag = "25")]
+    pub total_unclaimed_tokens: u64,
+    #[prost(uint64, tag = "26")]
+    pub total_claimed_tokens: u64,
+    #[prost(uint64, tag = "27")]
+    pub current_sol_volume: u64,
+    #[prost(int64, tag = "28")]
+    pub last_update_timestamp: i64,
+    #[prost(uint64, tag = "29")]
+    pub min_base_amount_out: u64,
+    #[prost(string, tag = "30")]
+    pub ix_name: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SellEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "2")]
+    pub base_amount_in: u64,
+    #[prost(uint64, tag = "3")]
+    pub min_quote_amount_out: u64,
+    #[prost(uint64, tag = "4")]
+    pub user_base_token_reserves: u64,
+    #[prost(uint64, tag = "5")]
+    pub user_quote_token_reserves: u64,
+    #[prost(uint64, tag = "6")]
+    pub pool_base_token_reserves: u64,
+    #[prost(uint64, tag = "7")]
+    pub pool_quote_token_reserves: u64,
+    #[prost(uint64, tag = "8")]
+    pub quote_amount_out: u64,
+    #[prost(uint64, tag = "9")]
+    pub lp_fee_basis_points: u64,
+    #[prost(uint64, tag = "10")]
+    pub lp_fee: u64,
+    #[prost(uint64, tag = "11")]
+    pub protocol_fee_basis_points: u64,
+    #[prost(uint64, tag = "12")]
+    pub protocol_fee: u64,
+    #[prost(uint64, tag = "13")]
+    pub quote_amount_out_without_lp_fee: u64,
+    #[prost(uint64, tag = "14")]
+    pub user_quote_amount_out: u64,
+    #[prost(bytes = "vec", tag = "15")]
+    pub pool: Vec<u8>,
+    #[prost(bytes = "vec", tag = "16")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "17")]
+    pub user_base_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "18")]
+    pub user_quote_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "19")]
+    pub protocol_fee_recipient: Vec<u8>,
+    #[prost(bytes = "vec", tag = "20")]
+    pub protocol_fee_recipient_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "21")]
+    pub coin_creator: Vec<u8>,
+    #[prost(uint64, tag = "22")]
+    pub coin_creator_fee_basis_points: u64,
+    #[prost(uint64, tag = "23")]
+    pub coin_creator_fee: u64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreatePoolEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(uint32, tag = "2")]
+    pub index: u32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub creator: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub base_mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub quote_mint: Vec<u8>,
+    #[prost(uint32, tag = "6")]
+    pub base_mint_decimals: u32,
+    #[prost(uint32, tag = "7")]
+    pub quote_mint_decimals: u32,
+    #[prost(uint64, tag = "8")]
+    pub base_amount_in: u64,
+    #[prost(uint64, tag = "9")]
+    pub quote_amount_in: u64,
+    #[prost(uint64, tag = "10")]
+    pub pool_base_amount: u64,
+    #[prost(uint64, tag = "11")]
+    pub pool_quote_amount: u64,
+    #[prost(uint64, tag = "12")]
+    pub minimum_liquidity: u64,
+    #[prost(uint64, tag = "13")]
+    pub initial_liquidity: u64,
+    #[prost(uint64, tag = "14")]
+    pub lp_token_amount_out: u64,
+    #[prost(uint32, tag = "15")]
+    pub pool_bump: u32,
+    #[prost(bytes = "vec", tag = "16")]
+    pub pool: Vec<u8>,
+    #[prost(bytes = "vec", tag = "17")]
+    pub lp_mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "18")]
+    pub user_base_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "19")]
+    pub user_quote_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "20")]
+    pub coin_creator: Vec<u8>,
+    #[prost(bool, tag = "21")]
+    pub is_mayhem_mode: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetParamsEvent {
+    #[prost(uint64, tag = "1")]
+    pub initial_virtual_token_reserves: u64,
+    #[prost(uint64, tag = "2")]
+    pub initial_virtual_sol_reserves: u64,
+    #[prost(uint64, tag = "3")]
+    pub initial_real_token_reserves: u64,
+    #[prost(uint64, tag = "4")]
+    pub final_real_sol_reserves: u64,
+    #[prost(uint64, tag = "5")]
+    pub token_total_supply: u64,
+    #[prost(uint64, tag = "6")]
+    pub fee_basis_points: u64,
+    #[prost(bytes = "vec", tag = "7")]
+    pub withdraw_authority: Vec<u8>,
+    #[prost(bool, tag = "8")]
+    pub enable_migrate: bool,
+    #[prost(uint64, tag = "9")]
+    pub pool_migration_fee: u64,
+    #[prost(uint64, tag = "10")]
+    pub creator_fee_basis_points: u64,
+    #[prost(bytes = "vec", repeated, tag = "11")]
+    pub fee_recipients: Vec<Vec<u8>>,
+    #[prost(int64, tag = "12")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "13")]
+    pub set_creator_authority: Vec<u8>,
+    #[prost(bytes = "vec", tag = "14")]
+    pub admin_set_creator_authority: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateGlobalAuthorityEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub global: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub authority: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub new_authority: Vec<u8>,
+    #[prost(int64, tag = "4")]
+    pub timestamp: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetCreatorEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub bonding_curve: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub creator: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMetaplexCreatorEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub bonding_curve: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub metadata: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub creator: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DepositEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "2")]
+    pub lp_token_amount_out: u64,
+    #[prost(uint64, tag = "3")]
+    pub max_base_amount_in: u64,
+    #[prost(uint64, tag = "4")]
+    pub max_quote_amount_in: u64,
+    #[prost(uint64, tag = "5")]
+    pub user_base_token_reserves: u64,
+    #[prost(uint64, tag = "6")]
+    pub user_quote_token_reserves: u64,
+    #[prost(uint64, tag = "7")]
+    pub pool_base_token_reserves: u64,
+    #[prost(uint64, tag = "8")]
+    pub pool_quote_token_reserves: u64,
+    #[prost(uint64, tag = "9")]
+    pub base_amount_in: u64,
+    #[prost(uint64, tag = "10")]
+    pub quote_amount_in: u64,
+    #[prost(uint64, tag = "11")]
+    pub lp_mint_supply: u64,
+    #[prost(bytes = "vec", tag = "12")]
+    pub pool: Vec<u8>,
+    #[prost(bytes = "vec", tag = "13")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "14")]
+    pub user_base_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "15")]
+    pub user_quote_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "16")]
+    pub user_pool_token_account: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithdrawEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "2")]
+    pub lp_token_amount_in: u64,
+    #[prost(uint64, tag = "3")]
+    pub min_base_amount_out: u64,
+    #[prost(uint64, tag = "4")]
+    pub min_quote_amount_out: u64,
+    #[prost(uint64, tag = "5")]
+    pub user_base_token_reserves: u64,
+    #[prost(uint64, tag = "6")]
+    pub user_quote_token_reserves: u64,
+    #[prost(uint64, tag = "7")]
+    pub pool_base_token_reserves: u64,
+    #[prost(uint64, tag = "8")]
+    pub pool_quote_token_reserves: u64,
+    #[prost(uint64, tag = "9")]
+    pub base_amount_out: u64,
+    #[prost(uint64, tag = "10")]
+    pub quote_amount_out: u64,
+    #[prost(uint64, tag = "11")]
+    pub lp_mint_supply: u64,
+    #[prost(bytes = "vec", tag = "12")]
+    pub pool: Vec<u8>,
+    #[prost(bytes = "vec", tag = "13")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "14")]
+    pub user_base_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "15")]
+    pub user_quote_token_account: Vec<u8>,
+    #[prost(bytes = "vec", tag = "16")]
+    pub user_pool_token_account: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectCreatorFeeEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub creator: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub creator_fee: u64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectCoinCreatorFeeEvent {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub coin_creator: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub coin_creator_fee: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub coin_creator_vault_ata: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub coin_creator_token_account: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MigrateEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub user: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub mint_amount: u64,
+    #[prost(uint64, tag = "4")]
+    pub sol_amount: u64,
+    #[prost(uint64, tag = "5")]
+    pub pool_migration_fee: u64,
+    #[prost(bytes = "vec", tag = "6")]
+    pub bonding_curve: Vec<u8>,
+    #[prost(int64, tag = "7")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "8")]
+    pub pool: Vec<u8>,
+}
+
+/// 事件及其所在交易的上下文
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PumpEventEnvelope {
+    #[prost(uint64, tag = "1")]
+    pub slot: u64,
+    #[prost(uint64, tag = "2")]
+    pub tx_index: u64,
+    /// 64 字节交易签名
+    #[prost(bytes = "vec", tag = "3")]
+    pub signature: Vec<u8>,
+    /// 链上区块时间（Unix 秒）
+    #[prost(int64, tag = "4")]
+    pub block_time: i64,
+    /// 收到交易时的系统时间（Unix 毫秒）
+    #[prost(uint64, tag = "5")]
+    pub received_at_ms: u64,
+    #[prost(
+        oneof = "pump_event_envelope::Event",
+        tags = "16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31"
+    )]
+    pub event: Option<pump_event_envelope::Event>,
+}
+
+pub mod pump_event_envelope {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    #[allow(clippy::large_enum_variant)]
+    pub enum Event {
+        #[prost(message, tag = "16")]
+        Create(super::CreateEvent),
+        #[prost(message, tag = "17")]
+        CreateV2(super::CreateV2Event),
+        #[prost(message, tag = "18")]
+        Complete(super::CompleteEvent),
+        #[prost(message, tag = "19")]
+        Trade(super::TradeEvent),
+        #[prost(message, tag = "20")]
+        Buy(super::BuyEvent),
+        #[prost(message, tag = "21")]
+        Sell(super::SellEvent),
+        #[prost(message, tag = "22")]
+        CreatePool(super::CreatePoolEvent),
+        #[prost(message, tag = "23")]
+        SetParams(super::SetParamsEvent),
+        #[prost(message, tag = "24")]
+        UpdateGlobalAuthority(super::UpdateGlobalAuthorityEvent),
+        #[prost(message, tag = "25")]
+        SetCreator(super::SetCreatorEvent),
+        #[prost(message, tag = "26")]
+        SetMetaplexCreator(super::SetMetaplexCreatorEvent),
+        #[prost(message, tag = "27")]
+        Deposit(super::DepositEvent),
+        #[prost(message, tag = "28")]
+        Withdraw(super::WithdrawEvent),
+        #[prost(message, tag = "29")]
+        CollectCreatorFee(super::CollectCreatorFeeEvent),
+        #[prost(message, tag = "30")]
+        CollectCoinCreatorFee(super::CollectCoinCreatorFeeEvent),
+        #[prost(message, tag = "31")]
+        Migrate(super::MigrateEvent),
+    }
+}
+
+impl From<&models::CreateEvent> for CreateEvent {
+    fn from(e: &models::CreateEvent) -> Self {
+        Self {
+            name: e.name.clone(),
+            symbol: e.symbol.clone(),
+            uri: e.uri.clone(),
+            mint: e.mint.to_bytes().to_vec(),
+            bonding_curve: e.bonding_curve.to_bytes().to_vec(),
+            user: e.user.to_bytes().to_vec(),
+            creator: e.creator.to_bytes().to_vec(),
+            timestamp: e.timestamp,
+            virtual_token_reserves: e.virtual_token_reserves,
+            virtual_sol_reserves: e.virtual_sol_reserves,
+            real_token_reserves: e.real_token_reserves,
+            token_total_supply: e.token_total_supply,
+            token_program: e.token_program.to_bytes().to_vec(),
+            is_mayhem_mode: e.is_mayhem_mode,
+        }
+    }
+}
+
+impl From<&models::CreateV2Event> for CreateV2Event {
+    fn from(e: &models::CreateV2Event) -> Self {
+        Self {
+            name: e.name.clone(),
+            symbol: e.symbol.clone(),
+            uri: e.uri.clone(),
+            mint: e.mint.to_bytes().to_vec(),
+            bonding_curve: e.bonding_curve.to_bytes().to_vec(),
+            user: e.user.to_bytes().to_vec(),
+            creator: e.creator.to_bytes().to_vec(),
+            timestamp: e.timestamp,
+            virtual_token_reserves: e.virtual_token_reserves,
+            virtual_sol_reserves: e.virtual_sol_reserves,
+            real_token_reserves: e.real_token_reserves,
+            token_total_supply: e.token_total_supply,
+            token_program: e.token_program.to_bytes().to_vec(),
+            is_mayhem_mode: e.is_mayhem_mode,
+        }
+    }
+}
+
+impl From<&models::CompleteEvent> for CompleteEvent {
+    fn from(e: &models::CompleteEvent) -> Self {
+        Self {
+            user: e.user.to_bytes().to_vec(),
+            mint: e.mint.to_bytes().to_vec(),
+            bonding_curve: e.bonding_curve.to_bytes().to_vec(),
+            timestamp: e.timestamp,
+        }
+    }
+}
+
+impl From<&models::TradeEvent> for TradeEvent {
+    fn from(e: &models::TradeEvent) -> Self {
+        Self {
+            mint: e.mint.to_bytes().to_vec(),
+            sol_amount: e.sol_amount,
+            token_amount: e.token_amount,
+            is_buy: e.is_buy,
+            user: e.user.to_bytes().to_vec(),
+            timestamp: e.timestamp,
+            virtual_sol_reserves: e.virtual_sol_reserves,
+            virtual_token_reserves: e.virtual_token_reserves,
+            real_sol_reserves: e.real_sol_reserves,
+            real_token_reserves: e.real_token_reserves,
+            fee_recipient: e.fee_recipient.to_bytes().to_vec(),
+            fee_basis_points: e.fee_basis_points,
+            fee: e.fee,
+            creator: e.creator.to_bytes().to_vec(),
+            creator_fee_basis_points: e.creator_fee_basis_points,
+            creator_fee: e.creator_fee,
+            track_volume: e.track_volume,
+            total_unclaimed_tokens: e.total_unclaimed_tokens,
+            total_claimed_tokens: e.total_claimed_tokens,
+            current_sol_volume: e.current_sol_volume,
+            last_update_timestamp: e.last_update_timestamp,
+            ix_name: e.ix_name.clone(),
+        }
+    }
+}
+
+impl From<&models::BuyEvent> for BuyEvent {
+    fn from(e: &models::BuyEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            base_amount_out: e.base_amount_out,
+            max_quote_amount_in: e.max_quote_amount_in,
+            user_base_token_reserves: e.user_base_token_reserves,
+            user_quote_token_reserves: e.user_quote_token_reserves,
+            pool_base_token_reserves: e.pool_base_token_reserves,
+            pool_quote_token_reserves: e.pool_quote_token_reserves,
+            quote_amount_in: e.quote_amount_in,
+            lp_fee_basis_points: e.lp_fee_basis_points,
+            lp_fee: e.lp_fee,
+            protocol_fee_basis_points: e.protocol_fee_basis_points,
+            protocol_fee: e.protocol_fee,
+            quote_amount_in_with_lp_fee: e.quote_amount_in_with_lp_fee,
+            user_quote_amount_in: e.user_quote_amount_in,
+            pool: e.pool.to_bytes().to_vec(),
+            user: e.user.to_bytes().to_vec(),
+            user_base_token_account: e.user_base_token_account.to_bytes().to_vec(),
+            user_quote_token_account: e.user_quote_token_account.to_bytes().to_vec(),
+            protocol_fee_recipient: e.protocol_fee_recipient.to_bytes().to_vec(),
+            protocol_fee_recipient_token_account: e
+                .protocol_fee_recipient_token_account
+                .to_bytes()
+                .to_vec(),
+            coin_creator: e.coin_creator.to_bytes().to_vec(),
+            coin_creator_fee_basis_points: e.coin_creator_fee_basis_points,
+            coin_creator_fee: e.coin_creator_fee,
+            track_volume: e.track_volume,
+            total_unclaimed_tokens: e.total_unclaimed_tokens,
+            total_claimed_tokens: e.total_claimed_tokens,
+            current_sol_volume: e.current_sol_volume,
+            last_update_timestamp: e.last_update_timestamp,
+            min_base_amount_out: e.min_base_amount_out,
+            ix_name: e.ix_name.clone(),
+        }
+    }
+}
+
+impl From<&models::SellEvent> for SellEvent {
+    fn from(e: &models::SellEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            base_amount_in: e.base_amount_in,
+            min_quote_amount_out: e.min_quote_amount_out,
+            user_base_token_reserves: e.user_base_token_reserves,
+            user_quote_token_reserves: e.user_quote_token_reserves,
+            pool_base_token_reserves: e.pool_base_token_reserves,
+            pool_quote_token_reserves: e.pool_quote_token_reserves,
+            quote_amount_out: e.quote_amount_out,
+            lp_fee_basis_points: e.lp_fee_basis_points,
+            lp_fee: e.lp_fee,
+            protocol_fee_basis_points: e.protocol_fee_basis_points,
+            protocol_fee: e.protocol_fee,
+            quote_amount_out_without_lp_fee: e.quote_amount_out_without_lp_fee,
+            user_quote_amount_out: e.user_quote_amount_out,
+            pool: e.pool.to_bytes().to_vec(),
+            user: e.user.to_bytes().to_vec(),
+            user_base_token_account: e.user_base_token_account.to_bytes().to_vec(),
+            user_quote_token_account: e.user_quote_token_account.to_bytes().to_vec(),
+            protocol_fee_recipient: e.protocol_fee_recipient.to_bytes().to_vec(),
+            protocol_fee_recipient_token_account: e
+                .protocol_fee_recipient_token_account
+                .to_bytes()
+                .to_vec(),
+            coin_creator: e.coin_creator.to_bytes().to_vec(),
+            coin_creator_fee_basis_points: e.coin_creator_fee_basis_points,
+            coin_creator_fee: e.coin_creator_fee,
+        }
+    }
+}
+
+impl From<&models::CreatePoolEvent> for CreatePoolEvent {
+    fn from(e: &models::CreatePoolEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            index: e.index as u32,
+            creator: e.creator.to_bytes().to_vec(),
+            base_mint: e.base_mint.to_bytes().to_vec(),
+            quote_mint: e.quote_mint.to_bytes().to_vec(),
+            base_mint_decimals: e.base_mint_decimals as u32,
+            quote_mint_decimals: e.quote_mint_decimals as u32,
+            base_amount_in: e.base_amount_in,
+            quote_amount_in: e.quote_amount_in,
+            pool_base_amount: e.pool_base_amount,
+            pool_quote_amount: e.pool_quote_amount,
+            minimum_liquidity: e.minimum_liquidity,
+            initial_liquidity: e.initial_liquidity,
+            lp_token_amount_out: e.lp_token_amount_out,
+            pool_bump: e.pool_bump as u32,
+            pool: e.pool.to_bytes().to_vec(),
+            lp_mint: e.lp_mint.to_bytes().to_vec(),
+            user_base_token_account: e.user_base_token_account.to_bytes().to_vec(),
+            user_quote_token_account: e.user_quote_token_account.to_bytes().to_vec(),
+            coin_creator: e.coin_creator.to_bytes().to_vec(),
+            is_mayhem_mode: e.is_mayhem_mode,
+        }
+    }
+}
+
+impl From<&models::SetParamsEvent> for SetParamsEvent {
+    fn from(e: &models::SetParamsEvent) -> Self {
+        Self {
+            initial_virtual_token_reserves: e.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: e.initial_virtual_sol_reserves,
+            initial_real_token_reserves: e.initial_real_token_reserves,
+            final_real_sol_reserves: e.final_real_sol_reserves,
+            token_total_supply: e.token_total_supply,
+            fee_basis_points: e.fee_basis_points,
+            withdraw_authority: e.withdraw_authority.to_bytes().to_vec(),
+            enable_migrate: e.enable_migrate,
+            pool_migration_fee: e.pool_migration_fee,
+            creator_fee_basis_points: e.creator_fee_basis_points,
+            fee_recipients: e.fee_recipients.iter().map(|key| key.to_bytes().to_vec()).collect(),
+            timestamp: e.timestamp,
+            set_creator_authority: e.set_creator_authority.to_bytes().to_vec(),
+            admin_set_creator_authority: e.admin_set_creator_authority.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::UpdateGlobalAuthorityEvent> for UpdateGlobalAuthorityEvent {
+    fn from(e: &models::UpdateGlobalAuthorityEvent) -> Self {
+        Self {
+            global: e.global.to_bytes().to_vec(),
+            authority: e.authority.to_bytes().to_vec(),
+            new_authority: e.new_authority.to_bytes().to_vec(),
+            timestamp: e.timestamp,
+        }
+    }
+}
+
+impl From<&models::SetCreatorEvent> for SetCreatorEvent {
+    fn from(e: &models::SetCreatorEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            mint: e.mint.to_bytes().to_vec(),
+            bonding_curve: e.bonding_curve.to_bytes().to_vec(),
+            creator: e.creator.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::SetMetaplexCreatorEvent> for SetMetaplexCreatorEvent {
+    fn from(e: &models::SetMetaplexCreatorEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            mint: e.mint.to_bytes().to_vec(),
+            bonding_curve: e.bonding_curve.to_bytes().to_vec(),
+            metadata: e.metadata.to_bytes().to_vec(),
+            creator: e.creator.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::DepositEvent> for DepositEvent {
+    fn from(e: &models::DepositEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            lp_token_amount_out: e.lp_token_amount_out,
+            max_base_amount_in: e.max_base_amount_in,
+            max_quote_amount_in: e.max_quote_amount_in,
+            user_base_token_reserves: e.user_base_token_reserves,
+            user_quote_token_reserves: e.user_quote_token_reserves,
+            pool_base_token_reserves: e.pool_base_token_reserves,
+            pool_quote_token_reserves: e.pool_quote_token_reserves,
+            base_amount_in: e.base_amount_in,
+            quote_amount_in: e.quote_amount_in,
+            lp_mint_supply: e.lp_mint_supply,
+            pool: e.pool.to_bytes().to_vec(),
+            user: e.user.to_bytes().to_vec(),
+            user_base_token_account: e.user_base_token_account.to_bytes().to_vec(),
+            user_quote_token_account: e.user_quote_token_account.to_bytes().to_vec(),
+            user_pool_token_account: e.user_pool_token_account.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::WithdrawEvent> for WithdrawEvent {
+    fn from(e: &models::WithdrawEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            lp_token_amount_in: e.lp_token_amount_in,
+            min_base_amount_out: e.min_base_amount_out,
+            min_quote_amount_out: e.min_quote_amount_out,
+            user_base_token_reserves: e.user_base_token_reserves,
+            user_quote_token_reserves: e.user_quote_token_reserves,
+            pool_base_token_reserves: e.pool_base_token_reserves,
+            pool_quote_token_reserves: e.pool_quote_token_reserves,
+            base_amount_out: e.base_amount_out,
+            quote_amount_out: e.quote_amount_out,
+            lp_mint_supply: e.lp_mint_supply,
+            pool: e.pool.to_bytes().to_vec(),
+            user: e.user.to_bytes().to_vec(),
+            user_base_token_account: e.user_base_token_account.to_bytes().to_vec(),
+            user_quote_token_account: e.user_quote_token_account.to_bytes().to_vec(),
+            user_pool_token_account: e.user_pool_token_account.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::CollectCreatorFeeEvent> for CollectCreatorFeeEvent {
+    fn from(e: &models::CollectCreatorFeeEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            creator: e.creator.to_bytes().to_vec(),
+            creator_fee: e.creator_fee,
+        }
+    }
+}
+
+impl From<&models::CollectCoinCreatorFeeEvent> for CollectCoinCreatorFeeEvent {
+    fn from(e: &models::CollectCoinCreatorFeeEvent) -> Self {
+        Self {
+            timestamp: e.timestamp,
+            coin_creator: e.coin_creator.to_bytes().to_vec(),
+            coin_creator_fee: e.coin_creator_fee,
+            coin_creator_vault_ata: e.coin_creator_vault_ata.to_bytes().to_vec(),
+            coin_creator_token_account: e.coin_creator_token_account.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::MigrateEvent> for MigrateEvent {
+    fn from(e: &models::MigrateEvent) -> Self {
+        Self {
+            user: e.user.to_bytes().to_vec(),
+            mint: e.mint.to_bytes().to_vec(),
+            mint_amount: e.mint_amount,
+            sol_amount: e.sol_amount,
+            pool_migration_fee: e.pool_migration_fee,
+            bonding_curve: e.bonding_curve.to_bytes().to_vec(),
+            timestamp: e.timestamp,
+            pool: e.pool.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<&models::PumpEvent> for pump_event_envelope::Event {
+    fn from(event: &models::PumpEvent) -> Self {
+        match event {
+            models::PumpEvent::Create(e) => Self::Create(e.into()),
+            models::PumpEvent::CreateV2(e) => Self::CreateV2(e.into()),
+            models::PumpEvent::Complete(e) => Self::Complete(e.into()),
+            models::PumpEvent::Trade(e) => Self::Trade(e.into()),
+            models::PumpEvent::Buy(e) => Self::Buy(e.into()),
+            models::PumpEvent::Sell(e) => Self::Sell(e.into()),
+            models::PumpEvent::CreatePool(e) => Self::CreatePool(e.into()),
+            models::PumpEvent::SetParams(e) => Self::SetParams(e.into()),
+            models::PumpEvent::UpdateGlobalAuthority(e) => Self::UpdateGlobalAuthority(e.into()),
+            models::PumpEvent::SetCreator(e) => Self::SetCreator(e.into()),
+            models::PumpEvent::SetMetaplexCreator(e) => Self::SetMetaplexCreator(e.into()),
+            models::PumpEvent::Deposit(e) => Self::Deposit(e.into()),
+            models::PumpEvent::Withdraw(e) => Self::Withdraw(e.into()),
+            models::PumpEvent::CollectCreatorFee(e) => Self::CollectCreatorFee(e.into()),
+            models::PumpEvent::CollectCoinCreatorFee(e) => Self::CollectCoinCreatorFee(e.into()),
+            models::PumpEvent::Migrate(e) => Self::Migrate(e.into()),
+        }
+    }
+}
+
+impl PumpEventEnvelope {
+    /// 由事件和上下文构建消息
+    pub fn new(event: &models::PumpEvent, ctx: &EventContext) -> Self {
+        Self {
+            slot: ctx.slot,
+            tx_index: ctx.tx_index,
+            signature: ctx.signature.as_ref().to_vec(),
+            block_time: ctx.block_time.unwrap_or_else(|| event.timestamp()),
+            received_at_ms: ctx
+                .received_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            event: Some(event.into()),
+        }
+    }
+}
+
+/// 把事件和上下文编码为 `PumpEventEnvelope` 的 Protobuf 字节
+pub fn encode_event(event: &models::PumpEvent, ctx: &EventContext) -> Vec<u8> {
+    PumpEventEnvelope::new(event, ctx).encode_to_vec()
+}
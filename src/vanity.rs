@@ -0,0 +1,284 @@
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::error::{Error, Result};
+
+/// Base58 字母表
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// 工作线程每生成这么多个密钥对汇总一次尝试次数并检查是否需要停止
+const BATCH: u64 = 256;
+
+/// Pump mint 地址约定的后缀
+pub const PUMP_SUFFIX: &str = "pump";
+
+/// 碰撞进度
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrindProgress {
+    /// 已尝试的密钥对数量
+    pub attempts: u64,
+    /// 已用时间
+    pub elapsed: Duration,
+    /// 找到一个匹配地址的期望尝试次数
+    pub expected_attempts: f64,
+}
+
+impl GrindProgress {
+    /// 每秒尝试次数
+    pub fn attempts_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.attempts as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// 按当前速度找到匹配地址的期望剩余时间，尚无速度数据时为 None
+    ///
+    /// 每次尝试相互独立，剩余时间的期望与已尝试次数无关。
+    pub fn estimated_remaining(&self) -> Option<Duration> {
+        let rate = self.attempts_per_sec();
+        (rate > 0.0).then(|| Duration::from_secs_f64(self.expected_attempts / rate))
+    }
+}
+
+/// mint 靓号地址碰撞
+///
+/// 多线程随机生成密钥对，直到公钥的 Base58 编码以指定后缀结尾。
+/// Pump 代币的 mint 地址约定以 `pump` 结尾，创建代币时可以用碰撞出的密钥对作为 mint 签名者。
+///
+/// 后缀每多一个字符，期望尝试次数乘以 58（`pump` 约 1100 万次）。
+#[derive(Clone, Debug)]
+pub struct VanityGrinder {
+    suffix: String,
+    threads: usize,
+}
+
+impl VanityGrinder {
+    /// 创建碰撞器，后缀只能包含 Base58 字符，默认使用所有可用 CPU 核心
+    pub fn new(suffix: &str) -> Result<Self> {
+        if suffix.is_empty() {
+            return Err(Error::VanityPattern("后缀不能为空".to_string()));
+        }
+        if let Some(c) = suffix.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+            return Err(Error::VanityPattern(format!("'{}' 不是 Base58 字符", c)));
+        }
+        // 32 字节公钥的 Base58 编码最长 44 个字符
+        if suffix.len() > 44 {
+            return Err(Error::VanityPattern("后缀长度超过公钥编码长度".to_string()));
+        }
+        Ok(Self {
+            suffix: suffix.to_string(),
+            threads: 0,
+        })
+    }
+
+    /// 设置工作线程数，0 表示使用所有可用 CPU 核心
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// 找到一个匹配地址的期望尝试次数
+    pub fn expected_attempts(&self) -> f64 {
+        58f64.powi(self.suffix.len() as i32)
+    }
+
+    /// 在后台线程中开始碰撞，立即返回句柄
+    pub fn spawn(&self) -> GrindHandle {
+        let threads = match self.threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        let shared = Arc::new(Shared {
+            found: Mutex::new(None),
+            done: Condvar::new(),
+            stop: AtomicBool::new(false),
+            attempts: AtomicU64::new(0),
+            running: AtomicUsize::new(threads),
+            started: Instant::now(),
+            expected_attempts: self.expected_attempts(),
+        });
+        for _ in 0..threads {
+            let shared = Arc::clone(&shared);
+            let suffix = self.suffix.clone();
+            thread::spawn(move || shared.work(&suffix));
+        }
+        GrindHandle {
+            owner: Arc::new(Owner { shared }),
+        }
+    }
+
+    /// 阻塞直到找到匹配的密钥对，每隔 `interval` 调用一次 `progress`
+    ///
+    /// 在 `progress` 中返回 false 可以取消碰撞，此时返回 None。
+    pub fn grind<F>(&self, interval: Duration, progress: F) -> Option<Keypair>
+    where
+        F: FnMut(&GrindProgress) -> bool,
+    {
+        let handle = self.spawn();
+        handle.wait_with_progress(interval, progress)
+    }
+}
+
+/// 阻塞碰撞以 `suffix` 结尾的 mint 密钥对，`threads` 为 0 时使用所有可用 CPU 核心
+///
+/// 需要进度或取消时使用 [`VanityGrinder`]。
+pub fn grind_mint_suffix(suffix: &str, threads: usize) -> Result<Keypair> {
+    let handle = VanityGrinder::new(suffix)?.with_threads(threads).spawn();
+    handle
+        .wait()
+        .ok_or_else(|| Error::Unknown("靓号碰撞被取消".to_string()))
+}
+
+/// 后台碰撞任务的句柄
+///
+/// 克隆后的句柄共享同一个任务；所有句柄都被丢弃时任务自动取消。
+#[derive(Clone)]
+pub struct GrindHandle {
+    owner: Arc<Owner>,
+}
+
+impl GrindHandle {
+    /// 取消碰撞，工作线程会在当前批次结束后退出
+    pub fn cancel(&self) {
+        self.shared().cancel();
+    }
+
+    /// 是否已找到匹配地址或已取消
+    pub fn is_finished(&self) -> bool {
+        self.shared().stop.load(Ordering::Relaxed)
+    }
+
+    /// 当前进度
+    pub fn progress(&self) -> GrindProgress {
+        self.shared().progress()
+    }
+
+    /// 阻塞直到找到匹配的密钥对，取消时返回 None
+    ///
+    /// 密钥对只会交给第一个取得结果的调用者，其他等待者返回 None。
+    pub fn wait(&self) -> Option<Keypair> {
+        self.wait_inner(None, |_| true)
+    }
+
+    /// 与 [`wait`](Self::wait) 相同，等待期间每隔 `interval` 调用一次 `progress`，
+    /// `progress` 返回 false 时取消碰撞
+    pub fn wait_with_progress<F>(&self, interval: Duration, progress: F) -> Option<Keypair>
+    where
+        F: FnMut(&GrindProgress) -> bool,
+    {
+        self.wait_inner(Some(interval), progress)
+    }
+
+    fn wait_inner<F>(&self, interval: Option<Duration>, mut progress: F) -> Option<Keypair>
+    where
+        F: FnMut(&GrindProgress) -> bool,
+    {
+        let shared = self.shared();
+        let mut found = shared.lock();
+        loop {
+            if let Some(keypair) = found.take() {
+                return Some(keypair);
+            }
+            if shared.stop.load(Ordering::Relaxed) && shared.running.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+            let Some(interval) = interval else {
+                found = shared.done.wait(found).unwrap_or_else(|e| e.into_inner());
+                continue;
+            };
+            let (guard, timeout) = shared
+                .done
+                .wait_timeout(found, interval)
+                .unwrap_or_else(|e| e.into_inner());
+            found = guard;
+            if timeout.timed_out() && found.is_none() && !progress(&shared.progress()) {
+                shared.cancel();
+            }
+        }
+    }
+
+    fn shared(&self) -> &Shared {
+        &self.owner.shared
+    }
+}
+
+/// 所有句柄共同持有，最后一个句柄丢弃时取消任务
+struct Owner {
+    shared: Arc<Shared>,
+}
+
+impl Drop for Owner {
+    fn drop(&mut self) {
+        self.shared.cancel();
+    }
+}
+
+struct Shared {
+    found: Mutex<Option<Keypair>>,
+    done: Condvar,
+    stop: AtomicBool,
+    attempts: AtomicU64,
+    /// 仍在运行的工作线程数
+    running: AtomicUsize,
+    started: Instant,
+    expected_attempts: f64,
+}
+
+impl Shared {
+    fn work(&self, suffix: &str) {
+        'outer: while !self.stop.load(Ordering::Relaxed) {
+            for _ in 0..BATCH {
+                let keypair = Keypair::new();
+                if matches_suffix(&keypair.pubkey(), suffix) {
+                    let mut found = self.lock();
+                    if !self.stop.swap(true, Ordering::Relaxed) {
+                        *found = Some(keypair);
+                        self.done.notify_all();
+                    }
+                    break 'outer;
+                }
+            }
+            self.attempts.fetch_add(BATCH, Ordering::Relaxed);
+        }
+        // 最后一个退出的线程负责唤醒等待者，让取消后的 wait 返回
+        if self.running.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let _found = self.lock();
+            self.done.notify_all();
+        }
+    }
+
+    fn cancel(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    fn progress(&self) -> GrindProgress {
+        GrindProgress {
+            attempts: self.attempts.load(Ordering::Relaxed),
+            elapsed: self.started.elapsed(),
+            expected_attempts: self.expected_attempts,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Keypair>> {
+        self.found.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 公钥的 Base58 编码是否以 `suffix` 结尾
+#[inline]
+fn matches_suffix(pubkey: &Pubkey, suffix: &str) -> bool {
+    pubkey.to_string().ends_with(suffix)
+}